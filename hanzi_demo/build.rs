@@ -1,9 +1,12 @@
 use std::env;
-use std::fs;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 fn main() {
+    firmware_info();
+    pack_images();
+
     let sdk_home = env::var("ECOS_SDK_HOME").expect("ECOS_SDK_HOME not set");
     let sdk_path = PathBuf::from(&sdk_home);
 
@@ -132,6 +135,38 @@ fn compile_startup_asm(sdk_path: &Path) {
     println!("cargo:rustc-link-arg={}", start_o.display());
 }
 
+// 在 .bss 前面加一个不清零的 .noinit 段，panic 记录复位后还在
+fn add_noinit_section(content: String) -> String {
+    if content.contains(".noinit") {
+        return content;
+    }
+    // .bss 段开头那一行
+    let bss = content
+        .match_indices('\n')
+        .map(|(i, _)| i)
+        .find(|&i| content[i + 1..].trim_start().starts_with(".bss"));
+    let Some(bss) = bss else {
+        println!(
+            "cargo:warning=no .bss in linker script, panic records will not survive a restart"
+        );
+        return content;
+    };
+    // 放在和 .bss 同一块内存里
+    let region = content[bss..]
+        .split_once('}')
+        .and_then(|(_, rest)| rest.trim_start().strip_prefix('>'))
+        .and_then(|rest| rest.split_whitespace().next())
+        .unwrap_or("RAM")
+        .to_string();
+    let section = format!(
+        "\n    .noinit (NOLOAD) :\n    {{\n        . = ALIGN(4);\n        *(.noinit .noinit.*)\n        . = ALIGN(4);\n    }} > {}\n",
+        region
+    );
+    let mut content = content;
+    content.insert_str(bss, &section);
+    content
+}
+
 fn link_libraries(sdk_path: &Path) {
     let sections_lds = sdk_path.join("board/StarrySkyC1/sections.lds");
     if sections_lds.exists() {
@@ -139,6 +174,7 @@ fn link_libraries(sdk_path: &Path) {
         let dest_lds = PathBuf::from(&out_dir).join("sections.lds");
 
         let content = fs::read_to_string(&sections_lds).expect("Failed to read linker script");
+        let content = add_noinit_section(content);
 
         fs::write(&dest_lds, content).expect("Failed to write modified linker script");
 
@@ -174,3 +210,168 @@ fn link_libraries(sdk_path: &Path) {
     println!("cargo:rustc-link-arg=-Wl,--gc-sections");
     println!("cargo:rustc-link-arg=-nostartfiles");
 }
+
+/// 从 include/config/auto.conf 读固件名和版本，开机画面上显示
+fn firmware_info() {
+    let conf = Path::new("include/config/auto.conf");
+    println!("cargo:rerun-if-changed={}", conf.display());
+
+    let Ok(content) = fs::read_to_string(conf) else {
+        return;
+    };
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match key {
+            "CONFIG_FIRMWARE_NAME" => println!("cargo:rustc-env=HANZI_FIRMWARE_NAME={}", value),
+            "CONFIG_FIRMWARE_VERSION" => {
+                println!("cargo:rustc-env=HANZI_FIRMWARE_VERSION={}", value)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 把 display/images 下的图片打包进固件，生成 `$OUT_DIR/images.rs` 给 src/image.rs 用
+///
+/// png 转成 RLE565（格式见 src/image.rs），bmp 原样打包。
+fn pack_images() {
+    if env::var_os("CARGO_FEATURE_CMD_IMAGE").is_none() {
+        return;
+    }
+
+    let image_dir = Path::new("display/images");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
+    println!("cargo:rerun-if-changed={}", image_dir.display());
+
+    let mut paths: Vec<PathBuf> = match fs::read_dir(image_dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.expect("Failed to get directory entry").path())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+
+    let mut images: Vec<(String, PathBuf)> = Vec::new();
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        let packed = match ext.as_deref() {
+            Some("png") => {
+                let dest = out_dir.join(format!("{}.r565", name));
+                fs::write(&dest, encode_rle565(&path)).expect("Failed to write image");
+                dest
+            }
+            Some("bmp") => path.canonicalize().expect("Invalid path"),
+            _ => continue,
+        };
+
+        if images.iter().any(|(n, _)| n == name) {
+            panic!("duplicate image name: {}", name);
+        }
+        println!("cargo:rerun-if-changed={}", path.display());
+        images.push((name.to_string(), packed));
+    }
+
+    let mut table = String::from("static IMAGES: &[(&str, &[u8])] = &[\n");
+    for (name, path) in &images {
+        table += &format!("    ({:?}, include_bytes!({:?})),\n", name, path);
+    }
+    table += "];\n";
+    fs::write(out_dir.join("images.rs"), table).expect("Failed to write images.rs");
+
+    println!("cargo:rustc-cfg=hanzi_images");
+}
+
+fn encode_rle565(path: &Path) -> Vec<u8> {
+    let file = File::open(path).expect("Failed to open image");
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().expect("Failed to read png header");
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).expect("Failed to decode png");
+    let bytes = &buf[..info.buffer_size()];
+
+    if info.width > u16::MAX as u32 || info.height > u16::MAX as u32 {
+        panic!("image too large: {}", path.display());
+    }
+
+    // 统一成 RGBA 再换算，alpha 小于一半算透明
+    let rgba: Vec<[u8; 4]> = match info.color_type {
+        png::ColorType::Rgba => bytes
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+            .collect(),
+        png::ColorType::Rgb => bytes
+            .chunks_exact(3)
+            .map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks_exact(2)
+            .map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => bytes.iter().map(|&v| [v, v, v, 255]).collect(),
+        png::ColorType::Indexed => panic!("unexpanded palette: {}", path.display()),
+    };
+
+    // 和 src/color.rs 一样四舍五入
+    let scale = |value: u8, max: u16| (value as u16 * max + 127) / 255;
+    let pixels: Vec<Option<u16>> = rgba
+        .iter()
+        .map(|&[r, g, b, a]| {
+            (a >= 128).then(|| scale(r, 31) << 11 | scale(g, 63) << 5 | scale(b, 31))
+        })
+        .collect();
+
+    let transparent = pixels.iter().any(|p| p.is_none());
+    let mut out = Vec::with_capacity(10 + pixels.len() * 2);
+    out.extend_from_slice(b"R565");
+    out.push(1);
+    out.push(transparent as u8);
+    out.extend_from_slice(&(info.width as u16).to_le_bytes());
+    out.extend_from_slice(&(info.height as u16).to_le_bytes());
+
+    let mut i = 0;
+    while i < pixels.len() {
+        let same = pixels[i..]
+            .iter()
+            .take(64)
+            .take_while(|&&p| p == pixels[i])
+            .count();
+        match pixels[i] {
+            None => {
+                out.push(0x80 | (same - 1) as u8);
+                i += same;
+            }
+            Some(color) if same >= 2 => {
+                out.push(0x40 | (same - 1) as u8);
+                out.extend_from_slice(&color.to_be_bytes());
+                i += same;
+            }
+            Some(_) => {
+                // 原样包一直到碰上透明或者连续相同的像素
+                let start = i;
+                while i < pixels.len() && i - start < 64 {
+                    let repeats = i + 1 < pixels.len() && pixels[i + 1] == pixels[i];
+                    if pixels[i].is_none() || (repeats && i > start) {
+                        break;
+                    }
+                    i += 1;
+                }
+                out.push((i - start - 1) as u8);
+                for color in pixels[start..i].iter().flatten() {
+                    out.extend_from_slice(&color.to_be_bytes());
+                }
+            }
+        }
+    }
+    out
+}
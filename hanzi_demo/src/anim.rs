@@ -0,0 +1,256 @@
+//! 简单的动画引擎：缓动函数 + 补间 + 时间线
+//!
+//! 时间线由一串按顺序执行的步骤组成，每个步骤有自己的时长，
+//! 每一帧用 `DisplayBackend::now_ms` 的当前时刻 `tick` 一次，步骤回调里根据进度用 `Tween` 算出位置、颜色、缩放再去画。
+
+use crate::*;
+
+use embedded_graphics::prelude::*;
+
+// ===========================================
+// 缓动函数
+// ============================================
+
+/// 缓动函数，输入输出都是 0.0 ~ 1.0（`OutBack` 会短暂超过 1.0）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Easing {
+    Linear,
+    /// 慢进
+    EaseIn,
+    /// 慢出
+    EaseOut,
+    /// 慢进慢出
+    EaseInOut,
+    /// 冲过头再回弹
+    OutBack,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    let u = -2.0 * t + 2.0;
+                    1.0 - u * u / 2.0
+                }
+            }
+            Easing::OutBack => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+                let u = t - 1.0;
+                1.0 + C3 * u * u * u + C1 * u * u
+            }
+        }
+    }
+}
+
+// ===========================================
+// 补间
+// ============================================
+
+/// 可以在两个值之间插值的类型
+pub(crate) trait Lerp: Copy {
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+// core 里没有 f32::round
+fn round(v: f32) -> i32 {
+    if v >= 0.0 {
+        (v + 0.5) as i32
+    } else {
+        (v - 0.5) as i32
+    }
+}
+
+impl Lerp for f32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for i32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        round((self as f32).lerp(to as f32, t))
+    }
+}
+
+impl Lerp for u32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        round((self as f32).lerp(to as f32, t)).max(0) as u32
+    }
+}
+
+impl Lerp for Point {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Point::new(self.x.lerp(to.x, t), self.y.lerp(to.y, t))
+    }
+}
+
+// 按各通道自己的位宽插值，Rgb565 和 Rgb888 都适用
+impl Lerp for DisplayColor {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        let channel = |a: u8, b: u8| (a as i32).lerp(b as i32, t).clamp(0, 255) as u8;
+        DisplayColor::new(
+            channel(self.r(), to.r()),
+            channel(self.g(), to.g()),
+            channel(self.b(), to.b()),
+        )
+    }
+}
+
+/// 补间：从 `from` 到 `to`，按缓动函数插值
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Tween<T> {
+    from: T,
+    to: T,
+    easing: Easing,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(from: T, to: T) -> Self {
+        Self {
+            from,
+            to,
+            easing: Easing::Linear,
+        }
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// 取进度 `progress`（0.0 ~ 1.0）处的值
+    pub fn at(&self, progress: f32) -> T {
+        self.from.lerp(self.to, self.easing.apply(progress))
+    }
+}
+
+// ===========================================
+// 时间线
+// ============================================
+
+/// 传给步骤回调的当前帧信息
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Frame {
+    /// 本步骤已经过的时间（毫秒）
+    pub elapsed: u32,
+    /// 本步骤的进度，0.0 ~ 1.0，最后一帧一定是 1.0
+    pub progress: f32,
+}
+
+/// 时间线状态
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum TimelineState {
+    Running,
+    Finished,
+    Cancelled,
+}
+
+struct Step<'a, C> {
+    duration: u32,
+    action: Option<Box<dyn FnMut(&mut C, Frame) + 'a>>,
+}
+
+/// 时间线：按顺序执行的动画步骤
+///
+/// `C` 是步骤回调拿到的上下文，字体演示里就是 `DisplayManager`。
+pub(crate) struct Timeline<'a, C> {
+    steps: Vec<Step<'a, C>>,
+    current: usize,
+    step_start: Option<u32>,
+    cancelled: bool,
+}
+
+impl<'a, C> Timeline<'a, C> {
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            current: 0,
+            step_start: None,
+            cancelled: false,
+        }
+    }
+
+    /// 立即执行一次的步骤
+    pub fn call<F>(mut self, mut action: F) -> Self
+    where
+        F: FnMut(&mut C) + 'a,
+    {
+        self.steps.push(Step {
+            duration: 0,
+            action: Some(Box::new(move |ctx, _| action(ctx))),
+        });
+        self
+    }
+
+    /// 持续 `duration` 毫秒的步骤，每帧回调一次
+    pub fn then<F>(mut self, duration: u32, action: F) -> Self
+    where
+        F: FnMut(&mut C, Frame) + 'a,
+    {
+        self.steps.push(Step {
+            duration,
+            action: Some(Box::new(action)),
+        });
+        self
+    }
+
+    /// 什么都不做，等待 `duration` 毫秒
+    pub fn wait(mut self, duration: u32) -> Self {
+        self.steps.push(Step {
+            duration,
+            action: None,
+        });
+        self
+    }
+
+    /// 取消播放，下一次 `tick` 返回 `Cancelled`
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+
+    /// 推进到时刻 `now`（毫秒），执行所有到期的步骤
+    ///
+    /// 一帧里可能跨过好几个步骤，跨过的步骤都会收到一次进度为 1.0 的回调，
+    /// 保证每一步的最终画面都会画出来。
+    pub fn tick(&mut self, ctx: &mut C, now: u32) -> TimelineState {
+        if self.cancelled {
+            return TimelineState::Cancelled;
+        }
+
+        while let Some(step) = self.steps.get_mut(self.current) {
+            let start = *self.step_start.get_or_insert(now);
+            let elapsed = now.wrapping_sub(start);
+
+            if elapsed < step.duration {
+                if let Some(action) = step.action.as_mut() {
+                    let progress = elapsed as f32 / step.duration as f32;
+                    action(ctx, Frame { elapsed, progress });
+                }
+                return TimelineState::Running;
+            }
+
+            if let Some(action) = step.action.as_mut() {
+                action(
+                    ctx,
+                    Frame {
+                        elapsed: step.duration,
+                        progress: 1.0,
+                    },
+                );
+            }
+            // 多出来的时间留给下一步
+            self.step_start = Some(start.wrapping_add(step.duration));
+            self.current += 1;
+        }
+
+        TimelineState::Finished
+    }
+}
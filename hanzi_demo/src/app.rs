@@ -0,0 +1,174 @@
+//! 交互应用
+//!
+//! 字体演示、阅读器、编辑器、贪吃蛇这种进去以后接管按键和屏幕的东西都实现 [`App`]，
+//! 由主循环里唯一的 [`Scheduler`] 来跑：按键转给当前应用、按帧间隔调 `on_tick`、需要时重画并 `present`。
+//! 应用自己不再写轮询串口的死循环，主循环照常处理模拟器窗口事件、闲置变暗这些事。
+//!
+//! 任何应用里按 Ctrl+C 都直接退出，不用记每个应用自己的退出键。
+
+use crate::*;
+
+use crate::heap;
+use crate::i18n::{self, tr};
+
+/// 强制退出键（Ctrl+C）
+const EXIT_KEY: u8 = 0x03;
+
+/// 应用处理完按键以后要怎样
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Flow {
+    /// 继续运行，画面没变
+    #[cfg_attr(
+        not(any(feature = "cmd-snake", feature = "cmd-font", feature = "cmd-text")),
+        allow(dead_code)
+    )]
+    Continue,
+    /// 继续运行，要重画
+    #[cfg_attr(not(feature = "cmd-text"), allow(dead_code))]
+    Redraw,
+    /// 退出，回到命令行或主菜单
+    Exit,
+}
+
+/// 交互应用的生命周期
+///
+/// 调用顺序：`on_enter` → `draw` →（`on_key` / `on_tick`，要重画时再 `draw`）... → `on_exit`。
+pub(crate) trait App {
+    /// 名字，打印用
+    fn name(&self) -> &'static str;
+
+    /// 进入时调用一次，打印帮助、准备资源；返回 `false` 表示起不来，直接退出
+    #[cfg_attr(
+        not(any(feature = "cmd-snake", feature = "cmd-font", feature = "cmd-text")),
+        allow(dead_code)
+    )]
+    fn on_enter(&mut self, _manager: &mut DisplayManager) -> bool {
+        true
+    }
+
+    /// 收到一个按键
+    fn on_key(&mut self, manager: &mut DisplayManager, byte: u8) -> Flow;
+
+    /// 每隔 [`App::frame_ms`] 调一次，返回 `true` 表示画面变了要重画
+    fn on_tick(&mut self, _manager: &mut DisplayManager, _now: u32) -> bool {
+        false
+    }
+
+    /// 按当前状态画整个画面
+    fn draw(&mut self, manager: &mut DisplayManager);
+
+    /// 退出前调用一次
+    fn on_exit(&mut self, _manager: &mut DisplayManager) {}
+
+    /// 帧间隔（毫秒），0 就是主循环每轮都调
+    fn frame_ms(&self) -> u32 {
+        16
+    }
+
+    /// 是否在自己动（放动画、游戏进行中），这时不算闲置
+    fn is_busy(&self) -> bool {
+        false
+    }
+}
+
+/// 跑当前应用，同一时间只有一个
+#[derive(Default)]
+pub(crate) struct Scheduler {
+    app: Option<Box<dyn App>>,
+    last_frame: u32,
+    /// 应用启动时的堆用量
+    heap_on_enter: Option<usize>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 有应用在跑时按键都交给它
+    pub fn is_running(&self) -> bool {
+        self.app.is_some()
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.app.as_ref().is_some_and(|app| app.is_busy())
+    }
+
+    /// 启动应用，正在跑的先退出
+    #[cfg_attr(
+        not(any(feature = "cmd-snake", feature = "cmd-font", feature = "cmd-text")),
+        allow(dead_code)
+    )]
+    pub fn start(&mut self, manager: &mut DisplayManager, mut app: Box<dyn App>) {
+        self.stop(manager);
+        let heap_before = heap::mark();
+        if !app.on_enter(manager) {
+            println!("{}\r", tr!(i18n::APP_START_FAILED, app.name()));
+            return;
+        }
+        self.heap_on_enter = Some(heap_before);
+        app.draw(manager);
+        manager.present();
+        self.last_frame = manager.now_ms();
+        self.app = Some(app);
+    }
+
+    /// 退出当前应用
+    pub fn stop(&mut self, manager: &mut DisplayManager) {
+        if let Some(mut app) = self.app.take() {
+            app.on_exit(manager);
+            println!("\r\n{}\r", tr!(i18n::APP_EXITED, app.name()));
+            if let Some(before) = self.heap_on_enter.take() {
+                let peak = heap::mark_peak();
+                log::info!(
+                    "{}",
+                    tr!(
+                        i18n::APP_MEM_PEAK,
+                        app.name(),
+                        peak,
+                        peak.saturating_sub(before)
+                    )
+                );
+            }
+        }
+    }
+
+    /// 把按键交给当前应用，应用因此退出时返回 [`Flow::Exit`]
+    pub fn handle_key(&mut self, manager: &mut DisplayManager, byte: u8) -> Flow {
+        let Some(app) = self.app.as_mut() else {
+            return Flow::Exit;
+        };
+        let flow = if byte == EXIT_KEY {
+            Flow::Exit
+        } else {
+            app.on_key(manager, byte)
+        };
+        match flow {
+            Flow::Continue => {}
+            Flow::Redraw => {
+                app.draw(manager);
+                manager.present();
+            }
+            Flow::Exit => self.stop(manager),
+        }
+        flow
+    }
+
+    /// 主循环每轮调一次，到了帧间隔就推进一帧
+    pub fn tick(&mut self, manager: &mut DisplayManager) {
+        let Some(app) = self.app.as_mut() else {
+            return;
+        };
+        let now = manager.now_ms();
+        if now.wrapping_sub(self.last_frame) < app.frame_ms() {
+            // 还没到下一帧，睡 1ms 让出 CPU，串口有 FIFO 不会丢键
+            manager.delay_ms(1);
+            return;
+        }
+        self.last_frame = now;
+        if app.on_tick(manager, now) {
+            app.draw(manager);
+            manager.present();
+        }
+    }
+}
@@ -0,0 +1,648 @@
+//! 显示后端
+//!
+//! 应用代码只通过 [`DisplayBackend`] 访问屏幕，不用关心底下是 ST7735、LED 点阵还是模拟器窗口，
+//! 和目标有关的 cfg 都收在这个文件里。以后接新的屏幕，再实现一次这个 trait 即可。
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+use crate::settings;
+
+use crate::geometry::Geometry;
+#[allow(unused)] // 只有部分后端用得到
+use crate::geometry::Rotation;
+use core::fmt::Debug;
+use embedded_graphics::prelude::*;
+#[allow(unused)] // 硬件真实环境需要
+use embedded_hal::delay::DelayNs;
+
+#[cfg(feature = "target-st7735")]
+use crate::backlight::PwmBacklight;
+#[cfg(feature = "target-st7735")]
+use crate::burst::St7735Burst;
+#[cfg(feature = "framebuffer")]
+use crate::framebuffer::FrameBuffer;
+#[cfg(feature = "target-st7735")]
+use crate::st7735::{self, DisplayConfig, TimerDelay};
+
+#[cfg(feature = "target-led8x8")]
+use crate::led8x8::GpioMatrix;
+#[cfg(feature = "target-led8x8")]
+use crate::matrix::{LedMatrix, LedMatrixManager, MatrixDriver};
+#[cfg(feature = "target-led8x8")]
+pub(crate) type DisplayManager = LedMatrixManager<GpioMatrix>;
+
+#[cfg(feature = "target-ui-sim")]
+use crate::record::{self, Recorder};
+#[cfg(feature = "target-ui-sim")]
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
+
+/// 显示后端
+pub(crate) trait DisplayBackend {
+    /// 绘制目标
+    type Target: DrawTarget<Color = DisplayColor, Error: Debug>;
+
+    /// 取得绘制目标，所有绘制都画到这里
+    fn target(&mut self) -> &mut Self::Target;
+
+    /// 把画好的内容显示出来
+    ///
+    /// 直接写屏的后端什么都不用做，模拟器在这里刷新窗口。
+    fn present(&mut self);
+
+    /// 屏幕几何（面板尺寸和安装方向）
+    fn geometry(&self) -> Geometry;
+
+    /// 应用看到的屏幕尺寸（像素），已经按旋转互换过宽高，布局都以它为准
+    fn size(&self) -> Size {
+        self.geometry().size()
+    }
+
+    /// 阻塞延时
+    fn delay_ms(&mut self, ms: u32);
+
+    /// 毫秒时钟，会回绕，计算时间差请用 `wrapping_sub`
+    fn now_ms(&self) -> u32;
+
+    /// 处理窗口事件，没有窗口的后端什么都不用做
+    fn poll_events(&mut self) {}
+
+    /// 当前背光亮度（0-100），不支持调光时返回 `None`
+    fn brightness(&self) -> Option<u8> {
+        None
+    }
+
+    /// 设置背光亮度（0-100），不支持调光时返回 false
+    fn set_brightness(&mut self, _level: u8) -> bool {
+        false
+    }
+
+    /// 最近一次统计的刷新情况，不统计的后端返回 `None`
+    fn frame_stats(&self) -> Option<FrameStats> {
+        None
+    }
+
+    /// 开关每秒打印一次帧率，不统计的后端返回 false
+    fn set_fps_report(&mut self, _on: bool) -> bool {
+        false
+    }
+
+    /// 读回第 `y` 行像素（应用坐标，`row` 长度等于屏宽），截图用
+    ///
+    /// 屏幕本身读不回来，要有一份内存里的影子缓冲才行，没有时返回 false。
+    fn read_row(&self, _y: u32, _row: &mut [DisplayColor]) -> bool {
+        false
+    }
+
+    /// 开始把每一帧录成动图存到 `path`
+    fn start_recording(&mut self, _path: &str) -> Result<(), String> {
+        Err(tr!(i18n::RECORD_UNSUPPORTED).into())
+    }
+
+    /// 停止录制并写完文件，返回写了多少帧
+    fn stop_recording(&mut self) -> Result<u32, String> {
+        Err(tr!(i18n::RECORD_UNSUPPORTED).into())
+    }
+}
+
+// ===========================================
+// 屏幕几何
+// ============================================
+
+/// 解析设置 `display` 的值，比如 `160x128` 或 `160x128@90`，这个后端用不了的尺寸返回 `None`
+pub(crate) fn parse_geometry(text: &str) -> Option<Geometry> {
+    let geometry = Geometry::parse(text)?;
+    // 点阵按块缩到 8x8，画布宽高得是 8 的倍数
+    #[cfg(feature = "target-led8x8")]
+    if !geometry.width.is_multiple_of(8) || !geometry.height.is_multiple_of(8) {
+        return None;
+    }
+    Some(geometry)
+}
+
+// 设置里的屏幕几何，存进去之前检查过，解析不了只可能是默认值写错了
+fn configured_geometry() -> Geometry {
+    parse_geometry(&settings::text(settings::Key::Display)).unwrap_or(Geometry::new(128, 128))
+}
+
+/// 屏幕几何，按设置 `display` 来，重启后生效
+#[cfg(not(feature = "target-ui-sim"))]
+pub(crate) fn default_geometry() -> Geometry {
+    configured_geometry()
+}
+
+// ===========================================
+// 帧率统计
+// ============================================
+
+/// 一个统计周期（约 1 秒）内的刷新情况
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct FrameStats {
+    /// 每秒帧数 x100，打印时保留两位小数
+    pub fps_x100: u32,
+    /// 平均每帧写了几块区域
+    pub rects_per_frame: u32,
+    /// 平均每帧写了多少像素
+    pub pixels_per_frame: u32,
+}
+
+impl core::fmt::Display for FrameStats {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let fps = format!("{}.{:02}", self.fps_x100 / 100, self.fps_x100 % 100);
+        f.write_str(&tr!(
+            i18n::FPS_STATS,
+            fps,
+            self.rects_per_frame,
+            self.pixels_per_frame
+        ))
+    }
+}
+
+/// 帧率计数器，后端每真正刷新一帧调用一次 `frame`
+///
+/// 只有模拟器和带帧缓冲的 ST7735 统计。
+#[cfg_attr(
+    not(any(feature = "target-ui-sim", feature = "framebuffer")),
+    allow(dead_code)
+)]
+pub(crate) struct FpsCounter {
+    window_start: Option<u32>,
+    frames: u32,
+    rects: u32,
+    pixels: u32,
+    last: FrameStats,
+    /// 每个统计周期结束时打印一次
+    pub report: bool,
+}
+
+#[cfg_attr(
+    not(any(feature = "target-ui-sim", feature = "framebuffer")),
+    allow(dead_code)
+)]
+impl FpsCounter {
+    const WINDOW_MS: u32 = 1000;
+
+    pub fn new() -> Self {
+        Self {
+            window_start: None,
+            frames: 0,
+            rects: 0,
+            pixels: 0,
+            last: FrameStats::default(),
+            report: false,
+        }
+    }
+
+    /// 记一帧，`rects`/`pixels` 是这一帧写屏的块数和像素数
+    pub fn frame(&mut self, now: u32, rects: u32, pixels: u32) {
+        let start = *self.window_start.get_or_insert(now);
+        self.frames += 1;
+        self.rects += rects;
+        self.pixels += pixels;
+
+        let elapsed = now.wrapping_sub(start);
+        if elapsed >= Self::WINDOW_MS {
+            self.last = FrameStats {
+                fps_x100: self.frames * 100_000 / elapsed,
+                rects_per_frame: self.rects / self.frames,
+                pixels_per_frame: self.pixels / self.frames,
+            };
+            if self.report {
+                log::info!(target: "fps", "{}", self.last);
+            }
+            self.window_start = Some(now);
+            self.frames = 0;
+            self.rects = 0;
+            self.pixels = 0;
+        }
+    }
+
+    pub fn stats(&self) -> FrameStats {
+        self.last
+    }
+}
+
+// ===========================================
+// ST7735
+// ============================================
+
+#[cfg(feature = "target-st7735")]
+pub(crate) struct DisplayManager {
+    pub display: St7735Burst,
+    pub delay: TimerDelay,
+    geometry: Geometry,
+    backlight: PwmBacklight,
+    #[cfg(feature = "framebuffer")]
+    framebuffer: FrameBuffer,
+    #[cfg(feature = "framebuffer")]
+    fps: FpsCounter,
+}
+
+#[cfg(feature = "target-st7735")]
+impl DisplayBackend for DisplayManager {
+    #[cfg(feature = "framebuffer")]
+    type Target = FrameBuffer;
+    #[cfg(not(feature = "framebuffer"))]
+    type Target = St7735Burst;
+
+    #[cfg(feature = "framebuffer")]
+    fn target(&mut self) -> &mut Self::Target {
+        &mut self.framebuffer
+    }
+
+    #[cfg(not(feature = "framebuffer"))]
+    fn target(&mut self) -> &mut Self::Target {
+        &mut self.display
+    }
+
+    // 把帧缓冲里改过的区域写到屏上
+    #[cfg(feature = "framebuffer")]
+    fn present(&mut self) {
+        match self.framebuffer.flush(&mut self.display) {
+            Ok(info) if info.rects > 0 => {
+                let now = self.now_ms();
+                self.fps.frame(now, info.rects, info.pixels);
+            }
+            Ok(_) => {}
+            Err(e) => log::error!("{}", tr!(i18n::DISPLAY_FLUSH_FAILED, format!("{:?}", e))),
+        }
+    }
+
+    #[cfg(not(feature = "framebuffer"))]
+    fn present(&mut self) {}
+
+    fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay.delay_ms(ms);
+    }
+
+    fn now_ms(&self) -> u32 {
+        ecos_ssc1::Timer::get_tick()
+    }
+
+    fn brightness(&self) -> Option<u8> {
+        Some(self.backlight.level())
+    }
+
+    fn set_brightness(&mut self, level: u8) -> bool {
+        self.backlight.set_level(level);
+        true
+    }
+
+    #[cfg(feature = "framebuffer")]
+    fn frame_stats(&self) -> Option<FrameStats> {
+        Some(self.fps.stats())
+    }
+
+    #[cfg(feature = "framebuffer")]
+    fn set_fps_report(&mut self, on: bool) -> bool {
+        self.fps.report = on;
+        true
+    }
+
+    // 帧缓冲就是影子缓冲，没开 framebuffer 时截不了图
+    #[cfg(feature = "framebuffer")]
+    fn read_row(&self, y: u32, row: &mut [DisplayColor]) -> bool {
+        row.copy_from_slice(self.framebuffer.row(y));
+        true
+    }
+}
+
+/// 没设置 `display` 时的屏幕几何
+#[cfg(feature = "target-st7735")]
+pub(crate) const DEFAULT_GEOMETRY: &str = "128x128";
+
+/// 创建并初始化显示后端，失败时停在这里
+#[cfg(feature = "target-st7735")]
+pub(crate) fn init(geometry: Geometry) -> DisplayManager {
+    // 驱动按逻辑尺寸裁剪，旋转后的宽高要先换好
+    let size = geometry.size();
+    let config = DisplayConfig {
+        dc_pin: 14,
+        rst_pin: None,
+        width: size.width as u16,
+        height: size.height as u16,
+        rgb: true,
+        inverted: false,
+    };
+
+    let mut display = match st7735::create_display(&config) {
+        Ok(display) => {
+            log::info!("{}", tr!(i18n::DISPLAY_MANAGER_CREATED));
+            display
+        }
+        Err(e) => {
+            log::error!("{}", tr!(i18n::DISPLAY_MANAGER_FAILED, format!("{:?}", e)));
+            loop {}
+        }
+    };
+
+    let mut delay = TimerDelay;
+    match st7735::init_display(&mut display, &mut delay) {
+        Ok(_) => log::info!("{}", tr!(i18n::DISPLAY_INIT_OK)),
+        Err(e) => {
+            log::error!("{}", tr!(i18n::DISPLAY_INIT_FAILED, format!("{:?}", e)));
+            loop {}
+        }
+    }
+
+    // init 固定按竖屏初始化，这里再改扫描方向
+    let orientation = match geometry.rotation {
+        Rotation::Deg0 => st7735_lcd::Orientation::Portrait,
+        Rotation::Deg90 => st7735_lcd::Orientation::Landscape,
+        Rotation::Deg180 => st7735_lcd::Orientation::PortraitSwapped,
+        Rotation::Deg270 => st7735_lcd::Orientation::LandscapeSwapped,
+    };
+    if display.set_orientation(&orientation).is_err() {
+        log::warn!("{}", tr!(i18n::DISPLAY_ORIENTATION_FAILED));
+    }
+    log::info!("{}", tr!(i18n::DISPLAY_GEOMETRY, geometry));
+
+    DisplayManager {
+        display: St7735Burst::new(display),
+        delay,
+        geometry,
+        // 背光接 PWM 通道 0
+        backlight: PwmBacklight::new(0),
+        #[cfg(feature = "framebuffer")]
+        framebuffer: FrameBuffer::new(size),
+        #[cfg(feature = "framebuffer")]
+        fps: FpsCounter::new(),
+    }
+}
+
+// ===========================================
+// 8x8 LED 点阵
+// ============================================
+
+#[cfg(feature = "target-led8x8")]
+impl<M: MatrixDriver> DisplayBackend for LedMatrixManager<M> {
+    type Target = LedMatrix;
+
+    fn target(&mut self) -> &mut Self::Target {
+        &mut self.matrix
+    }
+
+    fn present(&mut self) {
+        self.flush();
+    }
+
+    fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    // 延时期间也要一直扫描，否则点阵只会亮一行
+    fn delay_ms(&mut self, ms: u32) {
+        let start = self.now_ms();
+        while self.now_ms().wrapping_sub(start) < ms {
+            self.driver.refresh();
+        }
+    }
+
+    fn now_ms(&self) -> u32 {
+        ecos_ssc1::Timer::get_tick()
+    }
+
+    fn poll_events(&mut self) {
+        self.driver.refresh();
+    }
+
+    fn read_row(&self, y: u32, row: &mut [DisplayColor]) -> bool {
+        self.matrix.read_row(y, row);
+        true
+    }
+}
+
+/// 点阵的逻辑画布默认 16x16，每 2x2 个像素对应一颗灯
+#[cfg(feature = "target-led8x8")]
+pub(crate) const DEFAULT_GEOMETRY: &str = "16x16";
+
+/// 创建 8x8 点阵
+#[cfg(feature = "target-led8x8")]
+pub(crate) fn init(geometry: Geometry) -> DisplayManager {
+    // 行接排针 1-8（高电平点亮），列接排针 9-16（低电平点亮）
+    let driver = GpioMatrix::new(
+        [1, 2, 3, 4, 5, 6, 7, 8],
+        [9, 10, 11, 12, 13, 14, 15, 16],
+        true,
+        false,
+    );
+    let mut manager = DisplayManager::new(geometry, driver);
+    manager.present();
+
+    log::info!("{}", tr!(i18n::LED_MATRIX_READY));
+    manager
+}
+
+// ===========================================
+// 模拟器
+// ============================================
+
+#[cfg(feature = "target-ui-sim")]
+pub struct DisplayManager {
+    pub display: SimulatorDisplay<DisplayColor>,
+    pub window: Window,
+    geometry: Geometry,
+    fps: FpsCounter,
+    recorder: Option<Recorder>,
+    /// 模拟的背光亮度，刷新窗口时按它压暗像素
+    brightness: u8,
+    /// 创建时刻，`now_ms` 从这里算起，不受系统时间调整影响
+    started: std::time::Instant,
+    #[cfg(feature = "need-ecos")]
+    pub delay: ecos_ssc1::delay::Delay,
+    #[cfg(not(feature = "need-ecos"))]
+    pub delay: SimulatorDelay,
+}
+
+#[cfg(feature = "target-ui-sim")]
+#[allow(unused)]
+impl DisplayManager {
+    pub fn update_window(&mut self) {
+        if self.brightness >= 100 {
+            self.window.update(&self.display);
+            return;
+        }
+
+        // 窗口里显示压暗后的副本，绘制目标本身不动，截图和录屏还是原样
+        let level = self.brightness as u16;
+        let scale = |c: u8| (c as u16 * level / 100) as u8;
+        let mut dimmed = SimulatorDisplay::<DisplayColor>::new(self.display.size());
+        let _ = dimmed.draw_iter(self.display.bounding_box().points().map(|point| {
+            let color = self.display.get_pixel(point);
+            Pixel(
+                point,
+                DisplayColor::new(scale(color.r()), scale(color.g()), scale(color.b())),
+            )
+        }));
+        self.window.update(&dimmed);
+    }
+
+    pub fn display_mut(&mut self) -> &mut SimulatorDisplay<DisplayColor> {
+        &mut self.display
+    }
+
+    pub fn window_mut(&mut self) -> &mut Window {
+        &mut self.window
+    }
+}
+
+#[cfg(feature = "target-ui-sim")]
+impl DisplayBackend for DisplayManager {
+    type Target = SimulatorDisplay<DisplayColor>;
+
+    fn target(&mut self) -> &mut Self::Target {
+        &mut self.display
+    }
+
+    // 模拟器每次都整屏刷新窗口
+    fn present(&mut self) {
+        self.update_window();
+        let size = self.size();
+        let now = self.now_ms();
+        self.fps.frame(now, 1, size.width * size.height);
+
+        if let Some(mut recorder) = self.recorder.take() {
+            match recorder.frame(record::capture(self), now) {
+                Ok(()) => self.recorder = Some(recorder),
+                Err(e) => log::error!("{}", tr!(i18n::RECORD_ERROR, e)),
+            }
+        }
+    }
+
+    fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay.delay_ms(ms);
+    }
+
+    fn now_ms(&self) -> u32 {
+        self.started.elapsed().as_millis() as u32
+    }
+
+    fn brightness(&self) -> Option<u8> {
+        Some(self.brightness)
+    }
+
+    fn set_brightness(&mut self, level: u8) -> bool {
+        self.brightness = level.min(100);
+        self.update_window();
+        true
+    }
+
+    fn frame_stats(&self) -> Option<FrameStats> {
+        Some(self.fps.stats())
+    }
+
+    fn set_fps_report(&mut self, on: bool) -> bool {
+        self.fps.report = on;
+        true
+    }
+
+    fn read_row(&self, y: u32, row: &mut [DisplayColor]) -> bool {
+        for (x, color) in row.iter_mut().enumerate() {
+            *color = self.display.get_pixel(Point::new(x as i32, y as i32));
+        }
+        true
+    }
+
+    fn start_recording(&mut self, path: &str) -> Result<(), String> {
+        if self.recorder.is_some() {
+            return Err(tr!(i18n::RECORD_ALREADY).into());
+        }
+        self.recorder = Some(Recorder::create(path, self.size())?);
+        // 先把当前画面记上，不然静止的画面要等到下次变化才有第一帧
+        self.present();
+        Ok(())
+    }
+
+    fn stop_recording(&mut self) -> Result<u32, String> {
+        let recorder = self.recorder.take().ok_or(tr!(i18n::RECORD_NOT_STARTED))?;
+        recorder.finish(self.now_ms())
+    }
+
+    // 处理SDL事件，避免窗口无响应
+    fn poll_events(&mut self) {
+        use embedded_graphics_simulator::SimulatorEvent;
+        use embedded_graphics_simulator::sdl2::Keycode;
+
+        for event in self.window.events() {
+            match event {
+                SimulatorEvent::Quit => std::process::exit(0),
+                SimulatorEvent::KeyDown {
+                    keycode: Keycode::Escape,
+                    ..
+                } => {
+                    std::process::exit(0);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(feature = "target-ui-sim")]
+pub struct SimulatorDelay;
+
+#[cfg(feature = "target-ui-sim")]
+#[allow(unused)]
+impl SimulatorDelay {
+    pub fn delay_ms(&self, ms: u32) {
+        std::thread::sleep(std::time::Duration::from_millis(ms as u64));
+    }
+
+    pub fn delay_us(&self, us: u32) {
+        std::thread::sleep(std::time::Duration::from_micros(us as u64));
+    }
+}
+
+/// 没设置 `display` 时的窗口大小
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const DEFAULT_GEOMETRY: &str = "128x128";
+
+/// 屏幕几何，按设置 `display` 来，环境变量 `HANZI_DISPLAY` 可以临时覆盖，比如 `160x128` 或 `240x240@90`
+#[cfg(feature = "target-ui-sim")]
+pub(crate) fn default_geometry() -> Geometry {
+    let configured = configured_geometry();
+    match std::env::var("HANZI_DISPLAY") {
+        Ok(text) => Geometry::parse(&text).unwrap_or_else(|| {
+            log::warn!("{}", tr!(i18n::SIM_GEOMETRY_INVALID, text, configured));
+            configured
+        }),
+        Err(_) => configured,
+    }
+}
+
+/// 创建模拟器窗口
+///
+/// 窗口直接按旋转后的逻辑尺寸创建，相当于把屏幕转正了看。
+#[cfg(feature = "target-ui-sim")]
+pub(crate) fn init(geometry: Geometry) -> DisplayManager {
+    let display = SimulatorDisplay::<DisplayColor>::new(geometry.size());
+    let output_settings = OutputSettingsBuilder::new().scale(2).build();
+    let window = Window::new("ECOS Simulator", &output_settings);
+
+    let mut manager = DisplayManager {
+        display,
+        window,
+        geometry,
+        fps: FpsCounter::new(),
+        recorder: None,
+        brightness: 100,
+        started: std::time::Instant::now(),
+        #[cfg(feature = "need-ecos")]
+        delay: ecos_ssc1::delay::Delay,
+        #[cfg(not(feature = "need-ecos"))]
+        delay: SimulatorDelay,
+    };
+
+    log::info!("{}", tr!(i18n::SIM_DISPLAY_READY, geometry));
+    manager.update_window();
+
+    manager
+}
@@ -0,0 +1,54 @@
+//! ST7735 背光 PWM
+//!
+//! SoC 只有一组 PWM（4 个通道共用预分频和周期），SDK 的 C 驱动也就是直接写这几个寄存器：
+//! `PSCR` 预分频、`CMP` 周期、`CRn` 通道 n 的比较值，`CTRL` 写 3 启动。
+//! 屏的 BL 脚要接到所选通道的输出上，没接的话调亮度没有反应，但也不影响显示。
+
+const REG_PWM_CTRL: *mut u32 = 0x0300_4000 as *mut u32;
+const REG_PWM_PSCR: *mut u32 = 0x0300_4004 as *mut u32;
+const REG_PWM_CMP: *mut u32 = 0x0300_400c as *mut u32;
+const REG_PWM_CR0: *mut u32 = 0x0300_4010 as *mut u32;
+
+/// 周期取 100，比较值正好就是亮度百分比
+const PERIOD: u32 = 100;
+
+/// 72MHz / 72 / 100 = 10kHz，人眼和相机都看不出闪烁
+const PRESCALER: u32 = 72 - 1;
+
+/// PWM 背光
+pub(crate) struct PwmBacklight {
+    channel: u32,
+    level: u8,
+}
+
+impl PwmBacklight {
+    /// 初始化 PWM，`channel` 是 0-3，上电默认全亮
+    pub fn new(channel: u32) -> Self {
+        let mut backlight = Self {
+            channel: channel.min(3),
+            level: 100,
+        };
+        // SAFETY: 寄存器地址来自 board.h，PWM 只有背光在用
+        unsafe {
+            core::ptr::write_volatile(REG_PWM_PSCR, PRESCALER);
+            core::ptr::write_volatile(REG_PWM_CMP, PERIOD);
+            core::ptr::write_volatile(REG_PWM_CTRL, 3);
+        }
+        backlight.set_level(100);
+        backlight
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// 设置亮度（0-100），0 就是关背光
+    pub fn set_level(&mut self, level: u8) {
+        self.level = level.min(100);
+        let compare = self.level as u32 * PERIOD / 100;
+        // SAFETY: 同上，通道号已经限制在 0-3
+        unsafe {
+            core::ptr::write_volatile(REG_PWM_CR0.add(self.channel as usize), compare);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! ST7735 成块写屏
+//!
+//! st7735-lcd 自带的 `fill_contiguous` 每 32 字节（16 个像素）发一次 SPI 事务，
+//! `fill_solid` 更是退回到逐点 `set_pixel`，每个点都要重新设一次地址窗口。
+//! 这里地址窗口只设一次，然后两个像素拼成一个 u32，
+//! 每次往 QSPI 发送 FIFO 里塞满 32 个字（64 个像素）再启动传输。
+//! SoC 没有给 QSPI 配 DMA，FIFO 突发已经是能拿到的最快写法。
+
+use crate::*;
+
+use crate::st7735::St7735Display;
+
+use ecos_ssc1::qspi;
+use embedded_graphics::{pixelcolor::raw::RawU16, prelude::*, primitives::Rectangle};
+
+/// 一次突发写多少个字（QSPI 发送 FIFO 的深度）
+const BURST_WORDS: usize = 32;
+
+/// 带成块写屏快速路径的 ST7735
+pub(crate) struct St7735Burst {
+    pub display: St7735Display,
+}
+
+impl St7735Burst {
+    pub fn new(display: St7735Display) -> Self {
+        Self { display }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.display.size())
+    }
+
+    // 设好地址窗口并发出 RAMWR，之后 DC 保持高电平，直接往 QSPI 写像素数据就行
+    fn begin_window(&mut self, area: &Rectangle) -> Result<(), ()> {
+        let start = area.top_left;
+        let end = start + area.size - Size::new(1, 1);
+        self.display.set_pixels(
+            start.x as u16,
+            start.y as u16,
+            end.x as u16,
+            end.y as u16,
+            core::iter::empty(),
+        )
+    }
+
+    // 把像素流按 32 个字一批写出去，奇数个像素时最后一个单独发
+    fn write_pixels<I>(&mut self, colors: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = DisplayColor>,
+    {
+        let qspi = qspi::get_qspi().ok_or(())?;
+        let mut words = [0u32; BURST_WORDS];
+        let mut len = 0;
+        let mut pending: Option<u16> = None;
+
+        for color in colors {
+            let raw = RawU16::from(color).into_inner();
+            match pending.take() {
+                None => pending = Some(raw),
+                Some(first) => {
+                    // 先发的像素放在高 16 位，和 write_bytes 的字节序一致
+                    words[len] = (first as u32) << 16 | raw as u32;
+                    len += 1;
+                    if len == BURST_WORDS {
+                        qspi.write_words(&words).map_err(|_| ())?;
+                        len = 0;
+                    }
+                }
+            }
+        }
+
+        if len > 0 {
+            qspi.write_words(&words[..len]).map_err(|_| ())?;
+        }
+        if let Some(last) = pending {
+            qspi.write_u16(last).map_err(|_| ())?;
+        }
+        Ok(())
+    }
+}
+
+impl DrawTarget for St7735Burst {
+    type Color = DisplayColor;
+    type Error = ();
+
+    // 零散的点没法成块，交给驱动逐点画
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.display.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable = area.intersection(&self.bounds());
+        if drawable.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.begin_window(&drawable)?;
+        if drawable == *area {
+            self.write_pixels(colors)
+        } else {
+            // 只发落在屏幕里的部分
+            self.write_pixels(
+                area.points()
+                    .zip(colors)
+                    .filter(|(point, _)| drawable.contains(*point))
+                    .map(|(_, color)| color),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let drawable = area.intersection(&self.bounds());
+        if drawable.is_zero_sized() {
+            return Ok(());
+        }
+
+        self.begin_window(&drawable)?;
+        let count = (drawable.size.width * drawable.size.height) as usize;
+        self.write_pixels(core::iter::repeat_n(color, count))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill_solid(&self.bounds(), color)
+    }
+}
+
+impl OriginDimensions for St7735Burst {
+    fn size(&self) -> Size {
+        self.display.size()
+    }
+}
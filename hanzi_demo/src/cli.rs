@@ -1,6 +1,8 @@
 #[allow(unused)]
 use crate::*;
 
+use crate::i18n::{self, tr};
+
 use embedded_cli::{
     Command,
     arguments::{self, FromArgumentError},
@@ -64,15 +66,15 @@ pub(crate) enum CmdSample<'a> {
 pub(crate) fn handle_sample<'a>(command: CmdSample<'a>) -> Result<(), core::convert::Infallible> {
     match command {
         CmdSample::Hello { name, count } => {
-            let name = name.unwrap_or("世界");
+            let name = name.unwrap_or(tr!(i18n::HELLO_DEFAULT_NAME));
             let count = count.unwrap_or(1);
 
-            println!("\r\n你好，{}！", name);
+            println!("\r\n{}", tr!(i18n::HELLO, name));
 
             if count > 1 {
-                println!("\n重复 {} 次", count);
+                println!("\n{}", tr!(i18n::HELLO_REPEAT, count));
                 for i in 1..count {
-                    println!("\r\n第{}次：你好，{}！", i + 1, name);
+                    println!("\r\n{}", tr!(i18n::HELLO_NTH, i + 1, name));
                 }
             }
 
@@ -80,13 +82,13 @@ pub(crate) fn handle_sample<'a>(command: CmdSample<'a>) -> Result<(), core::conv
             Ok(())
         }
         CmdSample::Plain => {
-            println!("\r\n平平无奇的命令...\r\n");
+            println!("\r\n{}\r\n", tr!(i18n::PLAIN));
             Ok(())
         }
         CmdSample::Array { numbers, operation } => {
-            println!("数组操作:");
-            println!("  原始输入: {}", numbers.numbers);
-            println!("  解析的数字 ({}个):", numbers.len());
+            println!("{}", tr!(i18n::ARRAY_TITLE));
+            println!("{}", tr!(i18n::ARRAY_INPUT, numbers.numbers));
+            println!("{}", tr!(i18n::ARRAY_PARSED, numbers.len()));
 
             for (i, num) in numbers.iter().enumerate() {
                 println!("    [{}]: {}", i + 1, num);
@@ -97,44 +99,42 @@ pub(crate) fn handle_sample<'a>(command: CmdSample<'a>) -> Result<(), core::conv
                 "sum" => {
                     if !numbers.is_empty() {
                         let sum: i32 = numbers.iter().sum();
-                        println!("  总和: {}", sum);
+                        println!("{}", tr!(i18n::ARRAY_SUM, sum));
                     }
                 }
                 "avg" => {
                     if !numbers.is_empty() {
                         let sum: i32 = numbers.iter().sum();
                         let avg = sum as f32 / numbers.len() as f32;
-                        println!("  平均值: {:.2}", avg);
+                        println!("{}", tr!(i18n::ARRAY_AVG, format!("{:.2}", avg)));
                     }
                 }
                 "min" => {
                     if !numbers.is_empty() {
                         if let Some(min) = numbers.iter().min() {
-                            println!("  最小值: {}", min);
+                            println!("{}", tr!(i18n::ARRAY_MIN, min));
                         }
                     }
                 }
                 "max" => {
                     if !numbers.is_empty() {
                         if let Some(max) = numbers.iter().max() {
-                            println!("  最大值: {}", max);
+                            println!("{}", tr!(i18n::ARRAY_MAX, max));
                         }
                     }
                 }
-                _ => println!("  未知操作: {}", op),
+                _ => println!("{}", tr!(i18n::ARRAY_UNKNOWN_OP, op)),
             };
             Ok(())
         }
         CmdSample::Point { point } => {
-            println!("点坐标:");
+            println!("{}", tr!(i18n::POINT_TITLE));
             println!("  X = {:.2}", point.x);
             println!("  Y = {:.2}", point.y);
             #[allow(unused)] // 硬件真实环境需要
             use micromath::F32Ext;
-            println!(
-                "  距离原点: {:.2}",
-                (point.x * point.x + point.y * point.y).sqrt()
-            );
+            let distance = (point.x * point.x + point.y * point.y).sqrt();
+            println!("{}", tr!(i18n::POINT_DISTANCE, format!("{:.2}", distance)));
             Ok(())
         }
     }
@@ -177,7 +177,7 @@ impl<'a> arguments::FromArgument<'a> for IntArray<'a> {
             if i >= 10 {
                 return Err(FromArgumentError {
                     value: arg,
-                    expected: "参数数目最大为10",
+                    expected: tr!(i18n::ARG_TOO_MANY),
                 });
             }
 
@@ -188,7 +188,7 @@ impl<'a> arguments::FromArgument<'a> for IntArray<'a> {
 
             parsed[len] = trimmed.parse().map_err(|_| FromArgumentError {
                 value: trimmed,
-                expected: "无效的数字格式",
+                expected: tr!(i18n::ARG_BAD_NUMBER),
             })?;
             len += 1;
         }
@@ -196,7 +196,7 @@ impl<'a> arguments::FromArgument<'a> for IntArray<'a> {
         if len == 0 {
             return Err(FromArgumentError {
                 value: arg,
-                expected: "至少提供一个参数",
+                expected: tr!(i18n::ARG_EMPTY),
             });
         }
 
@@ -220,17 +220,17 @@ impl<'a> arguments::FromArgument<'a> for Point {
         if parts.len() != 2 {
             return Err(FromArgumentError {
                 value: arg,
-                expected: "点坐标格式应该是x,y",
+                expected: tr!(i18n::ARG_POINT_FORMAT),
             });
         }
 
         let x = parts[0].trim().parse().map_err(|_| FromArgumentError {
             value: parts[0],
-            expected: "无效的X",
+            expected: tr!(i18n::ARG_BAD_X),
         })?;
         let y = parts[1].trim().parse().map_err(|_| FromArgumentError {
             value: parts[1],
-            expected: "无效的Y",
+            expected: tr!(i18n::ARG_BAD_Y),
         })?;
 
         Ok(Point { x, y })
@@ -0,0 +1,124 @@
+//! 颜色工具
+//!
+//! `DisplayColor` 在 ST7735 和 LED 点阵上是 Rgb565，在模拟器上是 Rgb888，两边的通道范围不一样，
+//! 直接写 `DisplayColor::new(0, 31, 0)` 在模拟器上几乎是黑色。
+//! 所有颜色都统一用 8 位 RGB（或 HSL）描述，再由 [`rgb`] 换算成当前后端的颜色。
+
+use crate::*;
+
+// 8 位通道按四舍五入缩放到 `max`，和 embedded-graphics 自带的颜色转换一致
+#[allow(unused)]
+const fn scale(value: u8, max: u16) -> u8 {
+    ((value as u16 * max + 127) / 255) as u8
+}
+
+/// 从 8 位 RGB 构造当前后端的颜色
+#[cfg(any(feature = "target-st7735", feature = "target-led8x8"))]
+pub(crate) const fn rgb(r: u8, g: u8, b: u8) -> DisplayColor {
+    DisplayColor::new(scale(r, 31), scale(g, 63), scale(b, 31))
+}
+
+/// 从 8 位 RGB 构造当前后端的颜色
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const fn rgb(r: u8, g: u8, b: u8) -> DisplayColor {
+    DisplayColor::new(r, g, b)
+}
+
+/// `DisplayColor` 的像素格式名，截图时写在头里告诉主机怎么解
+#[cfg(any(feature = "target-st7735", feature = "target-led8x8"))]
+pub(crate) const PIXEL_FORMAT: &str = "rgb565";
+
+/// `DisplayColor` 的像素格式名，截图时写在头里告诉主机怎么解
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const PIXEL_FORMAT: &str = "rgb888";
+
+// ===========================================
+// 调色板
+// ============================================
+
+/// 各模块共用的颜色，纯色请直接用 `DisplayColor::RED` 之类的常量
+#[allow(unused)] // 按功能开关裁剪后不是每个颜色都用得到
+pub(crate) mod palette {
+    use super::rgb;
+    use crate::DisplayColor;
+
+    pub const DARK_RED: DisplayColor = rgb(128, 0, 0);
+    pub const DARK_GREEN: DisplayColor = rgb(0, 128, 0);
+    pub const DARK_BLUE: DisplayColor = rgb(0, 0, 128);
+    pub const NAVY: DisplayColor = rgb(0, 0, 64);
+    pub const MIDNIGHT: DisplayColor = rgb(32, 32, 64);
+    pub const SLATE: DisplayColor = rgb(48, 48, 96);
+    pub const DARK_GRAY: DisplayColor = rgb(64, 64, 64);
+    pub const GRAY: DisplayColor = rgb(96, 96, 96);
+}
+
+// ===========================================
+// HSL颜色表示和转换
+// ============================================
+
+/// HSL 颜色，贪吃蛇随机配色用
+#[cfg_attr(not(feature = "cmd-snake"), allow(dead_code))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Hsl {
+    pub h: f32, // 色调 0-360
+    pub s: f32, // 饱和度 0-1
+    pub l: f32, // 亮度 0-1
+}
+
+#[cfg_attr(not(feature = "cmd-snake"), allow(dead_code))]
+impl Hsl {
+    pub fn new(h: f32, s: f32, l: f32) -> Self {
+        Self {
+            h: h.max(0.0).min(360.0),
+            s: s.max(0.0).min(1.0),
+            l: l.max(0.0).min(1.0),
+        }
+    }
+
+    // HSL转当前后端的颜色
+    pub fn to_rgb(&self) -> DisplayColor {
+        let (r, g, b) = self.to_rgb888();
+        rgb(r, g, b)
+    }
+
+    pub fn to_rgb888(&self) -> (u8, u8, u8) {
+        let h = self.h / 360.0;
+        let s = self.s;
+        let l = self.l;
+
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = c * (1.0 - ((h * 6.0) % 2.0 - 1.0).abs());
+        let m = l - c / 2.0;
+
+        let (r1, g1, b1) = if h < 1.0 / 6.0 {
+            (c, x, 0.0)
+        } else if h < 2.0 / 6.0 {
+            (x, c, 0.0)
+        } else if h < 3.0 / 6.0 {
+            (0.0, c, x)
+        } else if h < 4.0 / 6.0 {
+            (0.0, x, c)
+        } else if h < 5.0 / 6.0 {
+            (x, 0.0, c)
+        } else {
+            (c, 0.0, x)
+        };
+
+        let r = ((r1 + m) * 255.0) as u8;
+        let g = ((g1 + m) * 255.0) as u8;
+        let b = ((b1 + m) * 255.0) as u8;
+
+        (r, g, b)
+    }
+
+    // 计算两个HSL颜色的角度差
+    pub fn angle_difference(&self, other: &Hsl) -> f32 {
+        let diff = (self.h - other.h).abs();
+        diff.min(360.0 - diff) // 取最短弧
+    }
+
+    // 调整亮度
+    pub fn lighter(&self, amount: f32) -> Hsl {
+        Hsl::new(self.h, self.s, (self.l + amount).min(0.9).max(0.1))
+    }
+}
@@ -0,0 +1,293 @@
+//! panic 处理（板子）
+//!
+//! `panic=abort` 下 SDK 自带的处理只在串口打一行然后停住，屏幕上什么都看不出来。这里换成自己的：
+//! 串口打出原因和位置，记到不随复位清零的 `.noinit` 内存里，再按设置自动重启回命令行或者停住。
+//! 重启后 `lastpanic` 能看到上一次的记录，断电才会丢。
+//!
+//! 处理过程中不分配内存，堆坏了也能打出来。开机不久就接连 panic 说明重启也没用，这时停住不再重启。
+//!
+//! 只在开了 `panic-handler` 时编译，板子目标都会打开；SDK 的 `panic` 特性要关掉，两个处理不能同时存在。
+
+#![allow(static_mut_refs)]
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+use crate::screenshot::Crc32;
+use crate::settings::{self, Key};
+
+use core::fmt::{self, Write};
+use core::mem::{MaybeUninit, offset_of};
+use core::panic::PanicInfo;
+use ecos_ssc1::Timer;
+use embedded_cli::Command;
+
+const MAGIC: u32 = 0x434E_4150; // "PANC"
+
+const LOCATION_LEN: usize = 96;
+const MESSAGE_LEN: usize = 160;
+
+/// 开机这么久之内 panic 算“开机不久”
+const EARLY_MS: u32 = 10_000;
+
+/// 开机不久连续 panic 几次后不再重启
+const MAX_STREAK: u32 = 3;
+
+const RESTART_DELAY_MS: u32 = 3000;
+
+/// 记在 `.noinit` 里的一次 panic
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Record {
+    magic: u32,
+    /// 上电以来第几次
+    count: u32,
+    /// 连续几次开机不久就 panic
+    streak: u32,
+    /// panic 时开机了多久
+    uptime_ms: u32,
+    /// 开机时报过了没有
+    reported: u32,
+    location_len: u32,
+    message_len: u32,
+    location: [u8; LOCATION_LEN],
+    message: [u8; MESSAGE_LEN],
+    crc: u32,
+}
+
+impl Record {
+    fn checksum(&self) -> u32 {
+        // SAFETY: repr(C)，crc 之前全是整数和字节数组，没有填充
+        let bytes = unsafe {
+            core::slice::from_raw_parts(self as *const Self as *const u8, offset_of!(Record, crc))
+        };
+        let mut crc = Crc32::new();
+        crc.update(bytes);
+        crc.value()
+    }
+
+    fn seal(&mut self) {
+        self.crc = self.checksum();
+    }
+
+    fn is_valid(&self) -> bool {
+        self.magic == MAGIC
+            && self.location_len as usize <= LOCATION_LEN
+            && self.message_len as usize <= MESSAGE_LEN
+            && self.crc == self.checksum()
+    }
+
+    fn location(&self) -> &str {
+        text(&self.location[..self.location_len as usize])
+    }
+
+    fn message(&self) -> &str {
+        text(&self.message[..self.message_len as usize])
+    }
+}
+
+fn text(bytes: &[u8]) -> &str {
+    core::str::from_utf8(bytes).unwrap_or("?")
+}
+
+// 复位时不清零，上电时是随机内容，靠 magic 和 crc 认
+#[unsafe(link_section = ".noinit")]
+static mut LAST: MaybeUninit<Record> = MaybeUninit::uninit();
+
+static mut PANICKING: bool = false;
+
+/// 上一次的记录，没有或者是上电后的随机内容时返回 `None`
+fn last() -> Option<Record> {
+    // SAFETY: 整数和字节数组什么位模式都合法，真假交给 is_valid
+    let record = unsafe { LAST.as_ptr().read_volatile() };
+    record.is_valid().then_some(record)
+}
+
+fn store(record: &Record) {
+    unsafe { LAST.as_mut_ptr().write_volatile(*record) };
+}
+
+fn clear() {
+    let mut record = last().unwrap_or_else(empty);
+    record.magic = 0;
+    store(&record);
+}
+
+fn empty() -> Record {
+    Record {
+        magic: MAGIC,
+        count: 0,
+        streak: 0,
+        uptime_ms: 0,
+        reported: 0,
+        location_len: 0,
+        message_len: 0,
+        location: [0; LOCATION_LEN],
+        message: [0; MESSAGE_LEN],
+        crc: 0,
+    }
+}
+
+/// 往定长缓冲里写，写不下的截掉，不会截半个字
+struct Truncate<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for Truncate<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let mut utf8 = [0; 4];
+            let bytes = c.encode_utf8(&mut utf8).as_bytes();
+            if self.len + bytes.len() > self.buf.len() {
+                break;
+            }
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        }
+        Ok(())
+    }
+}
+
+fn record_panic(info: &PanicInfo, uptime_ms: u32) -> Record {
+    let previous = last();
+    let mut record = empty();
+    record.count = previous.map_or(1, |r| r.count.wrapping_add(1));
+    record.streak = match previous {
+        Some(r) if uptime_ms < EARLY_MS => r.streak + 1,
+        _ if uptime_ms < EARLY_MS => 1,
+        _ => 0,
+    };
+    record.uptime_ms = uptime_ms;
+
+    let mut location = Truncate {
+        buf: &mut record.location,
+        len: 0,
+    };
+    if let Some(loc) = info.location() {
+        let _ = write!(location, "{}:{}:{}", loc.file(), loc.line(), loc.column());
+    }
+    record.location_len = location.len as u32;
+
+    let mut message = Truncate {
+        buf: &mut record.message,
+        len: 0,
+    };
+    let _ = write!(message, "{}", info.message());
+    record.message_len = message.len as u32;
+
+    record.seal();
+    store(&record);
+    record
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    // 处理过程中又 panic 了，只能停住
+    unsafe {
+        if PANICKING {
+            halt();
+        }
+        PANICKING = true;
+    }
+
+    let uptime_ms = Timer::get_tick();
+    println!("\r\n!!! PANIC: {}\r", info.message());
+    if let Some(loc) = info.location() {
+        println!("!!! {}:{}:{}\r", loc.file(), loc.line(), loc.column());
+    }
+
+    let record = record_panic(info, uptime_ms);
+    if settings::number(Key::PanicRestart) == 0 {
+        println!("!!! {}\r", tr!(i18n::PANIC_HALTED));
+        halt();
+    }
+    if record.streak >= MAX_STREAK {
+        println!("!!! {}\r", tr!(i18n::PANIC_LOOP));
+        halt();
+    }
+
+    println!("!!! {}\r", tr!(i18n::PANIC_RESTARTING));
+    Timer::delay_ms(RESTART_DELAY_MS);
+    restart();
+}
+
+/// 从启动代码重新跑一遍，和按复位键一样，只是 `.noinit` 留着
+fn restart() -> ! {
+    unsafe extern "C" {
+        // build.rs 把 start.s 里的入口导出成全局符号
+        fn start() -> !;
+    }
+    #[cfg(target_arch = "riscv32")]
+    unsafe {
+        // 先关中断，免得启动代码还没初始化完就进中断
+        core::arch::asm!("csrci mstatus, 8");
+    }
+    unsafe { start() }
+}
+
+fn halt() -> ! {
+    loop {
+        #[cfg(target_arch = "riscv32")]
+        unsafe {
+            core::arch::asm!("wfi");
+        }
+        #[cfg(not(target_arch = "riscv32"))]
+        core::hint::spin_loop();
+    }
+}
+
+/// 开机时调用，上次是 panic 重启的就提一句
+pub(crate) fn report() {
+    let Some(mut record) = last() else {
+        return;
+    };
+    if record.reported != 0 {
+        return;
+    }
+    log::warn!(
+        "{}",
+        tr!(i18n::PANIC_PREVIOUS, record.location(), record.message())
+    );
+    record.reported = 1;
+    record.seal();
+    store(&record);
+}
+
+#[derive(Command, Debug)]
+pub(crate) enum PanicCommand<'a> {
+    /// 查看上次 panic 的原因和位置，`lastpanic clear` 清掉记录
+    #[command(name = "lastpanic")]
+    LastPanic {
+        /// clear 清掉记录，不填查看
+        action: Option<&'a str>,
+    },
+}
+
+pub(crate) fn handle_panic_command(
+    command: PanicCommand<'_>,
+) -> Result<(), core::convert::Infallible> {
+    match command {
+        PanicCommand::LastPanic { action: None } => match last() {
+            Some(record) => {
+                println!(
+                    "\r\n{}\r",
+                    tr!(i18n::PANIC_REPORT, record.uptime_ms, record.count)
+                );
+                println!("{}\r", tr!(i18n::PANIC_AT, record.location()));
+                println!("{}\r\n", tr!(i18n::PANIC_MESSAGE, record.message()));
+            }
+            None => println!("\r\n{}\r\n", tr!(i18n::PANIC_NONE)),
+        },
+        PanicCommand::LastPanic {
+            action: Some("clear"),
+        } => {
+            clear();
+            println!("\r\n{}\r\n", tr!(i18n::PANIC_CLEARED));
+        }
+        PanicCommand::LastPanic {
+            action: Some(other),
+        } => println!("\r\n{}\r\n", tr!(i18n::PANIC_BAD_ACTION, other)),
+    }
+    Ok(())
+}
@@ -2,48 +2,72 @@ use crate::*;
 
 use rusttype::Font;
 
+use crate::anim::{Easing, Timeline, TimelineState, Tween};
+use crate::app::{App, Flow, Scheduler};
+use crate::color::palette;
+use crate::i18n::{self, tr};
+use crate::marquee::{LineBuffer, Marquee, MarqueeDirection, MarqueeRepeat};
+use crate::perf;
+use crate::theme;
 use embedded_cli::Command;
-use embedded_graphics::{Drawable, prelude::*, text::Text};
+use embedded_graphics::{
+    Drawable,
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Baseline, Text,
+        renderer::{CharacterStyle, TextRenderer},
+    },
+};
 use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point, pixelcolor::RgbColor};
-use embedded_ttf::FontTextStyleBuilder;
-
-const HARMONYOS_SANS_SC_LIGHT: &[u8] =
-    include_bytes!("../display/fonts/HarmonyOS_Sans_SC_Regular.ttf");
+use embedded_ttf::{FontTextStyle, FontTextStyleBuilder};
 
 // 字体演示命令定义
 #[derive(Command, Debug)]
-pub(crate) enum FontSample {
+pub(crate) enum FontSample<'a> {
     /// 启动 TTF 字体演示
     #[command(name = "font")]
     Start,
+
+    /// 整屏滚动显示一段文字，按 q 停止
+    #[command(name = "marquee")]
+    Marquee {
+        /// 要滚动的文字
+        text: Option<&'a str>,
+    },
 }
 
+const DEFAULT_MARQUEE_TEXT: &str = "欢迎使用 ECOS 汉字演示";
+
 // 字体演示处理函数
+//
+// 只负责把字体演示交给调度器，之后按键和每一帧的动画都由主循环驱动，演示期间主循环不会被阻塞。
 pub(crate) fn handle_font_display<T>(
     manager: &mut DisplayManager,
+    apps: &mut Scheduler,
 ) -> Result<(), core::convert::Infallible> {
-    println!("\r\n=== 启动 TTF 字体演示 ===");
-    println!("正在启动字体演示...\r\n");
-
-    // 创建字体演示实例
-    let mut font_demo = FontDemo::new();
-
-    // 开始交互式演示循环
-    loop {
-        let byte = Uart::read_byte_nonblock();
-        if let Some(byte) = byte {
-            // 处理输入，传递 manager 引用
-            if font_demo.handle_input(byte, manager) {
-                // 用户输入了 'q'，退出演示
-                println!("\r\n返回命令行模式...\r\n");
-                break;
-            }
-        }
-        #[cfg(feature = "target-ui-sim")]
-        {
-            manager.update_window();
-        }
-    }
+    i18n::FONT_START.print();
+
+    let size = manager.size();
+    apps.start(manager, Box::new(FontDemo::new(size)));
+
+    Ok(())
+}
+
+// 滚动字幕处理函数，和字体演示共用同一个状态机
+pub(crate) fn handle_font_marquee(
+    manager: &mut DisplayManager,
+    apps: &mut Scheduler,
+    text: Option<&str>,
+) -> Result<(), core::convert::Infallible> {
+    let text = text.unwrap_or(DEFAULT_MARQUEE_TEXT);
+    println!("\r\n{}", tr!(i18n::MARQUEE_TITLE, text));
+    println!("{}\r\n", tr!(i18n::MARQUEE_HINT));
+
+    let mut demo = FontDemo::new(manager.size());
+    demo.play_marquee(text);
+    apps.start(manager, Box::new(demo));
 
     Ok(())
 }
@@ -51,7 +75,11 @@ pub(crate) fn handle_font_display<T>(
 // 字体演示状态机
 pub(crate) struct FontDemo {
     current_demo: Option<FontDemoType>,
-    should_exit: bool,
+    // 正在播放的演示
+    timeline: Option<DemoTimeline>,
+    font: Option<Font<'static>>,
+    // 屏幕尺寸，演示按它布局
+    size: Size,
 }
 
 // 演示类型枚举
@@ -62,50 +90,61 @@ enum FontDemoType {
     Chinese,
     Mixed,
     Animated,
+    Effects,
 }
 
 impl FontDemo {
-    pub fn new() -> Self {
+    pub fn new(size: Size) -> Self {
         Self {
             current_demo: None,
-            should_exit: false,
+            timeline: None,
+            font: None,
+            size,
         }
     }
 
-    // 处理键盘输入
-    pub fn handle_input(&mut self, byte: u8, manager: &mut DisplayManager) -> bool {
+    // 处理键盘输入，返回 true 表示退出
+    fn handle_input(&mut self, byte: u8) -> bool {
         match byte {
             b'1' => {
-                self.play_demo(FontDemoType::Basic, manager);
+                self.play_demo(FontDemoType::Basic);
                 false
             }
             b'2' => {
-                self.play_demo(FontDemoType::Sizes, manager);
+                self.play_demo(FontDemoType::Sizes);
                 false
             }
             b'3' => {
-                self.play_demo(FontDemoType::Chinese, manager);
+                self.play_demo(FontDemoType::Chinese);
                 false
             }
             b'4' => {
-                self.play_demo(FontDemoType::Mixed, manager);
+                self.play_demo(FontDemoType::Mixed);
                 false
             }
             b'5' => {
-                self.play_demo(FontDemoType::Animated, manager);
+                self.play_demo(FontDemoType::Animated);
+                false
+            }
+            b'6' => {
+                self.play_demo(FontDemoType::Effects);
                 false
             }
             b'n' | b'N' => {
-                self.next_demo(manager);
+                self.next_demo();
                 false
             }
             b'p' | b'P' => {
-                self.prev_demo(manager);
+                self.prev_demo();
                 false
             }
             b'q' | b'Q' => {
-                println!("退出字体演示...\r\n");
-                self.should_exit = true;
+                // 演示播放中只中断当前演示，空闲时才退出
+                if let Some(timeline) = self.timeline.as_mut() {
+                    timeline.cancel();
+                    return false;
+                }
+                println!("{}\r\n", tr!(i18n::FONT_EXIT));
                 true
             }
             _ => {
@@ -115,183 +154,266 @@ impl FontDemo {
         }
     }
 
-    // 播放指定演示
-    fn play_demo(&mut self, demo_type: FontDemoType, manager: &mut DisplayManager) {
+    // 字体开机时已经加载过，这里只是取一份
+    fn load_font(&mut self) -> Option<Font<'static>> {
+        if self.font.is_none() {
+            self.font = fonts::harmony_sans();
+            if self.font.is_none() {
+                log::error!("{}", tr!(i18n::FONT_LOAD_FAILED));
+            }
+        }
+        self.font.clone()
+    }
+
+    // 推进正在播放的演示，由调度器每帧调用一次
+    fn tick(&mut self, manager: &mut DisplayManager, now: u32) {
+        let Some(timeline) = self.timeline.as_mut() else {
+            return;
+        };
+
+        match timeline.tick(manager, now) {
+            TimelineState::Running => {}
+            TimelineState::Finished => {
+                self.timeline = None;
+                println!("\r\n{}\r\n", tr!(i18n::FONT_DEMO_DONE));
+            }
+            TimelineState::Cancelled => {
+                self.timeline = None;
+                println!("\r\n{}", tr!(i18n::FONT_DEMO_CANCELLED));
+            }
+        }
+    }
+
+    // 播放指定演示，正在播放的演示会被直接替换
+    fn play_demo(&mut self, demo_type: FontDemoType) {
         self.current_demo = Some(demo_type);
 
-        match demo_type {
+        let Some(font) = self.load_font() else {
+            return;
+        };
+
+        let timeline = match demo_type {
             FontDemoType::Basic => {
-                println!("切换到: 基本字体渲染\r\n");
-                basic_font_demo(manager);
+                println!("{}\r\n", tr!(i18n::FONT_SWITCH, i18n::FONT_DEMO_BASIC));
+                basic_font_demo(font, self.size)
             }
             FontDemoType::Sizes => {
-                println!("切换到: 不同字体大小\r\n");
-                font_sizes_demo(manager);
+                println!("{}\r\n", tr!(i18n::FONT_SWITCH, i18n::FONT_DEMO_SIZES));
+                font_sizes_demo(font)
             }
             FontDemoType::Chinese => {
-                println!("切换到: 中文字体渲染\r\n");
-                chinese_font_demo(manager);
+                println!("{}\r\n", tr!(i18n::FONT_SWITCH, i18n::FONT_DEMO_CHINESE));
+                chinese_font_demo(font)
             }
             FontDemoType::Mixed => {
-                println!("切换到: 混合文本和图形\r\n");
-                mixed_graphics_demo(manager);
+                println!("{}\r\n", tr!(i18n::FONT_SWITCH, i18n::FONT_DEMO_MIXED));
+                mixed_graphics_demo(font, self.size)
             }
             FontDemoType::Animated => {
-                println!("切换到: 动画文本\r\n");
-                animated_text_demo(manager);
+                println!("{}\r\n", tr!(i18n::FONT_SWITCH, i18n::FONT_DEMO_ANIMATED));
+                animated_text_demo(font, self.size)
             }
-        }
+            FontDemoType::Effects => {
+                println!("{}\r\n", tr!(i18n::FONT_SWITCH, i18n::FONT_DEMO_EFFECTS));
+                text_effects_demo(font, self.size)
+            }
+        };
+
+        self.timeline = Some(timeline);
+    }
 
-        println!("\r\n演示结束，输入命令继续...\r\n");
+    // 整屏滚动一段文字，直到被 q 中断
+    fn play_marquee(&mut self, text: &str) {
+        let Some(font) = self.load_font() else {
+            return;
+        };
+        self.timeline = Some(marquee_timeline(font, text, self.size));
     }
 
     // 播放下一个演示
-    fn next_demo(&mut self, manager: &mut DisplayManager) {
+    fn next_demo(&mut self) {
         let next = match self.current_demo {
             Some(FontDemoType::Basic) => FontDemoType::Sizes,
             Some(FontDemoType::Sizes) => FontDemoType::Chinese,
             Some(FontDemoType::Chinese) => FontDemoType::Mixed,
             Some(FontDemoType::Mixed) => FontDemoType::Animated,
-            Some(FontDemoType::Animated) => FontDemoType::Basic,
+            Some(FontDemoType::Animated) => FontDemoType::Effects,
+            Some(FontDemoType::Effects) => FontDemoType::Basic,
             None => FontDemoType::Basic,
         };
-        self.play_demo(next, manager);
+        self.play_demo(next);
     }
 
     // 播放上一个演示
-    fn prev_demo(&mut self, manager: &mut DisplayManager) {
+    fn prev_demo(&mut self) {
         let prev = match self.current_demo {
-            Some(FontDemoType::Basic) => FontDemoType::Animated,
+            Some(FontDemoType::Basic) => FontDemoType::Effects,
             Some(FontDemoType::Sizes) => FontDemoType::Basic,
             Some(FontDemoType::Chinese) => FontDemoType::Sizes,
             Some(FontDemoType::Mixed) => FontDemoType::Chinese,
             Some(FontDemoType::Animated) => FontDemoType::Mixed,
-            None => FontDemoType::Animated,
+            Some(FontDemoType::Effects) => FontDemoType::Animated,
+            None => FontDemoType::Effects,
         };
-        self.play_demo(prev, manager);
+        self.play_demo(prev);
     }
 }
 
-// 演示1: 基本字体渲染
-fn basic_font_demo(manager: &mut DisplayManager) -> bool {
-    #[allow(unused)]
-    use embedded_hal::delay::DelayNs;
-
-    println!("=== 演示1: 基本字体渲染 ===");
-
-    // 清屏为黑色
-    manager.display.clear(DisplayColor::BLACK).unwrap();
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-    manager.delay.delay_ms(500);
-
-    // 加载字体
-    println!("加载 HarmonyOS Sans SC Light 字体...");
-    let font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
-        Some(font) => {
-            println!("字体加载成功");
-            font
-        }
-        None => {
-            println!("字体加载失败！");
-            return false;
+impl App for FontDemo {
+    fn name(&self) -> &'static str {
+        tr!(i18n::FONT_APP)
+    }
+
+    fn on_key(&mut self, _manager: &mut DisplayManager, byte: u8) -> Flow {
+        if self.handle_input(byte) {
+            Flow::Exit
+        } else {
+            Flow::Continue
         }
-    };
+    }
+
+    // 时间线自己画，画完就要 present
+    fn on_tick(&mut self, manager: &mut DisplayManager, now: u32) -> bool {
+        let playing = self.timeline.is_some();
+        self.tick(manager, now);
+        playing
+    }
+
+    fn draw(&mut self, _manager: &mut DisplayManager) {}
+
+    fn frame_ms(&self) -> u32 {
+        0
+    }
+
+    fn is_busy(&self) -> bool {
+        self.timeline.is_some()
+    }
+}
+
+// 每个演示都是一条时间线，由 `FontDemo::tick` 驱动
+type DemoTimeline = Timeline<'static, DisplayManager>;
+
+// 清屏步骤
+fn clear_screen(color: DisplayColor) -> impl FnMut(&mut DisplayManager) {
+    move |manager| {
+        manager.target().clear(color).unwrap_or_else(|e| {
+            log::error!("{}", tr!(i18n::FONT_CLEAR_ERROR, format!("{:?}", e)));
+        });
+    }
+}
+
+// 在离屏缓冲里重画一条区域再整块推上屏，动画每帧整条刷新不会闪
+fn redraw_band<F>(
+    manager: &mut DisplayManager,
+    band: &mut LineBuffer,
+    top_left: Point,
+    background: DisplayColor,
+    draw: F,
+) where
+    F: FnOnce(&mut LineBuffer),
+{
+    let _ = band.clear(background);
+    draw(band);
+    band.draw_at(manager.target(), top_left)
+        .unwrap_or_else(|e| {
+            log::error!("{}", tr!(i18n::FONT_DRAW_ERROR, format!("{:?}", e)));
+        });
+}
+
+// 整屏滚动字幕
+//
+// 字号等于屏幕高度，画布多大都能用，8x8 点阵上就靠它显示汉字。
+fn marquee_timeline(font: Font<'static>, text: &str, size: Size) -> DemoTimeline {
+    let theme = theme::current();
+    let style = FontTextStyleBuilder::new(font)
+        .font_size(size.height)
+        .text_color(theme.text)
+        .build();
+    let mut marquee = Marquee::new(text, Rectangle::new(Point::zero(), size), style)
+        .speed((size.width / 2).max(8))
+        .gap(size.width / 2)
+        .background(theme.background);
+    let mut last_elapsed = 0;
+
+    DemoTimeline::new()
+        .call(clear_screen(theme.background))
+        // 时长取最大值，相当于一直滚，直到被取消
+        .then(u32::MAX, move |manager, frame| {
+            marquee.update(frame.elapsed - last_elapsed);
+            last_elapsed = frame.elapsed;
+            let _ = marquee.draw(manager.target());
+        })
+}
+
+// 演示1: 基本字体渲染
+fn basic_font_demo(font: Font<'static>, size: Size) -> DemoTimeline {
+    println!("{}", tr!(i18n::FONT_DEMO_HEADER, 1, i18n::FONT_DEMO_BASIC));
+    let theme = theme::current();
 
     // 创建字体样式 - 小号字体
     let small_style = FontTextStyleBuilder::new(font.clone())
         .font_size(12) // 12像素高
-        .text_color(DisplayColor::WHITE)
+        .text_color(theme.text)
         .build();
 
     // 创建字体样式 - 中号字体
     let medium_style = FontTextStyleBuilder::new(font.clone())
         .font_size(16) // 16像素高
-        .text_color(DisplayColor::new(0, 31, 0)) // 绿色
+        .text_color(palette::DARK_GREEN) // 绿色
         .build();
 
     // 创建字体样式 - 大号字体
     let large_style = FontTextStyleBuilder::new(font)
         .font_size(20) // 20像素高
-        .text_color(DisplayColor::new(31, 0, 0)) // 红色
+        .text_color(DisplayColor::RED) // 红色
         .build();
 
-    // 绘制小号文本
-    println!("绘制小号文本...");
-    Text::new("Hello TTF!", Point::new(10, 15), small_style)
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(1000);
-
-    // 检查是否按了 'q' 键
-    if let Some(byte) = Uart::read_byte_nonblock() {
-        if byte == b'q' || byte == b'Q' {
-            println!("\r\n用户中断演示");
-            return true;
-        }
-    }
-
-    // 绘制中号文本
-    println!("绘制中号文本...");
-    Text::new("Embedded", Point::new(10, 40), medium_style)
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(1000);
-
-    // 检查是否按了 'q' 键
-    if let Some(byte) = Uart::read_byte_nonblock() {
-        if byte == b'q' || byte == b'Q' {
-            println!("\r\n用户中断演示");
-            return true;
-        }
-    }
-
-    // 绘制大号文本
-    println!("绘制大号文本...");
-    Text::new("ECOS", Point::new(10, 70), large_style)
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(2000);
-
-    false
+    // 大号文本从左边滑进来，带一点回弹
+    let slide = Tween::new(Point::new(-60, 0), Point::new(10, 0)).easing(Easing::OutBack);
+    let mut band = LineBuffer::new(Size::new(size.width, 24));
+
+    DemoTimeline::new()
+        .call(clear_screen(theme.background))
+        .wait(500)
+        .call(move |manager| {
+            println!("{}", tr!(i18n::FONT_DRAW_SMALL));
+            Text::new("Hello TTF!", Point::new(10, 15), small_style.clone())
+                .draw(manager.target())
+                .unwrap();
+        })
+        .wait(1000)
+        .call(move |manager| {
+            println!("{}", tr!(i18n::FONT_DRAW_MEDIUM));
+            Text::new("Embedded", Point::new(10, 40), medium_style.clone())
+                .draw(manager.target())
+                .unwrap();
+        })
+        .wait(1000)
+        .call(|_| println!("{}", tr!(i18n::FONT_DRAW_LARGE)))
+        .then(600, move |manager, frame| {
+            let position = slide.at(frame.progress);
+            redraw_band(
+                manager,
+                &mut band,
+                Point::new(0, 70),
+                theme.background,
+                |band| {
+                    let _ = Text::new("ECOS", position, large_style.clone()).draw(band);
+                },
+            );
+        })
+        .wait(2000)
 }
 
 // 演示2: 不同字体大小
-fn font_sizes_demo(manager: &mut DisplayManager) -> bool {
-    #[allow(unused)]
-    use embedded_hal::delay::DelayNs;
-
-    println!("=== 演示2: 不同字体大小 ===");
-
-    // 清屏为深蓝色
-    manager.display.clear(DisplayColor::new(0, 0, 15)).unwrap();
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-    manager.delay.delay_ms(500);
-
-    // 加载字体
-    let font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
-        Some(font) => {
-            println!("字体加载成功");
-            font
-        }
-        None => {
-            println!("字体加载失败！");
-            return false;
-        }
-    };
+fn font_sizes_demo(font: Font<'static>) -> DemoTimeline {
+    println!("{}", tr!(i18n::FONT_DEMO_HEADER, 2, i18n::FONT_DEMO_SIZES));
+    let theme = theme::current();
+
+    // 清屏为主题的面板色（深色主题是深蓝）
+    let mut timeline = DemoTimeline::new()
+        .call(clear_screen(theme.panel))
+        .wait(500);
 
     // 绘制不同大小的字体
     let mut y = 10;
@@ -304,457 +426,666 @@ fn font_sizes_demo(manager: &mut DisplayManager) -> bool {
         (18, "Size 18"),
         (20, "Size 20"),
     ] {
-        // 检查是否按了 'q' 键
-        if let Some(byte) = Uart::read_byte_nonblock() {
-            if byte == b'q' || byte == b'Q' {
-                println!("\r\n用户中断演示");
-                return true;
-            }
-        }
-
         let style = FontTextStyleBuilder::new(font.clone())
             .font_size(size)
-            .text_color(DisplayColor::WHITE)
+            .text_color(theme.text)
             .build();
 
-        Text::new(text, Point::new(10, y), style)
-            .draw(&mut manager.display)
-            .unwrap();
-
-        #[cfg(feature = "target-ui-sim")]
-        manager.update_window();
+        timeline = timeline
+            .call(move |manager| {
+                Text::new(text, Point::new(10, y), style.clone())
+                    .draw(manager.target())
+                    .unwrap();
+            })
+            .wait(300);
 
         y += size as i32 + 5;
-        manager.delay.delay_ms(300);
     }
 
-    manager.delay.delay_ms(1000);
-    false
+    timeline.wait(1000)
 }
 
 // 演示3: 中文字体渲染
-fn chinese_font_demo(manager: &mut DisplayManager) -> bool {
-    #[allow(unused)]
-    use embedded_hal::delay::DelayNs;
-
-    println!("=== 演示3: 中文字体渲染 ===");
-
-    // 清屏为深灰色
-    manager.display.clear(DisplayColor::new(8, 8, 8)).unwrap();
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-    manager.delay.delay_ms(500);
-
-    // 加载字体
-    let font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
-        Some(font) => {
-            println!("字体加载成功");
-            font
-        }
-        None => {
-            println!("字体加载失败！");
-            return false;
-        }
-    };
+fn chinese_font_demo(font: Font<'static>) -> DemoTimeline {
+    println!(
+        "{}",
+        tr!(i18n::FONT_DEMO_HEADER, 3, i18n::FONT_DEMO_CHINESE)
+    );
+    let theme = theme::current();
+
+    // 背景用主题的次要底色（深色主题是深灰）
+    let background = theme.surface;
 
     // 创建字体样式
     let style = FontTextStyleBuilder::new(font)
         .font_size(16)
-        .text_color(DisplayColor::CYAN)
+        .text_color(theme.accent)
         .build();
 
-    // 绘制中文文本
-    println!("绘制中文文本...");
-
-    // 第一行
-    Text::new("嵌入式系统", Point::new(10, 20), style.clone())
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(1000);
-
-    // 检查是否按了 'q' 键
-    if let Some(byte) = Uart::read_byte_nonblock() {
-        if byte == b'q' || byte == b'Q' {
-            println!("\r\n用户中断演示");
-            return true;
-        }
-    }
-
-    // 第二行
-    Text::new("ECOS开发板", Point::new(10, 45), style.clone())
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(1000);
-
-    // 检查是否按了 'q' 键
-    if let Some(byte) = Uart::read_byte_nonblock() {
-        if byte == b'q' || byte == b'Q' {
-            println!("\r\n用户中断演示");
-            return true;
-        }
-    }
-
-    // 第三行
-    Text::new("图形界面演示", Point::new(10, 70), style.clone())
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(1000);
-
-    // 检查是否按了 'q' 键
-    if let Some(byte) = Uart::read_byte_nonblock() {
-        if byte == b'q' || byte == b'Q' {
-            println!("\r\n用户中断演示");
-            return true;
-        }
+    let mut timeline = DemoTimeline::new()
+        .call(clear_screen(background))
+        .wait(500)
+        .call(|_| println!("{}", tr!(i18n::FONT_DRAW_CHINESE)));
+
+    // 每行从背景色淡入，颜色只会越来越亮，直接覆盖重画即可
+    for (text, y) in [
+        ("嵌入式系统", 20),
+        ("ECOS开发板", 45),
+        ("图形界面演示", 70),
+        ("TTF字体渲染", 95),
+    ] {
+        let mut style = style.clone();
+        let fade = Tween::new(background, theme.accent).easing(Easing::EaseOut);
+        timeline = timeline
+            .then(400, move |manager, frame| {
+                style.set_text_color(Some(fade.at(frame.progress)));
+                Text::new(text, Point::new(10, y), style.clone())
+                    .draw(manager.target())
+                    .unwrap();
+            })
+            .wait(600);
     }
 
-    // 第四行
-    Text::new("TTF字体渲染", Point::new(10, 95), style)
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    manager.delay.delay_ms(2000);
-    false
+    timeline.wait(1000)
 }
 
 // 演示4: 混合文本和图形
-fn mixed_graphics_demo(manager: &mut DisplayManager) -> bool {
-    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
-    #[allow(unused)]
-    use embedded_hal::delay::DelayNs;
-
-    println!("=== 演示4: 混合文本和图形 ===");
-
-    // 清屏为黑色
-    manager.display.clear(DisplayColor::BLACK).unwrap();
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-    manager.delay.delay_ms(500);
-
-    // 加载字体
-    let font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
-        Some(font) => {
-            println!("字体加载成功");
-            font
-        }
-        None => {
-            println!("字体加载失败！");
-            return false;
-        }
-    };
-
-    // 绘制背景矩形
-    println!("绘制背景矩形...");
-    Rectangle::new(Point::new(5, 5), Size::new(118, 50))
-        .into_styled(PrimitiveStyle::with_fill(DisplayColor::new(15, 0, 0)))
-        .draw(&mut manager.display)
-        .unwrap();
+fn mixed_graphics_demo(font: Font<'static>, size: Size) -> DemoTimeline {
+    use embedded_graphics::primitives::PrimitiveStyle;
 
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
+    println!("{}", tr!(i18n::FONT_DEMO_HEADER, 4, i18n::FONT_DEMO_MIXED));
+    let theme = theme::current();
 
-    // 绘制文字
     let style = FontTextStyleBuilder::new(font.clone())
         .font_size(14)
         .text_color(DisplayColor::YELLOW)
         .build();
 
-    Text::new("ECOS Display", Point::new(15, 25), style)
-        .draw(&mut manager.display)
-        .unwrap();
-
     let style2 = FontTextStyleBuilder::new(font.clone())
         .font_size(12)
         .text_color(DisplayColor::GREEN)
         .build();
 
-    Text::new("ST7735 + TTF", Point::new(15, 45), style2)
-        .draw(&mut manager.display)
-        .unwrap();
-
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-
-    // 绘制装饰性元素
-    Rectangle::new(Point::new(5, 60), Size::new(118, 58))
-        .into_styled(PrimitiveStyle::with_stroke(DisplayColor::BLUE, 2))
-        .draw(&mut manager.display)
-        .unwrap();
-
-    // 绘制小字
     let small_style = FontTextStyleBuilder::new(font)
         .font_size(10)
-        .text_color(DisplayColor::WHITE)
+        .text_color(theme.text)
         .build();
 
-    Text::new("Version 1.0", Point::new(20, 80), small_style.clone())
-        .draw(&mut manager.display)
-        .unwrap();
-
-    Text::new("128x128 RGB", Point::new(20, 100), small_style.clone())
-        .draw(&mut manager.display)
-        .unwrap();
+    DemoTimeline::new()
+        .call(clear_screen(theme.background))
+        .wait(500)
+        .call(move |manager| {
+            // 绘制背景矩形
+            println!("{}", tr!(i18n::FONT_DRAW_BACKGROUND));
+            Rectangle::new(Point::new(5, 5), Size::new(size.width - 10, 50))
+                .into_styled(PrimitiveStyle::with_fill(palette::DARK_RED))
+                .draw(manager.target())
+                .unwrap();
 
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
+            // 绘制文字
+            Text::new("ECOS Display", Point::new(15, 25), style.clone())
+                .draw(manager.target())
+                .unwrap();
 
-    // 等待期间检查 'q' 键
-    for _ in 0..30 {
-        manager.delay.delay_ms(100);
+            Text::new("ST7735 + TTF", Point::new(15, 45), style2.clone())
+                .draw(manager.target())
+                .unwrap();
+        })
+        .call(move |manager| {
+            // 绘制装饰性元素
+            Rectangle::new(Point::new(5, 60), Size::new(size.width - 10, 58))
+                .into_styled(PrimitiveStyle::with_stroke(DisplayColor::BLUE, 2))
+                .draw(manager.target())
+                .unwrap();
 
-        if let Some(byte) = Uart::read_byte_nonblock() {
-            if byte == b'q' || byte == b'Q' {
-                println!("\r\n用户中断演示");
-                return true;
-            }
-        }
-    }
+            // 绘制小字
+            Text::new("Version 1.0", Point::new(20, 80), small_style.clone())
+                .draw(manager.target())
+                .unwrap();
 
-    false
+            let resolution = format!("{}x{} RGB", size.width, size.height);
+            Text::new(&resolution, Point::new(20, 100), small_style.clone())
+                .draw(manager.target())
+                .unwrap();
+        })
+        .wait(3000)
 }
 
 // 演示5: 动画文本
-fn animated_text_demo(manager: &mut DisplayManager) -> bool {
-    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
-    #[allow(unused)]
-    use embedded_hal::delay::DelayNs;
-
-    println!("=== 演示5: 动画文本 ===");
-    println!("按 'q' 键可以提前退出动画演示\r\n");
-
-    // 清屏为黑色
-    manager.display.clear(DisplayColor::BLACK).unwrap();
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
-    manager.delay.delay_ms(500);
-
-    // 加载字体
-    let font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
-        Some(font) => {
-            println!("字体加载成功");
-            font
-        }
-        None => {
-            println!("字体加载失败！");
-            return false;
-        }
-    };
+fn animated_text_demo(font: Font<'static>, size: Size) -> DemoTimeline {
+    println!(
+        "{}",
+        tr!(i18n::FONT_DEMO_HEADER, 5, i18n::FONT_DEMO_ANIMATED)
+    );
+    let theme = theme::current();
+    println!("{}\r\n", tr!(i18n::FONT_ANIMATION_HINT));
 
     // 创建不同的文本样式
     let styles = [
+        DisplayColor::RED,
+        DisplayColor::GREEN,
+        DisplayColor::BLUE,
+        DisplayColor::YELLOW,
+        DisplayColor::CYAN,
+        DisplayColor::MAGENTA,
+    ]
+    .map(|color| {
         FontTextStyleBuilder::new(font.clone())
             .font_size(16)
-            .text_color(DisplayColor::RED)
-            .build(),
-        FontTextStyleBuilder::new(font.clone())
-            .font_size(16)
-            .text_color(DisplayColor::GREEN)
-            .build(),
-        FontTextStyleBuilder::new(font.clone())
-            .font_size(16)
-            .text_color(DisplayColor::BLUE)
-            .build(),
-        FontTextStyleBuilder::new(font.clone())
-            .font_size(16)
-            .text_color(DisplayColor::YELLOW)
-            .build(),
-        FontTextStyleBuilder::new(font.clone())
-            .font_size(16)
-            .text_color(DisplayColor::CYAN)
-            .build(),
-        FontTextStyleBuilder::new(font.clone())
-            .font_size(16)
-            .text_color(DisplayColor::MAGENTA)
-            .build(),
+            .text_color(color)
+            .build()
+    });
+
+    // 彩色文本滚动，每行一个跑马灯，起点错开
+    let texts = ["ECOS Display Demo", "跑马灯 Marquee 演示"];
+    let mut marquees: Vec<Marquee<FontTextStyle<DisplayColor>>> = styles
+        .iter()
+        .enumerate()
+        .map(|(i, style)| {
+            let area = Rectangle::new(
+                Point::new(0, 5 + (i * 20) as i32),
+                Size::new(size.width, 20),
+            );
+            let direction = if i % 2 == 0 {
+                MarqueeDirection::Left
+            } else {
+                MarqueeDirection::Right
+            };
+            // 最后两行演示有限次数的滚动
+            let repeat = match i {
+                4 => MarqueeRepeat::Times(2),
+                5 => MarqueeRepeat::Once,
+                _ => MarqueeRepeat::Forever,
+            };
+            Marquee::new(texts[i % 2], area, style.clone())
+                .speed(40)
+                .direction(direction)
+                .repeat(repeat)
+                .background(theme.background)
+                .gap(24)
+                .start_offset((i * 10) as u32)
+        })
+        .collect();
+    let mut last_elapsed = 0;
+
+    // 淡入、淡出、缩放共用一种样式，每帧只改颜色和字号
+    let style = FontTextStyleBuilder::new(font)
+        .font_size(20)
+        .text_color(DisplayColor::BLACK)
+        .build();
+    let band_origin = Point::new(0, 40);
+    let band_size = Size::new(size.width, 36);
+
+    let fade_in = Tween::new(theme.background, theme.text).easing(Easing::EaseInOut);
+    let mut fade_in_style = style.clone();
+    let mut fade_in_band = LineBuffer::new(band_size);
+
+    let fade_out = Tween::new(theme.text, theme.background).easing(Easing::EaseIn);
+    let mut fade_out_style = style.clone();
+    let mut fade_out_band = LineBuffer::new(band_size);
+
+    let zoom = Tween::new(8u32, 28u32).easing(Easing::OutBack);
+    let mut zoom_style = style;
+    zoom_style.set_text_color(Some(DisplayColor::YELLOW));
+    let mut zoom_band = LineBuffer::new(band_size);
+
+    DemoTimeline::new()
+        .call(clear_screen(theme.background))
+        .wait(500)
+        .call(|_| {
+            println!("{}", tr!(i18n::FONT_SCROLLING));
+            println!("{}\r\n", tr!(i18n::FONT_SCROLLING_HINT));
+        })
+        .then(3000, move |manager, frame| {
+            let elapsed = frame.elapsed - last_elapsed;
+            last_elapsed = frame.elapsed;
+            for marquee in marquees.iter_mut() {
+                marquee.update(elapsed);
+                marquee.draw(manager.target()).unwrap_or_else(|e| {
+                    log::error!("{}", tr!(i18n::FONT_DRAW_ERROR, format!("{:?}", e)));
+                });
+            }
+        })
+        .call(clear_screen(theme.background))
+        .call(|_| println!("{}", tr!(i18n::FONT_FADE)))
+        .then(500, move |manager, frame| {
+            fade_in_style.set_text_color(Some(fade_in.at(frame.progress)));
+            redraw_band(
+                manager,
+                &mut fade_in_band,
+                band_origin,
+                theme.background,
+                |band| {
+                    let _ =
+                        Text::new("Fade In", Point::new(30, 10), fade_in_style.clone()).draw(band);
+                },
+            );
+        })
+        .wait(1000)
+        .then(500, move |manager, frame| {
+            fade_out_style.set_text_color(Some(fade_out.at(frame.progress)));
+            redraw_band(
+                manager,
+                &mut fade_out_band,
+                band_origin,
+                theme.background,
+                |band| {
+                    let _ = Text::new("Fade Out", Point::new(30, 10), fade_out_style.clone())
+                        .draw(band);
+                },
+            );
+        })
+        .call(|_| println!("{}", tr!(i18n::FONT_ZOOM)))
+        .then(700, move |manager, frame| {
+            zoom_style.font_size = zoom.at(frame.progress);
+            // 每帧按当前字号重新居中
+            let width = zoom_style
+                .measure_string("ECOS", Point::zero(), Baseline::Top)
+                .bounding_box
+                .size
+                .width as i32;
+            let position = Point::new(
+                (band_size.width as i32 - width) / 2,
+                (band_size.height as i32 - zoom_style.font_size as i32) / 2,
+            );
+            redraw_band(
+                manager,
+                &mut zoom_band,
+                band_origin,
+                theme.background,
+                |band| {
+                    let _ = Text::new("ECOS", position, zoom_style.clone()).draw(band);
+                },
+            );
+        })
+        .wait(800)
+        // 最后清屏
+        .call(clear_screen(theme.background))
+}
+
+// 逐字着色用的彩虹色
+static RAINBOW: [DisplayColor; 6] = [
+    DisplayColor::RED,
+    DisplayColor::YELLOW,
+    DisplayColor::GREEN,
+    DisplayColor::CYAN,
+    DisplayColor::BLUE,
+    DisplayColor::MAGENTA,
+];
+
+// 演示6: 文字特效（描边、阴影、渐变、逐字着色）
+fn text_effects_demo(font: Font<'static>, size: Size) -> DemoTimeline {
+    use embedded_graphics::primitives::PrimitiveStyle;
+
+    println!(
+        "{}",
+        tr!(i18n::FONT_DEMO_HEADER, 6, i18n::FONT_DEMO_EFFECTS)
+    );
+
+    // 画一个花哨的条纹背景，模拟贪吃蛇游戏里随机变化的背景色
+    let stripes = [
+        DisplayColor::RED,
+        DisplayColor::YELLOW,
+        DisplayColor::GREEN,
+        DisplayColor::CYAN,
+        DisplayColor::BLUE,
+        DisplayColor::MAGENTA,
+        DisplayColor::WHITE,
+        DisplayColor::BLACK,
     ];
 
-    // 动画：彩色文本滚动
-    println!("彩色文本滚动动画...");
-    println!("按 'q' 键退出动画\r\n");
-
-    let mut frame = 0;
-    let mut user_interrupted = false;
-    let mut prev_offsets: [i32; 6] = [0; 6];
-
-    while frame < 60 && !user_interrupted {
-        // 检查用户是否按了 'q' 键
-        if let Some(byte) = Uart::read_byte_nonblock() {
-            if byte == b'q' || byte == b'Q' {
-                println!("\r\n用户中断动画演示");
-                user_interrupted = true;
-                break;
-            }
-        }
+    let effects = [
+        // 描边：任何背景上都能看清
+        (
+            i18n::FONT_EFFECT_OUTLINE,
+            "描边 Outline",
+            Point::new(4, 4),
+            TextEffectBuilder::new(font.clone())
+                .font_size(16)
+                .text_color(DisplayColor::WHITE)
+                .outline(DisplayColor::BLACK)
+                .build(),
+        ),
+        // 阴影
+        (
+            i18n::FONT_EFFECT_SHADOW,
+            "阴影 Shadow",
+            Point::new(4, 28),
+            TextEffectBuilder::new(font.clone())
+                .font_size(16)
+                .text_color(DisplayColor::YELLOW)
+                .shadow(Point::new(2, 2), DisplayColor::BLACK)
+                .build(),
+        ),
+        // 竖直渐变 + 描边
+        (
+            i18n::FONT_EFFECT_VERTICAL,
+            "渐变 Vertical",
+            Point::new(4, 52),
+            TextEffectBuilder::new(font.clone())
+                .font_size(16)
+                .gradient(
+                    DisplayColor::YELLOW,
+                    DisplayColor::RED,
+                    GradientDirection::Vertical,
+                )
+                .outline(DisplayColor::BLACK)
+                .build(),
+        ),
+        // 水平渐变 + 阴影
+        (
+            i18n::FONT_EFFECT_HORIZONTAL,
+            "渐变 Horizontal",
+            Point::new(4, 76),
+            TextEffectBuilder::new(font.clone())
+                .font_size(16)
+                .gradient(
+                    DisplayColor::CYAN,
+                    DisplayColor::MAGENTA,
+                    GradientDirection::Horizontal,
+                )
+                .shadow(Point::new(1, 1), DisplayColor::BLACK)
+                .build(),
+        ),
+        // 逐字着色 + 描边
+        (
+            i18n::FONT_EFFECT_RAINBOW,
+            "ECOS 彩虹字",
+            Point::new(4, 100),
+            TextEffectBuilder::new(font)
+                .font_size(18)
+                .char_colors(&RAINBOW)
+                .outline(DisplayColor::BLACK)
+                .build(),
+        ),
+    ];
 
-        // 清除上一帧的内容
-        if frame > 0 {
-            for i in 0..6 {
-                let y = 10 + (i * 20) as i32;
-                // 清除前一帧文本区域（稍微扩大清除区域确保完全清除）
-                Rectangle::new(Point::new(prev_offsets[i] - 5, y - 5), Size::new(140, 25))
-                    .into_styled(PrimitiveStyle::with_fill(DisplayColor::BLACK))
-                    .draw(&mut manager.display)
+    let mut timeline = DemoTimeline::new()
+        .call(move |manager| {
+            let stripe_height = (size.height / stripes.len() as u32).max(1);
+            for (i, color) in stripes.iter().enumerate() {
+                Rectangle::new(
+                    Point::new(0, (i as u32 * stripe_height) as i32),
+                    Size::new(size.width, stripe_height),
+                )
+                .into_styled(PrimitiveStyle::with_fill(*color))
+                .draw(manager.target())
+                .unwrap_or_else(|e| {
+                    log::error!("{}", tr!(i18n::FONT_BACKGROUND_ERROR, format!("{:?}", e)));
+                });
+            }
+        })
+        .wait(500);
+
+    for (message, text, position, effect) in effects {
+        timeline = timeline
+            .call(move |manager| {
+                println!("{}", message);
+                effect
+                    .draw(text, position, manager.target())
                     .unwrap_or_else(|e| {
-                        println!("清除错误: {:?}", e);
+                        log::error!("{}", tr!(i18n::FONT_EFFECT_ERROR, format!("{:?}", e)));
+                        position
                     });
-            }
-        }
+            })
+            .wait(800);
+    }
 
-        // 绘制当前帧的多行彩色文本
-        for (i, style) in styles.iter().enumerate() {
-            let y = 10 + (i * 20) as i32;
-            let offset = ((frame as i32) * 2 + (i as i32) * 10) % 150 - 50;
+    timeline.wait(2000)
+}
 
-            // 保存当前偏移量用于下一帧清除
-            prev_offsets[i] = offset;
+// ===========================================
+// 文字特效：描边、阴影、渐变填充、逐字着色
+// ============================================
+
+/// 渐变方向
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum GradientDirection {
+    /// 从上到下
+    Vertical,
+    /// 从左到右
+    Horizontal,
+}
 
-            Text::new("ECOS Display Demo", Point::new(offset, y), style.clone())
-                .draw(&mut manager.display)
-                .unwrap();
-        }
+/// 文字填充方式
+#[derive(Clone, Debug)]
+enum TextFill<'c> {
+    /// 纯色
+    Solid(DisplayColor),
+    /// 两色渐变（覆盖整段文字的包围盒）
+    Gradient {
+        from: DisplayColor,
+        to: DisplayColor,
+        direction: GradientDirection,
+    },
+    /// 逐字着色，颜色不够时循环使用
+    PerChar(&'c [DisplayColor]),
+}
 
-        #[cfg(feature = "target-ui-sim")]
-        manager.update_window();
+/// 带特效的文字样式
+///
+/// 描边和阴影都是用同一份字形在偏移位置重复绘制实现的，
+/// 不需要读回背景像素，所以 ST7735 上也能用。
+#[derive(Clone, Debug)]
+pub(crate) struct TextEffect<'c> {
+    base: FontTextStyle<DisplayColor>,
+    fill: TextFill<'c>,
+    outline: Option<(DisplayColor, u8)>,
+    shadow: Option<(Point, DisplayColor)>,
+}
 
-        manager.delay.delay_ms(50);
-        frame += 1;
+/// 文字特效构建器，用法和 `FontTextStyleBuilder` 一致
+pub(crate) struct TextEffectBuilder<'c> {
+    effect: TextEffect<'c>,
+}
+
+impl<'c> TextEffectBuilder<'c> {
+    pub fn new(font: Font<'static>) -> Self {
+        Self {
+            effect: TextEffect {
+                base: FontTextStyleBuilder::new(font)
+                    .text_color(DisplayColor::WHITE)
+                    .build(),
+                fill: TextFill::Solid(DisplayColor::WHITE),
+                outline: None,
+                shadow: None,
+            },
+        }
+    }
+
+    /// 字体大小（像素）
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.effect.base.font_size = font_size;
+        self
     }
 
-    // 如果用户没有中断，继续淡入淡出效果
-    if !user_interrupted {
-        println!("淡入淡出效果...");
-        println!("按 'q' 键跳过此效果\r\n");
+    /// 纯色填充
+    pub fn text_color(mut self, color: DisplayColor) -> Self {
+        self.effect.fill = TextFill::Solid(color);
+        self
+    }
 
-        // 重新加载字体
-        let font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
-            Some(font) => font,
-            None => return false,
+    /// 渐变填充
+    pub fn gradient(
+        mut self,
+        from: DisplayColor,
+        to: DisplayColor,
+        direction: GradientDirection,
+    ) -> Self {
+        self.effect.fill = TextFill::Gradient {
+            from,
+            to,
+            direction,
         };
+        self
+    }
 
-        // 淡入效果
-        for brightness in 0..=15 {
-            // 检查用户是否按了 'q' 键
-            if let Some(byte) = Uart::read_byte_nonblock() {
-                if byte == b'q' || byte == b'Q' {
-                    println!("\r\n用户中断淡入效果");
-                    user_interrupted = true;
-                    break;
-                }
-            }
+    /// 逐字着色
+    pub fn char_colors(mut self, colors: &'c [DisplayColor]) -> Self {
+        self.effect.fill = TextFill::PerChar(colors);
+        self
+    }
 
-            // 先清除上一帧
-            if brightness > 0 {
-                Rectangle::new(Point::new(25, 40), Size::new(80, 25))
-                    .into_styled(PrimitiveStyle::with_fill(DisplayColor::BLACK))
-                    .draw(&mut manager.display)
-                    .unwrap_or_else(|e| {
-                        println!("清除错误: {:?}", e);
-                    });
-            }
+    /// 1 像素描边
+    pub fn outline(self, color: DisplayColor) -> Self {
+        self.outline_width(color, 1)
+    }
 
-            // 创建渐变颜色
-            let color = DisplayColor::new(brightness, brightness, brightness);
-            let style = FontTextStyleBuilder::new(font.clone())
-                .font_size(20)
-                .text_color(color)
-                .build();
+    /// 指定宽度的描边
+    pub fn outline_width(mut self, color: DisplayColor, width: u8) -> Self {
+        self.effect.outline = Some((color, width.max(1)));
+        self
+    }
 
-            Text::new("Fade In", Point::new(30, 50), style)
-                .draw(&mut manager.display)
-                .unwrap();
+    /// 投影，`offset` 为阴影相对文字的偏移
+    pub fn shadow(mut self, offset: Point, color: DisplayColor) -> Self {
+        self.effect.shadow = Some((offset, color));
+        self
+    }
 
-            #[cfg(feature = "target-ui-sim")]
-            manager.update_window();
+    pub fn build(self) -> TextEffect<'c> {
+        self.effect
+    }
+}
 
-            manager.delay.delay_ms(30);
+impl TextEffect<'_> {
+    /// 绘制文字，返回下一个字符的位置（和 `Text::draw` 一样）
+    pub fn draw<D>(&self, text: &str, position: Point, target: &mut D) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = DisplayColor>,
+    {
+        // 先画阴影，再画描边，最后画文字本体
+        if let Some((offset, color)) = self.shadow {
+            self.draw_solid(text, position + offset, color, target)?;
         }
 
-        // 如果不是用户中断，等待一下然后淡出
-        if !user_interrupted {
-            // 等待期间检查 'q' 键
-            for _ in 0..10 {
-                manager.delay.delay_ms(100);
-
-                #[cfg(feature = "target-ui-sim")]
-                manager.update_window();
-
-                if let Some(byte) = Uart::read_byte_nonblock() {
-                    if byte == b'q' || byte == b'Q' {
-                        println!("\r\n用户中断等待");
-                        user_interrupted = true;
-                        break;
+        if let Some((color, width)) = self.outline {
+            let width = width as i32;
+            for dy in -width..=width {
+                for dx in -width..=width {
+                    if dx != 0 || dy != 0 {
+                        self.draw_solid(text, position + Point::new(dx, dy), color, target)?;
                     }
                 }
             }
+        }
 
-            if !user_interrupted {
-                // 淡出效果
-                for brightness in (0..=15).rev() {
-                    // 检查用户是否按了 'q' 键
-                    if let Some(byte) = Uart::read_byte_nonblock() {
-                        if byte == b'q' || byte == b'Q' {
-                            println!("\r\n用户中断淡出效果");
-                            user_interrupted = true;
-                            break;
-                        }
-                    }
+        match self.fill {
+            TextFill::Solid(color) => self.draw_solid(text, position, color, target),
+            TextFill::Gradient {
+                from,
+                to,
+                direction,
+            } => {
+                let area = Text::new(text, position, self.base.clone()).bounding_box();
+                let mut gradient = GradientTarget {
+                    target,
+                    gradient: Gradient {
+                        area,
+                        from: from.into(),
+                        to: to.into(),
+                        direction,
+                    },
+                };
+                self.draw_solid(text, position, from, &mut gradient)
+            }
+            TextFill::PerChar(colors) => {
+                let mut next = position;
+                let mut buf = [0u8; 4];
+                for (i, ch) in text.chars().enumerate() {
+                    let color = if colors.is_empty() {
+                        DisplayColor::WHITE
+                    } else {
+                        colors[i % colors.len()]
+                    };
+                    let ch = ch.encode_utf8(&mut buf);
+                    self.draw_solid(ch, next, color, target)?;
+                    // 按字形的步进宽度往后挪；画出来的宽度只算有像素的字形，空格会变成 0
+                    next = self
+                        .base
+                        .measure_string(ch, next, Baseline::Alphabetic)
+                        .next_position;
+                }
+                Ok(next)
+            }
+        }
+    }
 
-                    // 先清除上一帧
-                    if brightness < 15 {
-                        Rectangle::new(Point::new(25, 40), Size::new(80, 25))
-                            .into_styled(PrimitiveStyle::with_fill(DisplayColor::BLACK))
-                            .draw(&mut manager.display)
-                            .unwrap_or_else(|e| {
-                                println!("清除错误: {:?}", e);
-                            });
-                    }
+    fn draw_solid<D>(
+        &self,
+        text: &str,
+        position: Point,
+        color: DisplayColor,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = DisplayColor>,
+    {
+        let _t = perf::scope("glyph");
+        let mut style = self.base.clone();
+        style.set_text_color(Some(color));
+        Text::new(text, position, style).draw(target)
+    }
+}
 
-                    // 创建渐变颜色
-                    let color = DisplayColor::new(brightness, brightness, brightness);
-                    let style = FontTextStyleBuilder::new(font.clone())
-                        .font_size(20)
-                        .text_color(color)
-                        .build();
+/// 覆盖一块区域的两色渐变
+#[derive(Clone, Copy)]
+struct Gradient {
+    area: Rectangle,
+    from: Rgb888,
+    to: Rgb888,
+    direction: GradientDirection,
+}
 
-                    Text::new("Fade Out", Point::new(30, 50), style)
-                        .draw(&mut manager.display)
-                        .unwrap();
+impl Gradient {
+    fn color_at(&self, point: Point) -> DisplayColor {
+        let (offset, length) = match self.direction {
+            GradientDirection::Vertical => {
+                (point.y - self.area.top_left.y, self.area.size.height as i32)
+            }
+            GradientDirection::Horizontal => {
+                (point.x - self.area.top_left.x, self.area.size.width as i32)
+            }
+        };
+        let length = (length - 1).max(1);
+        let t = offset.clamp(0, length);
 
-                    #[cfg(feature = "target-ui-sim")]
-                    manager.update_window();
+        let lerp = |a: u8, b: u8| -> u8 { (a as i32 + (b as i32 - a as i32) * t / length) as u8 };
 
-                    manager.delay.delay_ms(30);
-                }
-            }
-        }
+        Rgb888::new(
+            lerp(self.from.r(), self.to.r()),
+            lerp(self.from.g(), self.to.g()),
+            lerp(self.from.b(), self.to.b()),
+        )
+        .into()
     }
+}
 
-    // 最后清屏
-    manager
-        .display
-        .clear(DisplayColor::BLACK)
-        .unwrap_or_else(|e| {
-            println!("清屏错误: {:?}", e);
-        });
+/// 渐变绘制适配器：按像素在区域内的位置替换颜色
+struct GradientTarget<'a, T: DrawTarget> {
+    target: &'a mut T,
+    gradient: Gradient,
+}
 
-    #[cfg(feature = "target-ui-sim")]
-    manager.update_window();
+impl<T: DrawTarget<Color = DisplayColor>> DrawTarget for GradientTarget<'_, T> {
+    type Color = DisplayColor;
+    type Error = T::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let gradient = self.gradient;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, _)| Pixel(point, gradient.color_at(point))),
+        )
+    }
+}
 
-    // 返回是否被用户中断
-    user_interrupted
+impl<T: DrawTarget<Color = DisplayColor>> Dimensions for GradientTarget<'_, T> {
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
 }
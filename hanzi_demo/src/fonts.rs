@@ -0,0 +1,25 @@
+//! 字体
+//!
+//! 字体文件只打包一份，第一次用到时解析，之后字体演示和文本阅读器共用。
+//! 开机画面会提前加载，进演示时就不用再等。
+
+use crate::perf;
+
+use rusttype::Font;
+
+const HARMONYOS_SANS_SC: &[u8] = include_bytes!("../display/fonts/HarmonyOS_Sans_SC_Regular.ttf");
+
+static mut HARMONYOS: Option<Font<'static>> = None;
+
+/// HarmonyOS Sans SC，解析失败时返回 `None`
+#[allow(static_mut_refs)]
+pub(crate) fn harmony_sans() -> Option<Font<'static>> {
+    // 只在主循环里用，没有并发访问
+    unsafe {
+        if HARMONYOS.is_none() {
+            let _t = perf::scope("font.load");
+            HARMONYOS = Font::try_from_bytes(HARMONYOS_SANS_SC);
+        }
+        HARMONYOS.clone()
+    }
+}
@@ -0,0 +1,194 @@
+//! 内存帧缓冲
+//!
+//! 所有绘制先落到内存里，同时记下被改过的矩形（脏矩形），
+//! `present` 时每个脏矩形只设一次地址窗口、整块写到屏上。
+//! 不然每画一个点都是一次完整的 SPI 事务，贪吃蛇和编辑器在板子上卡得要死。
+//!
+//! 板子上的堆本来就在 8MB 的外部 RAM 里，128x128 的 Rgb565 缓冲只要 32KB。
+
+use crate::*;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// 最多同时记几块脏矩形，再多就合成一整块
+const MAX_DIRTY: usize = 8;
+
+/// 内存帧缓冲
+pub(crate) struct FrameBuffer {
+    size: Size,
+    pixels: Vec<DisplayColor>,
+    dirty: Vec<Rectangle>,
+}
+
+/// 一次刷新写了多少东西
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct FlushInfo {
+    pub rects: u32,
+    pub pixels: u32,
+}
+
+impl FrameBuffer {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            pixels: vec![DisplayColor::BLACK; (size.width * size.height) as usize],
+            // 第一次刷新要把整屏写上去
+            dirty: vec![Rectangle::new(Point::zero(), size)],
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size)
+    }
+
+    /// 标记一块区域需要重新写屏
+    pub fn mark_dirty(&mut self, area: Rectangle) {
+        let area = area.intersection(&self.bounds());
+        if area.is_zero_sized() {
+            return;
+        }
+
+        // 和已有的矩形重叠或者挨着就合并，合并后可能又碰到别的，所以递归
+        if let Some(i) = self.dirty.iter().position(|r| touches(r, &area)) {
+            let merged = union(&self.dirty.swap_remove(i), &area);
+            self.mark_dirty(merged);
+            return;
+        }
+
+        if self.dirty.len() >= MAX_DIRTY {
+            let all = self.dirty.drain(..).fold(area, |acc, r| union(&acc, &r));
+            self.dirty.push(all);
+        } else {
+            self.dirty.push(area);
+        }
+    }
+
+    /// 把脏矩形写到 `target`，每块一次窗口写
+    pub fn flush<D>(&mut self, target: &mut D) -> Result<FlushInfo, D::Error>
+    where
+        D: DrawTarget<Color = DisplayColor>,
+    {
+        let mut info = FlushInfo::default();
+        let width = self.size.width as usize;
+
+        for area in self.dirty.drain(..) {
+            let x = area.top_left.x as usize;
+            let y = area.top_left.y as usize;
+            let w = area.size.width as usize;
+            let h = area.size.height as usize;
+
+            let rows = (y..y + h).flat_map(|row| {
+                let start = row * width + x;
+                self.pixels[start..start + w].iter().copied()
+            });
+            target.fill_contiguous(&area, rows)?;
+
+            info.rects += 1;
+            info.pixels += (w * h) as u32;
+        }
+
+        Ok(info)
+    }
+
+    /// 第 `y` 行的像素，截图用
+    pub fn row(&self, y: u32) -> &[DisplayColor] {
+        let start = (y * self.size.width) as usize;
+        &self.pixels[start..start + self.size.width as usize]
+    }
+
+    fn fill_row(&mut self, y: i32, x0: i32, x1: i32, color: DisplayColor) {
+        let start = (y as u32 * self.size.width) as usize;
+        self.pixels[start + x0 as usize..start + x1 as usize].fill(color);
+    }
+}
+
+// 两个矩形重叠或者边挨着
+fn touches(a: &Rectangle, b: &Rectangle) -> bool {
+    let (a0, a1) = (a.top_left, a.top_left + a.size);
+    let (b0, b1) = (b.top_left, b.top_left + b.size);
+    a0.x <= b1.x && b0.x <= a1.x && a0.y <= b1.y && b0.y <= a1.y
+}
+
+// 能同时框住两个矩形的最小矩形
+fn union(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    let (a0, a1) = (a.top_left, a.top_left + a.size);
+    let (b0, b1) = (b.top_left, b.top_left + b.size);
+    let top_left = Point::new(a0.x.min(b0.x), a0.y.min(b0.y));
+    let bottom_right = Point::new(a1.x.max(b1.x), a1.y.max(b1.y));
+    Rectangle::new(
+        top_left,
+        Size::new(
+            (bottom_right.x - top_left.x) as u32,
+            (bottom_right.y - top_left.y) as u32,
+        ),
+    )
+}
+
+impl DrawTarget for FrameBuffer {
+    type Color = DisplayColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (w, h) = (self.size.width as i32, self.size.height as i32);
+        // 一次调用里画到的范围合成一块脏矩形，不按点记
+        let (mut min, mut max) = (Point::new(w, h), Point::new(-1, -1));
+
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 && point.x < w && point.y < h {
+                self.pixels[(point.y * w + point.x) as usize] = color;
+                min = min.component_min(point);
+                max = max.component_max(point);
+            }
+        }
+
+        if max.x >= min.x {
+            self.mark_dirty(Rectangle::with_corners(min, max));
+        }
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let bounds = self.bounds();
+        let w = self.size.width as i32;
+        for (point, color) in area.points().zip(colors) {
+            if bounds.contains(point) {
+                self.pixels[(point.y * w + point.x) as usize] = color;
+            }
+        }
+        self.mark_dirty(*area);
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounds());
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+        let x0 = area.top_left.x;
+        let x1 = x0 + area.size.width as i32;
+        for y in area.rows() {
+            self.fill_row(y, x0, x1, color);
+        }
+        self.mark_dirty(area);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels.fill(color);
+        self.dirty.clear();
+        self.dirty.push(self.bounds());
+        Ok(())
+    }
+}
+
+impl OriginDimensions for FrameBuffer {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
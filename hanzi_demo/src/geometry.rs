@@ -0,0 +1,107 @@
+//! 屏幕几何：面板尺寸 + 安装方向
+//!
+//! 面板尺寸是屏幕本身的像素数，旋转 90°/270° 后应用看到的宽高互换。
+//! 各模块布局一律用 [`crate::DisplayBackend::size`] 返回的逻辑尺寸，不要再写死 128。
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// 安装方向（顺时针）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub(crate) enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+#[allow(unused)]
+impl Rotation {
+    pub fn from_degrees(degrees: u16) -> Option<Self> {
+        match degrees {
+            0 => Some(Rotation::Deg0),
+            90 => Some(Rotation::Deg90),
+            180 => Some(Rotation::Deg180),
+            270 => Some(Rotation::Deg270),
+            _ => None,
+        }
+    }
+
+    pub fn degrees(self) -> u16 {
+        match self {
+            Rotation::Deg0 => 0,
+            Rotation::Deg90 => 90,
+            Rotation::Deg180 => 180,
+            Rotation::Deg270 => 270,
+        }
+    }
+
+    /// 宽高是否互换
+    pub fn is_swapped(self) -> bool {
+        matches!(self, Rotation::Deg90 | Rotation::Deg270)
+    }
+}
+
+/// 屏幕几何
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Geometry {
+    /// 面板宽度（像素，未旋转）
+    pub width: u32,
+    /// 面板高度（像素，未旋转）
+    pub height: u32,
+    pub rotation: Rotation,
+}
+
+#[allow(unused)]
+impl Geometry {
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            rotation: Rotation::Deg0,
+        }
+    }
+
+    pub const fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// 应用看到的尺寸（已按旋转互换宽高）
+    pub fn size(&self) -> Size {
+        if self.rotation.is_swapped() {
+            Size::new(self.height, self.width)
+        } else {
+            Size::new(self.width, self.height)
+        }
+    }
+
+    /// 整个屏幕的矩形
+    pub fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.size())
+    }
+
+    /// 解析 `160x128` 或 `160x128@90` 这样的写法
+    pub fn parse(text: &str) -> Option<Self> {
+        let (size, rotation) = match text.trim().split_once('@') {
+            Some((size, degrees)) => (size, degrees.trim().parse().ok()?),
+            None => (text.trim(), 0),
+        };
+        let (width, height) = size.split_once(['x', 'X', '*'])?;
+        let width = width.trim().parse().ok().filter(|w| *w > 0)?;
+        let height = height.trim().parse().ok().filter(|h| *h > 0)?;
+        Some(Self::new(width, height).rotation(Rotation::from_degrees(rotation)?))
+    }
+}
+
+impl core::fmt::Display for Geometry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}x{}@{}",
+            self.width,
+            self.height,
+            self.rotation.degrees()
+        )
+    }
+}
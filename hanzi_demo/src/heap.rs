@@ -0,0 +1,452 @@
+//! 堆用量统计
+//!
+//! 在全局分配器外面包一层 `Tracking`，记当前用了多少、峰值、分配和释放次数。
+//! `free` 看一行概况，`meminfo` 看详细；应用退出时打一条日志，说它跑的这段时间堆最高用到多少。
+//!
+//! 模拟器包的是系统分配器。板子上关掉了 ecos-ssc1 的 `alloc` 特性（它自己注册全局分配器，外面套不上），
+//! 换成这里的 `FreeList`，管 `_heap_start` 到内存末尾这一段，所以板子上还能看到最大空闲块。
+
+#[allow(unused)]
+use crate::*;
+
+use crate::i18n::{self, tr};
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use embedded_cli::Command;
+
+/// 某一时刻的统计
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Stats {
+    /// 当前用了多少字节
+    pub current: usize,
+    /// 开机以来最多用到多少
+    pub peak: usize,
+    pub allocs: usize,
+    pub frees: usize,
+    pub reallocs: usize,
+    /// 分配失败的次数
+    pub failed: usize,
+    /// 最大的连续空闲块，分配器不公开时是 None
+    pub largest_free: Option<usize>,
+}
+
+impl Stats {
+    /// 还没释放的块数
+    pub fn live(&self) -> usize {
+        self.allocs.saturating_sub(self.frees)
+    }
+}
+
+// ===========================================
+// 记账
+// ============================================
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+// 从上次 mark 起的峰值
+static MARK_PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static FREES: AtomicUsize = AtomicUsize::new(0);
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+static FAILED: AtomicUsize = AtomicUsize::new(0);
+
+/// 记账的分配器，真正分配交给 `A`
+pub(crate) struct Tracking<A> {
+    inner: A,
+}
+
+impl<A> Tracking<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+fn grow(size: usize) {
+    let now = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(now, Ordering::Relaxed);
+    MARK_PEAK.fetch_max(now, Ordering::Relaxed);
+}
+
+fn shrink(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+// 分配器里不能再分配，这里只动原子计数
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Tracking<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if ptr.is_null() {
+            FAILED.fetch_add(1, Ordering::Relaxed);
+        } else {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if ptr.is_null() {
+            FAILED.fetch_add(1, Ordering::Relaxed);
+        } else {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.inner.dealloc(ptr, layout) };
+        FREES.fetch_add(1, Ordering::Relaxed);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if new_ptr.is_null() {
+            // 失败时原来的块还在
+            FAILED.fetch_add(1, Ordering::Relaxed);
+        } else {
+            REALLOCS.fetch_add(1, Ordering::Relaxed);
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// 当前统计
+pub(crate) fn stats() -> Stats {
+    Stats {
+        current: CURRENT.load(Ordering::Relaxed),
+        peak: PEAK.load(Ordering::Relaxed),
+        allocs: ALLOCS.load(Ordering::Relaxed),
+        frees: FREES.load(Ordering::Relaxed),
+        reallocs: REALLOCS.load(Ordering::Relaxed),
+        failed: FAILED.load(Ordering::Relaxed),
+        largest_free: allocator::largest_free(),
+    }
+}
+
+/// 从现在开始另记一个峰值，应用启动时调用，返回当前用量
+pub(crate) fn mark() -> usize {
+    let now = CURRENT.load(Ordering::Relaxed);
+    MARK_PEAK.store(now, Ordering::Relaxed);
+    now
+}
+
+/// 上次 [`mark`] 以来的峰值
+pub(crate) fn mark_peak() -> usize {
+    MARK_PEAK.load(Ordering::Relaxed)
+}
+
+// ===========================================
+// 全局分配器
+// ============================================
+
+#[cfg(not(feature = "need-ecos"))]
+mod allocator {
+    use super::Tracking;
+
+    #[global_allocator]
+    static ALLOCATOR: Tracking<std::alloc::System> = Tracking::new(std::alloc::System);
+
+    // 系统分配器不公开空闲块
+    pub(crate) fn largest_free() -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(feature = "need-ecos")]
+mod allocator {
+    use super::{FreeList, Tracking};
+
+    use core::alloc::{GlobalAlloc, Layout};
+
+    // 链接脚本里堆的起点，终点是 8MB 内存的末尾
+    unsafe extern "C" {
+        static _heap_start: u8;
+    }
+    const HEAP_END: usize = 0x0480_0000;
+
+    // 单核，中断里不分配，不用加锁。
+    // SDK 说过板子上原子的 compare_exchange 有特权级问题，也加不了。
+    static mut HEAP: FreeList = FreeList::new();
+    static mut READY: bool = false;
+
+    #[allow(static_mut_refs)]
+    fn heap() -> &'static mut FreeList {
+        unsafe {
+            // 启动代码只清零 .bss，第一次分配时再把整段挂上
+            if !READY {
+                HEAP.init(&raw const _heap_start as usize, HEAP_END);
+                READY = true;
+            }
+            &mut HEAP
+        }
+    }
+
+    /// 板子上的堆
+    pub(crate) struct BoardHeap;
+
+    unsafe impl GlobalAlloc for BoardHeap {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            heap().alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { heap().dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Tracking<BoardHeap> = Tracking::new(BoardHeap);
+
+    pub(crate) fn largest_free() -> Option<usize> {
+        Some(heap().largest())
+    }
+}
+
+// ===========================================
+// 空闲链表（板子上的堆，主机上只测试用）
+// ============================================
+
+/// 空闲块的头，放在空闲块的开头，分配出去的块不带头
+#[cfg(any(feature = "need-ecos", test))]
+struct FreeBlock {
+    size: usize,
+    next: *mut FreeBlock,
+}
+
+/// 按地址排序的空闲链表，首次适配，释放时和前后相邻的空闲块合并
+///
+/// 块的起点和大小都按 [`FreeList::UNIT`] 对齐，切剩下的零头至少放得下一个块头。
+/// 分配出去的块不记大小，释放时按 `Layout` 算回来，`GlobalAlloc` 保证两边的 `Layout` 一样。
+#[cfg(any(feature = "need-ecos", test))]
+pub(crate) struct FreeList {
+    head: *mut FreeBlock,
+}
+
+#[cfg(any(feature = "need-ecos", test))]
+impl FreeList {
+    const UNIT: usize = core::mem::size_of::<FreeBlock>();
+
+    pub const fn new() -> Self {
+        Self {
+            head: core::ptr::null_mut(),
+        }
+    }
+
+    /// 把 `start..end` 整段挂成一个空闲块
+    ///
+    /// # Safety
+    /// 这段内存之后只归这个链表管
+    pub unsafe fn init(&mut self, start: usize, end: usize) {
+        let start = start.next_multiple_of(Self::UNIT);
+        let end = end & !(Self::UNIT - 1);
+        self.head = core::ptr::null_mut();
+        if end > start {
+            unsafe { self.insert(start, end - start) };
+        }
+    }
+
+    fn block_size(layout: Layout) -> usize {
+        layout.size().max(1).next_multiple_of(Self::UNIT)
+    }
+
+    pub fn alloc(&mut self, layout: Layout) -> *mut u8 {
+        let size = Self::block_size(layout);
+        let align = layout.align().max(Self::UNIT);
+
+        let mut prev: *mut FreeBlock = core::ptr::null_mut();
+        let mut current = self.head;
+        while !current.is_null() {
+            let (start, block_size, next) = unsafe {
+                let block = &*current;
+                (current as usize, block.size, block.next)
+            };
+            let end = start + block_size;
+            let data = start.next_multiple_of(align);
+            if data + size <= end {
+                // 先把整块摘下来，前后切剩的再挂回去
+                unsafe {
+                    match prev.is_null() {
+                        true => self.head = next,
+                        false => (*prev).next = next,
+                    }
+                    if data > start {
+                        self.insert(start, data - start);
+                    }
+                    if end > data + size {
+                        self.insert(data + size, end - data - size);
+                    }
+                }
+                return data as *mut u8;
+            }
+            prev = current;
+            current = next;
+        }
+        core::ptr::null_mut()
+    }
+
+    /// # Safety
+    /// `ptr` 是这个链表用同一个 `layout` 分配出去的
+    pub unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.insert(ptr as usize, Self::block_size(layout)) };
+    }
+
+    /// 最大的连续空闲块
+    pub fn largest(&self) -> usize {
+        let mut largest = 0;
+        let mut current = self.head;
+        while !current.is_null() {
+            let block = unsafe { &*current };
+            largest = largest.max(block.size);
+            current = block.next;
+        }
+        largest
+    }
+
+    // 按地址插进链表，和前后挨着的块合并
+    unsafe fn insert(&mut self, start: usize, size: usize) {
+        let mut prev: *mut FreeBlock = core::ptr::null_mut();
+        let mut next = self.head;
+        while !next.is_null() && (next as usize) < start {
+            prev = next;
+            next = unsafe { (*next).next };
+        }
+
+        let block = start as *mut FreeBlock;
+        unsafe {
+            block.write(FreeBlock { size, next });
+            if !next.is_null() && start + size == next as usize {
+                (*block).size += (*next).size;
+                (*block).next = (*next).next;
+            }
+            if prev.is_null() {
+                self.head = block;
+            } else if prev as usize + (*prev).size == start {
+                (*prev).size += (*block).size;
+                (*prev).next = (*block).next;
+            } else {
+                (*prev).next = block;
+            }
+        }
+    }
+}
+
+#[derive(Command, Debug)]
+pub(crate) enum HeapCommand {
+    /// 堆用量概况
+    #[command(name = "free")]
+    Free,
+
+    /// 堆的详细统计：用量、峰值、分配次数
+    #[command(name = "meminfo")]
+    Info,
+}
+
+pub(crate) fn handle_heap_command(command: HeapCommand) -> Result<(), core::convert::Infallible> {
+    let stats = stats();
+    match command {
+        HeapCommand::Free => {
+            println!(
+                "\r\n{}\r\n",
+                tr!(i18n::MEM_FREE, stats.current, stats.peak, stats.live())
+            );
+        }
+        HeapCommand::Info => {
+            println!("\r\n{}\r", tr!(i18n::MEM_CURRENT, stats.current));
+            println!("{}\r", tr!(i18n::MEM_PEAK, stats.peak));
+            println!("{}\r", tr!(i18n::MEM_LIVE, stats.live()));
+            println!(
+                "{}\r",
+                tr!(i18n::MEM_COUNTS, stats.allocs, stats.frees, stats.reallocs)
+            );
+            println!("{}\r", tr!(i18n::MEM_FAILED, stats.failed));
+            match stats.largest_free {
+                Some(size) => println!("{}\r\n", tr!(i18n::MEM_LARGEST, size)),
+                None => println!("{}\r\n", tr!(i18n::MEM_LARGEST_UNKNOWN)),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIT: usize = FreeList::UNIT;
+
+    /// 测试用的一段"堆"，按 64 字节对齐
+    #[repr(align(64))]
+    struct Arena([u8; 1024]);
+
+    fn with_list(test: impl FnOnce(&mut FreeList, usize)) {
+        let mut arena = Arena([0; 1024]);
+        let start = arena.0.as_mut_ptr() as usize;
+        let mut list = FreeList::new();
+        unsafe { list.init(start, start + arena.0.len()) };
+        test(&mut list, start);
+    }
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn alloc_is_first_fit_and_merges_on_free() {
+        with_list(|list, start| {
+            let a = list.alloc(layout(UNIT, 1));
+            let b = list.alloc(layout(3 * UNIT, 1));
+            let c = list.alloc(layout(UNIT, 1));
+            assert_eq!(a as usize, start);
+            assert_eq!(b as usize, start + UNIT);
+            assert_eq!(c as usize, start + 4 * UNIT);
+            assert_eq!(list.largest(), 1024 - 5 * UNIT);
+
+            // b 空出来的洞放得下就先用洞
+            unsafe { list.dealloc(b, layout(3 * UNIT, 1)) };
+            assert_eq!(list.alloc(layout(UNIT, 1)) as usize, start + UNIT);
+
+            // 全放回去又合成一整块
+            unsafe {
+                list.dealloc(a, layout(UNIT, 1));
+                list.dealloc((start + UNIT) as *mut u8, layout(UNIT, 1));
+            }
+            assert_eq!(list.largest(), 1024 - 5 * UNIT);
+            unsafe { list.dealloc(c, layout(UNIT, 1)) };
+            assert_eq!(list.largest(), 1024);
+        });
+    }
+
+    #[test]
+    fn alloc_honours_alignment() {
+        with_list(|list, start| {
+            let small = list.alloc(layout(1, 1));
+            let aligned = list.alloc(layout(8, 64));
+            assert_eq!(aligned as usize % 64, 0);
+            assert_eq!(aligned as usize, start + 64);
+            // 对齐切下来的零头还能用
+            assert_eq!(list.alloc(layout(UNIT, 1)) as usize, start + UNIT);
+            unsafe {
+                list.dealloc(small, layout(1, 1));
+                list.dealloc(aligned, layout(8, 64));
+                list.dealloc((start + UNIT) as *mut u8, layout(UNIT, 1));
+            }
+            assert_eq!(list.largest(), 1024);
+        });
+    }
+
+    #[test]
+    fn alloc_fails_when_no_block_fits() {
+        with_list(|list, _| {
+            let a = list.alloc(layout(512, 1));
+            assert!(!a.is_null());
+            assert!(list.alloc(layout(1024, 1)).is_null());
+            assert_eq!(list.largest(), 512);
+        });
+    }
+}
@@ -0,0 +1,135 @@
+//! 命令说明
+//!
+//! `embedded-cli` 自带的 `help` 是从 `#[derive(Command)]` 的文档注释生成的，只有一种语言，
+//! 所以关掉了它的 `help` 特性，改由 [`WithHelp`] 在命令解析前截下 `help`、`help <命令>`、
+//! `<命令> --help`（`-h`），从 [`i18n`] 的消息表按当前语言打印。
+//! 加命令时记得在 [`TOPICS`] 里加一条，说明写在 i18n.rs 的"命令说明"一节。
+
+#[allow(unused)]
+use crate::*;
+
+use crate::i18n::{self, Lines, tr};
+
+use embedded_cli::cli::CliHandle;
+use embedded_cli::command::RawCommand;
+use embedded_cli::help::HelpRequest;
+use embedded_cli::service::{CommandProcessor, ProcessError};
+use embedded_io::Write;
+
+/// 一个命令的说明
+struct Topic {
+    name: &'static str,
+    /// 第一行用法，第二行一句话说明，后面是参数
+    text: Lines,
+}
+
+const fn topic(name: &'static str, text: Lines) -> Topic {
+    Topic { name, text }
+}
+
+/// 编进来的所有命令，按 `help` 列出的顺序
+const TOPICS: &[Topic] = &[
+    topic("help", i18n::HELP_HELP),
+    topic("menu", i18n::HELP_MENU),
+    #[cfg(feature = "cmd-cli")]
+    topic("hello", i18n::HELP_HELLO),
+    #[cfg(feature = "cmd-cli")]
+    topic("plain", i18n::HELP_PLAIN),
+    #[cfg(feature = "cmd-cli")]
+    topic("array", i18n::HELP_ARRAY),
+    #[cfg(feature = "cmd-cli")]
+    topic("point", i18n::HELP_POINT),
+    #[cfg(feature = "cmd-font")]
+    topic("font", i18n::HELP_FONT),
+    #[cfg(feature = "cmd-font")]
+    topic("marquee", i18n::HELP_MARQUEE),
+    #[cfg(feature = "cmd-text")]
+    topic("reader", i18n::HELP_READER),
+    #[cfg(feature = "cmd-text")]
+    topic("editor", i18n::HELP_EDITOR),
+    #[cfg(feature = "cmd-text")]
+    topic("list", i18n::HELP_LIST),
+    #[cfg(feature = "cmd-text")]
+    topic("new", i18n::HELP_NEW),
+    #[cfg(feature = "cmd-text")]
+    topic("open", i18n::HELP_OPEN),
+    #[cfg(feature = "cmd-snake")]
+    topic("snake", i18n::HELP_SNAKE),
+    #[cfg(feature = "cmd-snake")]
+    topic("snake-difficulty", i18n::HELP_SNAKE_DIFFICULTY),
+    #[cfg(feature = "cmd-snake")]
+    topic("snake-help", i18n::HELP_SNAKE_HELP),
+    #[cfg(feature = "cmd-snake")]
+    topic("snake-seed", i18n::HELP_SNAKE_SEED),
+    #[cfg(feature = "cmd-image")]
+    topic("show", i18n::HELP_SHOW),
+    topic("fps", i18n::HELP_FPS),
+    topic("brightness", i18n::HELP_BRIGHTNESS),
+    topic("idle", i18n::HELP_IDLE),
+    topic("theme", i18n::HELP_THEME),
+    topic("screenshot", i18n::HELP_SCREENSHOT),
+    topic("record", i18n::HELP_RECORD),
+    topic("get", i18n::HELP_GET),
+    topic("set", i18n::HELP_SET),
+    topic("settings", i18n::HELP_SETTINGS),
+    topic("log", i18n::HELP_LOG),
+    topic("dmesg", i18n::HELP_DMESG),
+    topic("free", i18n::HELP_FREE),
+    topic("meminfo", i18n::HELP_MEMINFO),
+    topic("perf", i18n::HELP_PERF),
+    #[cfg(feature = "panic-handler")]
+    topic("lastpanic", i18n::HELP_LASTPANIC),
+    #[cfg(feature = "target-ui-sim")]
+    topic("quit", i18n::HELP_QUIT),
+];
+
+/// 包在命令处理器外面，帮助请求自己答，其余的照常交给里面的处理器
+pub(crate) struct WithHelp<P>(pub P);
+
+impl<W, E, P> CommandProcessor<W, E> for WithHelp<P>
+where
+    W: Write<Error = E>,
+    E: embedded_io::Error,
+    P: CommandProcessor<W, E>,
+{
+    fn process<'a>(
+        &mut self,
+        cli: &mut CliHandle<'_, W, E>,
+        raw: RawCommand<'a>,
+    ) -> Result<(), ProcessError<'a, E>> {
+        match HelpRequest::from_command(&raw) {
+            Some(request) => {
+                print_help(request);
+                Ok(())
+            }
+            None => self.0.process(cli, raw),
+        }
+    }
+}
+
+fn print_help(request: HelpRequest<'_>) {
+    match request {
+        HelpRequest::All => {
+            println!("\r\n{}\r", tr!(i18n::HELP_HEADER));
+            let width = TOPICS.iter().map(|t| t.name.len()).max().unwrap_or(0);
+            for topic in TOPICS {
+                let summary = topic.text.get().get(1).copied().unwrap_or("");
+                println!("  {:<width$}  {}\r", topic.name, summary);
+            }
+            println!("\r");
+        }
+        HelpRequest::Command(command) => {
+            let name = command.name();
+            match TOPICS.iter().find(|t| t.name == name) {
+                Some(topic) => {
+                    println!("\r");
+                    for line in topic.text.get() {
+                        println!("{}\r", line);
+                    }
+                    println!("\r");
+                }
+                None => println!("\r\n{}\r\n", tr!(i18n::HELP_UNKNOWN, name)),
+            }
+        }
+    }
+}
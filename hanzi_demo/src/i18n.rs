@@ -0,0 +1,1410 @@
+//! 多语言
+//!
+//! 串口上打印的提示、帮助和错误都从这里的消息表取，按当前语言挑中文或英文。
+//! 默认中文，编译时开 `lang-en` 特性默认英文；运行时 `set lang en` 切换并记到设置里。
+//!
+//! 消息里的 `{}` 由 [`tr!`] 按顺序填参数，对齐、小数位这些格式在调用处先 `format!` 好再传进来。
+//! 屏幕上的字（状态栏、菜单）大多用只有 ASCII 的点阵字体，写死英文；带 TTF 字体的阅读器里的文件列表走这里，
+//! 中文由部件换成 TTF 画。
+//! 演示内容（示例文档、字体演示里的汉字）是演示本身，也不翻译。
+//! 命令说明（`help`）也在这里，不用 `embedded-cli` 从文档注释生成的那份，见 [`crate::help`]。
+
+#[allow(unused)]
+use crate::*;
+
+use core::fmt::{Display, Write};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// 语言
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Lang {
+    Zh,
+    En,
+}
+
+impl Lang {
+    /// 设置里存的代码
+    pub const fn code(self) -> &'static str {
+        match self {
+            Lang::Zh => "zh",
+            Lang::En => "en",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Lang> {
+        match code {
+            "zh" | "zh-CN" => Some(Lang::Zh),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+}
+
+/// 编译时选的默认语言
+#[cfg(not(feature = "lang-en"))]
+pub(crate) const DEFAULT: Lang = Lang::Zh;
+#[cfg(feature = "lang-en")]
+pub(crate) const DEFAULT: Lang = Lang::En;
+
+static CURRENT: AtomicU8 = AtomicU8::new(DEFAULT as u8);
+
+/// 当前语言
+pub(crate) fn current() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+/// 按代码切换语言，不认识的代码返回 `false`
+pub(crate) fn set(code: &str) -> bool {
+    match Lang::from_code(code) {
+        Some(lang) => {
+            CURRENT.store(lang as u8, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 一条消息
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Msg {
+    zh: &'static str,
+    en: &'static str,
+}
+
+impl Msg {
+    const fn new(zh: &'static str, en: &'static str) -> Self {
+        Self { zh, en }
+    }
+
+    /// 当前语言的文字
+    pub fn get(&self) -> &'static str {
+        match current() {
+            Lang::Zh => self.zh,
+            Lang::En => self.en,
+        }
+    }
+}
+
+impl Display for Msg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.get())
+    }
+}
+
+/// 一段多行的帮助，每行单独打印
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Lines {
+    zh: &'static [&'static str],
+    en: &'static [&'static str],
+}
+
+impl Lines {
+    const fn new(zh: &'static [&'static str], en: &'static [&'static str]) -> Self {
+        Self { zh, en }
+    }
+
+    /// 当前语言的各行
+    pub fn get(&self) -> &'static [&'static str] {
+        match current() {
+            Lang::Zh => self.zh,
+            Lang::En => self.en,
+        }
+    }
+
+    #[cfg(any(feature = "cmd-font", feature = "cmd-snake", feature = "cmd-text"))]
+    pub fn print(&self) {
+        for line in self.get() {
+            println!("{}", line);
+        }
+    }
+}
+
+/// 按顺序把参数填进 `{}`，多出来的 `{}` 原样留着
+pub(crate) fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len() + 16);
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => {
+                let _ = write!(out, "{}", arg);
+            }
+            None => out.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 取当前语言的消息，带参数时按顺序填进 `{}`
+///
+/// ```ignore
+/// println!("{}", tr!(i18n::FILE_NOT_FOUND));
+/// println!("{}", tr!(i18n::TEXT_OPEN, filename));
+/// ```
+macro_rules! tr {
+    ($msg:expr) => {
+        $msg.get()
+    };
+    ($msg:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($msg.get(), &[$(&$arg as &dyn core::fmt::Display),+])
+    };
+}
+pub(crate) use tr;
+
+// ===========================================
+// 开机、主循环
+// ============================================
+
+pub(crate) const BOOT_STARTING: Msg = Msg::new("{} v{} 启动中...", "{} v{} starting...");
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+pub(crate) const BOOT_LOADING_FONT: Msg = Msg::new("加载字体", "Loading font");
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+pub(crate) const BOOT_FONT_LOADED: Msg =
+    Msg::new("字体加载成功，用时 {} ms", "Font loaded in {} ms");
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+pub(crate) const BOOT_FONT_FAILED: Msg = Msg::new("字体加载失败！", "Failed to load font!");
+pub(crate) const BOOT_INIT_CLI: Msg = Msg::new("初始化命令行", "Starting command line");
+pub(crate) const BOOT_DONE: Msg = Msg::new("启动完成", "Ready");
+pub(crate) const BACK_TO_SHELL: Msg = Msg::new("返回命令行模式...", "Back to the command line...");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const QUITTING: Msg = Msg::new("正在退出程序...", "Exiting...");
+pub(crate) const UNSUPPORTED_COMMAND: Msg =
+    Msg::new("暂时不支持命令：\n{}", "Unsupported command:\n{}");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const UART_SIM_READY: Msg = Msg::new("UART 模拟器初始化完成", "UART simulator ready");
+
+// ===========================================
+// 应用调度
+// ============================================
+
+pub(crate) const APP_START_FAILED: Msg = Msg::new("{} 启动失败", "{} failed to start");
+pub(crate) const APP_EXITED: Msg = Msg::new("退出 {}", "Left {}");
+pub(crate) const APP_MEM_PEAK: Msg = Msg::new(
+    "{} 运行期间堆最多用到 {} 字节，比进入时多 {} 字节",
+    "{} peaked at {} heap bytes, {} more than on entry",
+);
+
+// ===========================================
+// 显示后端
+// ============================================
+
+#[cfg(feature = "target-st7735")]
+pub(crate) const DISPLAY_MANAGER_CREATED: Msg =
+    Msg::new("显示管理器创建成功", "Display manager created");
+#[cfg(feature = "target-st7735")]
+pub(crate) const DISPLAY_MANAGER_FAILED: Msg = Msg::new(
+    "显示管理器创建失败: {}",
+    "Failed to create display manager: {}",
+);
+#[cfg(feature = "target-st7735")]
+pub(crate) const DISPLAY_INIT_OK: Msg = Msg::new("显示初始化成功！", "Display initialised!");
+#[cfg(feature = "target-st7735")]
+pub(crate) const DISPLAY_INIT_FAILED: Msg =
+    Msg::new("显示初始化失败: {}", "Display initialisation failed: {}");
+#[cfg(feature = "target-st7735")]
+pub(crate) const DISPLAY_ORIENTATION_FAILED: Msg =
+    Msg::new("设置屏幕方向失败", "Failed to set screen orientation");
+#[cfg(feature = "target-st7735")]
+pub(crate) const DISPLAY_GEOMETRY: Msg = Msg::new("屏幕几何: {}", "Screen geometry: {}");
+#[cfg(feature = "framebuffer")]
+pub(crate) const DISPLAY_FLUSH_FAILED: Msg =
+    Msg::new("刷新屏幕失败: {}", "Failed to refresh the screen: {}");
+#[cfg(feature = "target-led8x8")]
+pub(crate) const LED_MATRIX_READY: Msg =
+    Msg::new("8x8 点阵初始化成功！", "8x8 LED matrix initialised!");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const SIM_DISPLAY_READY: Msg = Msg::new(
+    "模拟器显示初始化成功！屏幕几何: {}",
+    "Simulator display initialised! Screen geometry: {}",
+);
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const SIM_GEOMETRY_INVALID: Msg = Msg::new(
+    "HANZI_DISPLAY 格式不对: {}，使用设置里的 {}",
+    "Invalid HANZI_DISPLAY: {}, using {} from the settings",
+);
+pub(crate) const FPS_STATS: Msg = Msg::new(
+    "{} fps, {} 块/帧, {} 像素/帧",
+    "{} fps, {} rects/frame, {} pixels/frame",
+);
+pub(crate) const RECORD_UNSUPPORTED: Msg = Msg::new(
+    "当前显示后端不支持录制",
+    "This display backend cannot record",
+);
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const RECORD_ALREADY: Msg = Msg::new("已经在录制了", "Already recording");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const RECORD_NOT_STARTED: Msg = Msg::new("没有在录制", "Not recording");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const RECORD_ERROR: Msg = Msg::new(
+    "录制出错，已停止: {}",
+    "Recording failed and was stopped: {}",
+);
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const RECORD_CREATE_FAILED: Msg =
+    Msg::new("创建 {} 失败: {}", "Failed to create {}: {}");
+
+// ===========================================
+// 屏幕命令
+// ============================================
+
+pub(crate) const FPS_LAST_SECOND: Msg = Msg::new("最近一秒: {}", "Last second: {}");
+pub(crate) const FPS_UNSUPPORTED: Msg = Msg::new(
+    "当前显示后端不统计帧率",
+    "This display backend does not count frames",
+);
+pub(crate) const FPS_REPORT_ON: Msg = Msg::new("帧率打印已打开", "FPS reporting on");
+pub(crate) const FPS_REPORT_OFF: Msg = Msg::new("帧率打印已关闭", "FPS reporting off");
+pub(crate) const BAD_ON_OFF: Msg = Msg::new(
+    "未知参数: {}，请用 on 或 off",
+    "Unknown argument: {}, use on or off",
+);
+pub(crate) const BRIGHTNESS_NOW: Msg = Msg::new("背光亮度: {}%", "Backlight: {}%");
+pub(crate) const BRIGHTNESS_SET: Msg = Msg::new("背光亮度已设为 {}%", "Backlight set to {}%");
+pub(crate) const BRIGHTNESS_RANGE: Msg = Msg::new("亮度范围是 0-100", "Brightness must be 0-100");
+pub(crate) const BRIGHTNESS_UNSUPPORTED: Msg =
+    Msg::new("当前显示后端不支持调光", "This display backend cannot dim");
+pub(crate) const IDLE_STATUS: Msg = Msg::new(
+    "闲置 {} 秒变暗，{} 秒关背光（0 表示不会）",
+    "Dim after {} s idle, backlight off after {} s (0 = never)",
+);
+pub(crate) const THEME_LIST: Msg = Msg::new("主题列表（* 是当前主题）:", "Themes (* = current):");
+pub(crate) const THEME_SET: Msg = Msg::new(
+    "主题已切换为 {}，下次画面刷新时生效",
+    "Theme switched to {}, applies on the next redraw",
+);
+pub(crate) const THEME_UNKNOWN: Msg = Msg::new(
+    "没有叫 {} 的主题，theme 不带参数看列表",
+    "No theme named {}, run theme without arguments to list them",
+);
+pub(crate) const SCREENSHOT_UNSUPPORTED: Msg = Msg::new(
+    "当前显示后端读不回像素，截图需要影子缓冲（ST7735 请打开 framebuffer 特性）",
+    "This display backend cannot read pixels back; screenshots need a shadow buffer (enable the framebuffer feature on ST7735)",
+);
+pub(crate) const RECORD_STARTED: Msg = Msg::new(
+    "开始录制到 {}，record stop 结束",
+    "Recording to {}, record stop to finish",
+);
+pub(crate) const RECORD_STOPPED: Msg =
+    Msg::new("录制结束，共 {} 帧", "Recording finished, {} frames");
+pub(crate) const BAD_START_STOP: Msg = Msg::new(
+    "未知参数: {}，请用 start 或 stop",
+    "Unknown argument: {}, use start or stop",
+);
+
+// ===========================================
+// 主菜单
+// ============================================
+
+pub(crate) const MENU_TITLE: Msg = Msg::new("=== 主菜单 ===", "=== Main menu ===");
+pub(crate) const MENU_HINT: Msg = Msg::new(
+    "方向键/数字键选择，回车进入，q 回命令行",
+    "Arrows/digits to choose, Enter to start, q for the command line",
+);
+pub(crate) const MENU_CLOSED: Msg = Msg::new(
+    "回到命令行，输入 menu 打开主菜单",
+    "Back to the command line, type menu to open the main menu",
+);
+pub(crate) const MENU_LAUNCH: Msg = Msg::new("启动 {}（命令 {}）", "Starting {} (command {})");
+pub(crate) const MENU_TOO_SMALL: Msg = Msg::new(
+    "屏幕太小（{}），放不下菜单",
+    "Screen too small ({}) for the menu",
+);
+
+// ===========================================
+// 设置
+// ============================================
+
+pub(crate) const SETTINGS_NONE: Msg = Msg::new(
+    "没有保存的设置，使用默认值",
+    "No saved settings, using defaults",
+);
+pub(crate) const SETTINGS_LOADED: Msg =
+    Msg::new("已加载 {} 项设置（{}）", "Loaded {} settings ({})");
+pub(crate) const SETTINGS_LOAD_FAILED: Msg = Msg::new(
+    "设置读取失败: {}，使用默认值",
+    "Could not read settings: {}, using defaults",
+);
+pub(crate) const SETTINGS_UNKNOWN_KEY: Msg = Msg::new(
+    "没有 {} 这一项，settings 列出全部",
+    "No setting named {}, run settings to list them",
+);
+pub(crate) const SETTINGS_LIST: Msg = Msg::new("设置（* 是改过的）:", "Settings (* = changed):");
+pub(crate) const SETTINGS_STORAGE: Msg = Msg::new("存储: {}", "Storage: {}");
+pub(crate) const SETTINGS_RESET: Msg =
+    Msg::new("设置已全部恢复默认", "All settings restored to defaults");
+pub(crate) const SETTINGS_EXPORT: Msg = Msg::new("{} 字节，{}:", "{} bytes, {}:");
+pub(crate) const SETTINGS_BAD_ACTION: Msg = Msg::new(
+    "未知参数: {}，请用 reset 或 export",
+    "Unknown argument: {}, use reset or export",
+);
+pub(crate) const SETTINGS_SAVED: Msg = Msg::new("已保存", "Saved");
+pub(crate) const SETTINGS_NOT_SAVED: Msg = Msg::new("没能保存: {}", "Not saved: {}");
+pub(crate) const SETTINGS_RANGE: Msg = Msg::new("{} 的范围是 {}-{}", "{} must be {}-{}");
+pub(crate) const SETTINGS_ANSI: Msg = Msg::new(
+    "{} 要用终端颜色代码 30-37 或 90-97",
+    "{} must be a terminal colour code, 30-37 or 90-97",
+);
+pub(crate) const SETTINGS_LENGTH: Msg = Msg::new("{} 要 1-{} 个字节", "{} must be 1-{} bytes");
+pub(crate) const SETTINGS_NO_THEME: Msg = Msg::new("没有叫 {} 的主题", "No theme named {}");
+pub(crate) const SETTINGS_NO_LANG: Msg = Msg::new(
+    "不支持语言 {}，可以用 zh、en",
+    "Unsupported language {}, use zh or en",
+);
+pub(crate) const SETTINGS_BAD_GEOMETRY: Msg = Msg::new(
+    "屏幕几何 {} 不对，要写成 160x128 或 160x128@90 这样",
+    "Invalid display geometry {}, write it like 160x128 or 160x128@90",
+);
+pub(crate) const SETTINGS_DISPLAY_RESTART: Msg = Msg::new(
+    "屏幕几何重启后生效",
+    "The display geometry takes effect after a restart",
+);
+pub(crate) const SETTINGS_WRONG_TYPE: Msg = Msg::new("{} 的类型不对", "{} has the wrong type");
+pub(crate) const SETTINGS_NOT_NUMBER: Msg = Msg::new("{} 要填数字", "{} must be a number");
+pub(crate) const SETTINGS_NOT_DATA: Msg = Msg::new("不是设置数据", "not settings data");
+pub(crate) const SETTINGS_TOO_NEW: Msg = Msg::new(
+    "格式版本 {} 比固件新",
+    "format version {} is newer than this firmware",
+);
+pub(crate) const SETTINGS_TRUNCATED: Msg = Msg::new("数据不完整", "data is truncated");
+pub(crate) const SETTINGS_BAD_CRC: Msg = Msg::new("crc 不对", "crc mismatch");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const SETTINGS_FILE: Msg = Msg::new(
+    "{}，可以用环境变量 HANZI_SETTINGS 换",
+    "{}, override with the HANZI_SETTINGS environment variable",
+);
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const SETTINGS_WRITE_FAILED: Msg = Msg::new("写 {} 失败: {}", "Failed to write {}: {}");
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const SETTINGS_FILE_EXPORT: Msg = Msg::new(
+    "模拟器里就是 settings.bin 文件的内容",
+    "this is what the simulator keeps in settings.bin",
+);
+#[cfg(feature = "need-ecos")]
+pub(crate) const SETTINGS_FLASH_TOO_LARGE: Msg = Msg::new(
+    "设置有 {} 字节，超过了 flash 扇区的 {} 字节",
+    "settings take {} bytes, more than the {} byte flash sector",
+);
+#[cfg(feature = "need-ecos")]
+pub(crate) const SETTINGS_FLASH_VERIFY_FAILED: Msg = Msg::new(
+    "写 flash 后读回来不一致",
+    "flash contents differ from what was written",
+);
+#[cfg(feature = "need-ecos")]
+pub(crate) const SETTINGS_FLASH_EXPORT: Msg = Msg::new(
+    "就是 flash 偏移 0xFFF000 里存的内容",
+    "this is what is stored at flash offset 0xFFF000",
+);
+
+pub(crate) const KEY_THEME: Msg = Msg::new("配色主题", "Colour theme");
+pub(crate) const KEY_BRIGHTNESS: Msg = Msg::new("背光亮度（%）", "Backlight brightness (%)");
+pub(crate) const KEY_IDLE_DIM: Msg = Msg::new(
+    "闲置多少秒变暗，0 不变暗",
+    "Seconds idle before dimming, 0 = never",
+);
+pub(crate) const KEY_IDLE_SLEEP: Msg = Msg::new(
+    "闲置多少秒关背光，0 不关",
+    "Seconds idle before backlight off, 0 = never",
+);
+pub(crate) const KEY_PROMPT_USER: Msg = Msg::new(
+    "提示符用户名颜色（30-37/90-97）",
+    "Prompt user colour (30-37/90-97)",
+);
+pub(crate) const KEY_PROMPT_HOST: Msg = Msg::new(
+    "提示符主机名颜色（30-37/90-97）",
+    "Prompt host colour (30-37/90-97)",
+);
+pub(crate) const KEY_FONT_SIZE: Msg = Msg::new(
+    "阅读器、编辑器字号（像素）",
+    "Reader/editor font size (pixels)",
+);
+pub(crate) const KEY_SNAKE_SPEED: Msg = Msg::new("贪吃蛇速度（1-10）", "Snake speed (1-10)");
+pub(crate) const KEY_SNAKE_HIGH: Msg = Msg::new("贪吃蛇最高分", "Snake high score");
+pub(crate) const KEY_LAST_DOCUMENT: Msg =
+    Msg::new("阅读器上次打开的文档", "Document last opened in the reader");
+pub(crate) const KEY_LANGUAGE: Msg = Msg::new("界面语言（zh/en）", "Language (zh/en)");
+pub(crate) const KEY_LOG_LEVEL: Msg = Msg::new("串口日志级别", "Console log level");
+pub(crate) const KEY_PANIC_RESTART: Msg = Msg::new(
+    "板子 panic 后自动重启（1）还是停住（0）",
+    "Restart the board after a panic (1) or halt (0)",
+);
+pub(crate) const KEY_DISPLAY: Msg = Msg::new(
+    "屏幕尺寸和安装方向，宽x高@角度",
+    "Display size and mounting, WIDTHxHEIGHT@DEGREES",
+);
+
+// ===========================================
+// 日志
+// ============================================
+
+pub(crate) const LOG_LEVEL_NOW: Msg = Msg::new(
+    "串口日志级别: {}（dmesg 记到 debug）",
+    "Console log level: {} (dmesg keeps up to debug)",
+);
+pub(crate) const LOG_LEVEL_SET: Msg =
+    Msg::new("串口日志级别已设为 {}", "Console log level set to {}");
+pub(crate) const LOG_LEVEL_UNKNOWN: Msg = Msg::new(
+    "不认识的级别 {}，可以用 off、error、warn、info、debug、trace",
+    "Unknown level {}, use off, error, warn, info, debug or trace",
+);
+pub(crate) const DMESG_EMPTY: Msg = Msg::new("还没有日志", "No log entries yet");
+pub(crate) const DMESG_CLEARED: Msg = Msg::new("日志已清空", "Log cleared");
+pub(crate) const DMESG_BAD_ACTION: Msg = Msg::new(
+    "未知参数: {}，请用 clear",
+    "Unknown argument: {}, use clear",
+);
+
+// ===========================================
+// 内存
+// ============================================
+
+pub(crate) const MEM_FREE: Msg = Msg::new(
+    "堆: 用了 {} 字节，峰值 {} 字节，{} 块没释放",
+    "Heap: {} bytes used, {} peak, {} live blocks",
+);
+pub(crate) const MEM_CURRENT: Msg = Msg::new("当前用量: {} 字节", "In use: {} bytes");
+pub(crate) const MEM_PEAK: Msg = Msg::new("峰值: {} 字节", "Peak: {} bytes");
+pub(crate) const MEM_LIVE: Msg = Msg::new("没释放的块: {}", "Live blocks: {}");
+pub(crate) const MEM_COUNTS: Msg = Msg::new(
+    "分配 {} 次，释放 {} 次，重新分配 {} 次",
+    "{} allocations, {} frees, {} reallocations",
+);
+pub(crate) const MEM_FAILED: Msg = Msg::new("分配失败: {} 次", "Failed allocations: {}");
+pub(crate) const MEM_LARGEST: Msg = Msg::new("最大空闲块: {} 字节", "Largest free block: {} bytes");
+pub(crate) const MEM_LARGEST_UNKNOWN: Msg = Msg::new(
+    "最大空闲块: 系统分配器不公开",
+    "Largest free block: not exposed by the system allocator",
+);
+
+// ===========================================
+// 性能计时
+// ============================================
+
+pub(crate) const PERF_UNIT_CYCLES: Msg = Msg::new("CPU 周期", "CPU cycles");
+pub(crate) const PERF_UNIT: Msg = Msg::new("单位: {}", "Unit: {}");
+pub(crate) const PERF_ROW: Msg = Msg::new(
+    "{}: {} 次，最短 {}，平均 {}，最长 {}",
+    "{}: {} calls, min {}, avg {}, max {}",
+);
+pub(crate) const PERF_EMPTY: Msg = Msg::new(
+    "还没有计时记录，先跑一下演示",
+    "Nothing timed yet, run a demo first",
+);
+pub(crate) const PERF_RESET: Msg = Msg::new("计时已清零", "Timings reset");
+pub(crate) const PERF_BAD_ACTION: Msg = Msg::new(
+    "未知参数: {}，请用 reset",
+    "Unknown argument: {}, use reset",
+);
+
+// ===========================================
+// panic
+// ============================================
+
+// panic 处理里不能分配内存，这几条不带参数
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_RESTARTING: Msg = Msg::new(
+    "3 秒后重启回命令行，重启后 lastpanic 查看",
+    "Restarting into the command line in 3 s, run lastpanic afterwards",
+);
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_HALTED: Msg = Msg::new(
+    "已停住，按复位键重启（set panic.restart 1 自动重启）",
+    "Halted, press reset (set panic.restart 1 to restart automatically)",
+);
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_LOOP: Msg = Msg::new(
+    "开机不久就接连 panic，不再自动重启，按复位键重试",
+    "Panicked repeatedly right after boot, not restarting again; press reset to retry",
+);
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_PREVIOUS: Msg = Msg::new(
+    "上次是 panic 后重启的: {} {}，lastpanic 查看",
+    "Restarted after a panic: {} {}, run lastpanic for details",
+);
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_REPORT: Msg = Msg::new(
+    "上次 panic: 开机 {} ms 时，上电以来第 {} 次",
+    "Last panic: {} ms after boot, #{} since power-on",
+);
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_AT: Msg = Msg::new("位置: {}", "Location: {}");
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_MESSAGE: Msg = Msg::new("原因: {}", "Message: {}");
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_NONE: Msg = Msg::new(
+    "没有 panic 记录（断电会清掉）",
+    "No panic recorded (cleared on power-off)",
+);
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_CLEARED: Msg = Msg::new("panic 记录已清掉", "Panic record cleared");
+#[cfg(feature = "panic-handler")]
+pub(crate) const PANIC_BAD_ACTION: Msg = Msg::new(
+    "未知参数: {}，请用 clear",
+    "Unknown argument: {}, use clear",
+);
+
+// ===========================================
+// 示例命令
+// ============================================
+
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELLO_DEFAULT_NAME: Msg = Msg::new("世界", "world");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELLO: Msg = Msg::new("你好，{}！", "Hello, {}!");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELLO_REPEAT: Msg = Msg::new("重复 {} 次", "Repeating {} times");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELLO_NTH: Msg = Msg::new("第{}次：你好，{}！", "#{}: Hello, {}!");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const PLAIN: Msg = Msg::new("平平无奇的命令...", "A perfectly ordinary command...");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_TITLE: Msg = Msg::new("数组操作:", "Array operation:");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_INPUT: Msg = Msg::new("  原始输入: {}", "  Input: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_PARSED: Msg = Msg::new("  解析的数字 ({}个):", "  Parsed numbers ({}):");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_SUM: Msg = Msg::new("  总和: {}", "  Sum: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_AVG: Msg = Msg::new("  平均值: {}", "  Average: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_MIN: Msg = Msg::new("  最小值: {}", "  Min: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_MAX: Msg = Msg::new("  最大值: {}", "  Max: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARRAY_UNKNOWN_OP: Msg = Msg::new("  未知操作: {}", "  Unknown operation: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const POINT_TITLE: Msg = Msg::new("点坐标:", "Point:");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const POINT_DISTANCE: Msg = Msg::new("  距离原点: {}", "  Distance from origin: {}");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARG_TOO_MANY: Msg = Msg::new("参数数目最大为10", "at most 10 numbers");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARG_BAD_NUMBER: Msg = Msg::new("无效的数字格式", "invalid number");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARG_EMPTY: Msg = Msg::new("至少提供一个参数", "at least one number");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARG_POINT_FORMAT: Msg = Msg::new("点坐标格式应该是x,y", "a point as x,y");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARG_BAD_X: Msg = Msg::new("无效的X", "invalid X");
+#[cfg(feature = "cmd-cli")]
+pub(crate) const ARG_BAD_Y: Msg = Msg::new("无效的Y", "invalid Y");
+
+// ===========================================
+// 图片
+// ============================================
+
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_NONE: Msg = Msg::new(
+    "固件里没有图片，把 png 放到 display/images 下重新编译",
+    "No images in this firmware; put png files in display/images and rebuild",
+);
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_LIST: Msg = Msg::new("图片列表:", "Images:");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_ENTRY: Msg = Msg::new("  {} {} {} {} 字节", "  {} {} {} {} bytes");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_ENTRY_BROKEN: Msg = Msg::new("  {} 损坏: {}", "  {} broken: {}");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_SHOWN: Msg = Msg::new("显示 {}（{} {}）", "Showing {} ({} {})");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_BROKEN: Msg = Msg::new("图片 {} 损坏: {}", "Image {} is broken: {}");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_UNKNOWN: Msg = Msg::new(
+    "没有叫 {} 的图片，show 不带参数看列表",
+    "No image named {}, run show without arguments to list them",
+);
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_BMP_UNSUPPORTED: Msg = Msg::new("BMP 格式不支持", "unsupported BMP format");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_UNKNOWN_FORMAT: Msg = Msg::new("不认识的图片格式", "unknown image format");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_NOT_RLE: Msg = Msg::new("不是 RLE565 图片", "not an RLE565 image");
+#[cfg(feature = "cmd-image")]
+pub(crate) const IMAGE_RLE_VERSION: Msg = Msg::new("RLE565 版本不对", "unsupported RLE565 version");
+
+// ===========================================
+// 字体演示
+// ============================================
+
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_APP: Msg = Msg::new("字体演示", "font demo");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_START: Lines = Lines::new(
+    &[
+        "\r\n=== 启动 TTF 字体演示 ===",
+        "正在启动字体演示...\r\n",
+        "按 1-6 选择演示，n/p 切换，q 退出\r\n",
+    ],
+    &[
+        "\r\n=== TTF font demo ===",
+        "Starting the font demo...\r\n",
+        "Press 1-6 to pick a demo, n/p to switch, q to quit\r\n",
+    ],
+);
+#[cfg(feature = "cmd-font")]
+pub(crate) const MARQUEE_TITLE: Msg = Msg::new("=== 滚动字幕: {} ===", "=== Marquee: {} ===");
+#[cfg(feature = "cmd-font")]
+pub(crate) const MARQUEE_HINT: Msg = Msg::new(
+    "按 q 停止，再按 q 返回命令行",
+    "Press q to stop, q again to return to the command line",
+);
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EXIT: Msg = Msg::new("退出字体演示...", "Leaving the font demo...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_LOAD_FAILED: Msg = Msg::new("字体加载失败！", "Failed to load font!");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_DONE: Msg = Msg::new(
+    "演示结束，输入命令继续...",
+    "Demo finished, type a command to continue...",
+);
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_CANCELLED: Msg = Msg::new("用户中断演示", "Demo interrupted");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_SWITCH: Msg = Msg::new("切换到: {}", "Switching to: {}");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_BASIC: Msg = Msg::new("基本字体渲染", "Basic font rendering");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_SIZES: Msg = Msg::new("不同字体大小", "Font sizes");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_CHINESE: Msg = Msg::new("中文字体渲染", "Chinese text rendering");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_MIXED: Msg = Msg::new("混合文本和图形", "Text and graphics");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_ANIMATED: Msg = Msg::new("动画文本", "Animated text");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_EFFECTS: Msg = Msg::new("文字特效", "Text effects");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DEMO_HEADER: Msg = Msg::new("=== 演示{}: {} ===", "=== Demo {}: {} ===");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_CLEAR_ERROR: Msg = Msg::new("清屏错误: {}", "Clear failed: {}");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DRAW_ERROR: Msg = Msg::new("绘制错误: {}", "Draw failed: {}");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_BACKGROUND_ERROR: Msg =
+    Msg::new("背景绘制错误: {}", "Background draw failed: {}");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EFFECT_ERROR: Msg = Msg::new("特效绘制错误: {}", "Effect draw failed: {}");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DRAW_SMALL: Msg = Msg::new("绘制小号文本...", "Drawing small text...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DRAW_MEDIUM: Msg = Msg::new("绘制中号文本...", "Drawing medium text...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DRAW_LARGE: Msg = Msg::new("绘制大号文本...", "Drawing large text...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DRAW_CHINESE: Msg = Msg::new("绘制中文文本...", "Drawing Chinese text...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_DRAW_BACKGROUND: Msg =
+    Msg::new("绘制背景矩形...", "Drawing background boxes...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_ANIMATION_HINT: Msg = Msg::new(
+    "按 'q' 键可以提前退出动画演示",
+    "Press 'q' to leave the animation early",
+);
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_SCROLLING: Msg =
+    Msg::new("彩色文本滚动动画...", "Scrolling coloured text...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_SCROLLING_HINT: Msg =
+    Msg::new("按 'q' 键退出动画", "Press 'q' to leave the animation");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_FADE: Msg = Msg::new("淡入淡出效果...", "Fade in and out...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_ZOOM: Msg = Msg::new("缩放效果...", "Zoom...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EFFECT_OUTLINE: Msg = Msg::new("描边文字...", "Outlined text...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EFFECT_SHADOW: Msg = Msg::new("阴影文字...", "Drop shadow...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EFFECT_VERTICAL: Msg = Msg::new("竖直渐变...", "Vertical gradient...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EFFECT_HORIZONTAL: Msg = Msg::new("水平渐变...", "Horizontal gradient...");
+#[cfg(feature = "cmd-font")]
+pub(crate) const FONT_EFFECT_RAINBOW: Msg = Msg::new("逐字着色...", "Per-character colours...");
+
+// ===========================================
+// 阅读器、编辑器
+// ============================================
+
+#[cfg(feature = "cmd-text")]
+pub(crate) const READER_APP: Msg = Msg::new("阅读器", "reader");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_APP: Msg = Msg::new("编辑器", "editor");
+#[cfg(feature = "cmd-text")]
+pub(crate) const READER_START: Msg = Msg::new("=== 启动文本阅读器 ===", "=== Text reader ===");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_START: Msg = Msg::new("=== 启动文本编辑器 ===", "=== Text editor ===");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_LIST: Msg = Msg::new("=== 文件列表 ===", "=== Files ===");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_NEW: Msg = Msg::new("=== 创建新文件: {} ===", "=== New file: {} ===");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_CREATED: Msg = Msg::new("文件创建成功", "File created");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_OPEN: Msg = Msg::new("=== 打开文件: {} ===", "=== Open file: {} ===");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_CONTENT: Msg = Msg::new("文件内容:", "Contents:");
+#[cfg(feature = "cmd-text")]
+pub(crate) const FILE_NOT_FOUND: Msg = Msg::new("文件不存在", "File not found");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_FONT_LOADED: Msg = Msg::new("字体加载成功", "Font loaded");
+#[cfg(feature = "cmd-text")]
+pub(crate) const TEXT_FONT_FAILED: Msg = Msg::new("字体加载失败", "Failed to load font");
+#[cfg(feature = "cmd-text")]
+pub(crate) const READER_HELP: Lines = Lines::new(
+    &[
+        "\r\n=== 文本阅读器 ===",
+        "可用命令:",
+        "  n/p - 下一个/上一个文档",
+        "  ↑/↓ - 滚动文本",
+        "  f   - 切换文件",
+        "  q   - 退出",
+        "==================\r\n",
+    ],
+    &[
+        "\r\n=== Text reader ===",
+        "Keys:",
+        "  n/p - next/previous document",
+        "  ↑/↓ - scroll",
+        "  f   - pick a file",
+        "  q   - quit",
+        "===================\r\n",
+    ],
+);
+#[cfg(feature = "cmd-text")]
+pub(crate) const READER_EXIT: Msg = Msg::new("退出阅读器", "Leaving the reader");
+#[cfg(feature = "cmd-text")]
+pub(crate) const READER_SHOWING: Msg = Msg::new("显示文档: {}", "Showing: {}");
+#[cfg(feature = "cmd-text")]
+pub(crate) const READER_SELECTED: Msg = Msg::new("已选择: {}", "Selected: {}");
+// 下面三条画在屏幕上的文件列表里，中文由部件换成 TTF 画
+#[cfg(feature = "cmd-text")]
+pub(crate) const PICKER_TITLE: Msg = Msg::new("打开文件", "Open file");
+#[cfg(feature = "cmd-text")]
+pub(crate) const PICKER_OPEN: Msg = Msg::new("回车:打开", "Enter:open");
+#[cfg(feature = "cmd-text")]
+pub(crate) const PICKER_CANCEL: Msg = Msg::new("q:取消", "q:cancel");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_HELP: Lines = Lines::new(
+    &[
+        "\r\n=== 文本编辑器 ===",
+        "模式: Normal (按 i 进入插入模式)",
+        "快捷键:",
+        "  i     - 进入插入模式",
+        "  Esc   - 返回Normal模式",
+        "  :     - 进入命令模式",
+        "  h/j/k/l - 左/下/上/右移动",
+        "  w/b   - 向前/后移动一个词",
+        "  0/$   - 行首/行尾",
+        "  dd    - 删除当前行",
+        "  x     - 删除字符",
+        "  u     - 撤销",
+        "  Ctrl+S - 保存",
+        "  :q    - 退出",
+        "==================\r\n",
+    ],
+    &[
+        "\r\n=== Text editor ===",
+        "Mode: Normal (press i to insert)",
+        "Keys:",
+        "  i     - insert mode",
+        "  Esc   - back to Normal mode",
+        "  :     - command mode",
+        "  h/j/k/l - left/down/up/right",
+        "  w/b   - next/previous word",
+        "  0/$   - start/end of line",
+        "  dd    - delete line",
+        "  x     - delete character",
+        "  u     - undo",
+        "  Ctrl+S - save",
+        "  :q    - quit",
+        "===================\r\n",
+    ],
+);
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_INSERT: Msg = Msg::new("进入插入模式", "Insert mode");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_COMMAND: Msg = Msg::new("进入命令模式", "Command mode");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_EXIT: Msg = Msg::new("退出编辑器", "Leaving the editor");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_UNDO: Msg = Msg::new("撤销", "Undo");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_SAVED: Msg = Msg::new("文件已保存", "File saved");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_NORMAL: Msg = Msg::new("返回Normal模式", "Normal mode");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_LEAVE_INSERT: Msg = Msg::new("退出插入模式", "Leaving insert mode");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_SAVED_EXIT: Msg = Msg::new("文件已保存并退出", "File saved, leaving");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_RELOADED: Msg = Msg::new("重新加载文件", "File reloaded");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_FILES: Msg = Msg::new("文件列表:", "Files:");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_EDITING: Msg = Msg::new("编辑文件: {}", "Editing: {}");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_SAVED_AS: Msg = Msg::new("另存为: {}", "Saved as: {}");
+#[cfg(feature = "cmd-text")]
+pub(crate) const EDITOR_UNKNOWN: Msg = Msg::new("未知命令: {}", "Unknown command: {}");
+
+// ===========================================
+// 贪吃蛇
+// ============================================
+
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_APP: Msg = Msg::new("贪吃蛇", "snake");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_START_TITLE: Msg = Msg::new("=== 启动贪吃蛇游戏 ===", "=== Snake ===");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_DIFFICULTY_TITLE: Msg =
+    Msg::new("=== 设置游戏难度 ===", "=== Snake difficulty ===");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_HELP_TITLE: Msg = Msg::new("=== 贪吃蛇游戏帮助 ===", "=== Snake help ===");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SEED_TITLE: Msg = Msg::new("=== 设置随机种子 ===", "=== Snake seed ===");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_LEVEL_UP: Msg = Msg::new("🎮 升级到 Level {}!", "🎮 Level {}!");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_PAUSED: Msg = Msg::new("游戏 暂停", "Paused");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_RESUMED: Msg = Msg::new("游戏 继续", "Resumed");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_NEW_RECORD: Msg =
+    Msg::new("🎉 新纪录！最高分: {}", "🎉 New record! High score: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_RESTART: Msg = Msg::new("重新开始游戏...", "Restarting...");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_QUIT: Msg = Msg::new("退出贪吃蛇游戏", "Leaving snake");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SCORE: Msg = Msg::new("当前得分: {}, 等级: {}", "Score: {}, level: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_GAME_OVER: Msg = Msg::new("游戏结束！", "Game over!");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_FINAL_SCORE: Msg = Msg::new("最终得分: {}", "Final score: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_HIGH_SCORE: Msg = Msg::new("最高分: {}", "High score: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_LEVEL_REACHED: Msg = Msg::new("达到等级: {}", "Level reached: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_INTRO: Lines = Lines::new(
+    &[
+        "\r\n=== 贪吃蛇游戏开始 ===",
+        "游戏特性:",
+        "  • 随机变化的背景颜色",
+        "  • 动态缩放的食物 (0.85-1.15倍)",
+        "  • 颜色差异越大，分数越高",
+        "",
+        "游戏控制:",
+        "  W/A/S/D 或 I/J/K/L - 控制方向",
+        "  空格键              - 暂停/继续",
+        "  R                  - 重新开始",
+        "  Q                  - 退出游戏",
+        "======================\r\n",
+    ],
+    &[
+        "\r\n=== Snake ===",
+        "Features:",
+        "  • background colour changes at random",
+        "  • food pulses in size (0.85-1.15x)",
+        "  • the more the colours differ, the higher the score",
+        "",
+        "Controls:",
+        "  W/A/S/D or I/J/K/L - steer",
+        "  Space              - pause/resume",
+        "  R                  - restart",
+        "  Q                  - quit",
+        "======================\r\n",
+    ],
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_UNKNOWN_LEVEL: Msg = Msg::new(
+    "  难度: 未知 (使用 easy/normal/hard)",
+    "  Difficulty: unknown (use easy/normal/hard)",
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SPEED_SET: Msg = Msg::new(
+    "  速度已设为 {} ({}ms/帧)",
+    "  Speed set to {} ({} ms/frame)",
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SPEED_NOT_SAVED: Msg = Msg::new(
+    "  速度已设为 {}，但没能保存: {}",
+    "  Speed set to {} but not saved: {}",
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SPEED_INVALID: Msg = Msg::new(
+    "  速度: 无效 (应为 1-10)",
+    "  Speed: invalid (must be 1-10)",
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_CURRENT: Msg = Msg::new("当前设置:", "Current settings:");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_CURRENT_SPEED: Msg =
+    Msg::new("  速度: {} ({}ms/帧)", "  Speed: {} ({} ms/frame)");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_CURRENT_HIGH: Msg = Msg::new("  最高分: {}", "  High score: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_DIFFICULTY_EXAMPLES: Lines = Lines::new(
+    &[
+        "\r\n使用示例:",
+        "  snake-difficulty --level easy --speed 3",
+        "  snake-difficulty -l normal -s 6",
+    ],
+    &[
+        "\r\nExamples:",
+        "  snake-difficulty --level easy --speed 3",
+        "  snake-difficulty -l normal -s 6",
+    ],
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_HELP: Lines = Lines::new(
+    &[
+        "贪吃蛇游戏说明:",
+        "================",
+        "游戏特性:",
+        "  • 背景颜色随机变化",
+        "  • 蛇颜色与背景保持60°以上差异",
+        "  • 食物颜色与背景/蛇都保持30°以上差异",
+        "  • 食物动态缩放 (0.85-1.15倍)",
+        "  • 颜色差异越大，分数加成越高",
+        "  • 状态机模式，流畅动画",
+        "",
+        "基本命令:",
+        "  snake            - 开始游戏",
+        "  snake-difficulty - 设置游戏难度和速度",
+        "  snake-seed       - 设置随机种子",
+        "  snake-help       - 显示此帮助信息",
+        "",
+        "游戏内控制:",
+        "  任意键           - 开始游戏（等待状态时）",
+        "  W/A/S/D 或 I/J/K/L - 控制蛇的移动方向",
+        "  空格键              - 暂停/继续游戏",
+        "  R                  - 游戏结束后重新开始",
+        "  Q                  - 退出游戏",
+        "",
+        "计分规则:",
+        "  • 基础分: 10分",
+        "  • 颜色差异加成: 最多+20分",
+        "  • 每100分升一级",
+        "",
+        "颜色系统:",
+        "  • 背景: 暗色，低饱和度，定期变化",
+        "  • 蛇: 与背景差异60°以上",
+        "  • 食物: 与背景和蛇都差异30°以上",
+        "  • 蛇头: 比蛇身亮20%",
+        "",
+        "动画效果:",
+        "  • 食物: 0.85-1.15倍动态缩放",
+        "  • 状态机驱动，不依赖延时",
+        "  • 局部刷新，性能优化",
+        "",
+        "难度设置（会保存，下次开局生效）:",
+        "  简单 (easy)   - 速度 2",
+        "  普通 (normal) - 速度 5",
+        "  困难 (hard)   - 速度 8",
+        "",
+        "速度设置 (1-10):",
+        "  1: 最慢 (230ms/帧)，10: 最快 (50ms/帧)",
+        "",
+        "随机种子:",
+        "  使用 snake-seed <十六进制数> 设置随机种子",
+        "  相同的种子会产生相同的颜色序列",
+    ],
+    &[
+        "How to play snake:",
+        "==================",
+        "Features:",
+        "  • background colour changes at random",
+        "  • the snake stays at least 60° away from the background hue",
+        "  • food stays at least 30° away from background and snake",
+        "  • food pulses in size (0.85-1.15x)",
+        "  • the more the colours differ, the bigger the bonus",
+        "  • state machine driven, smooth animation",
+        "",
+        "Commands:",
+        "  snake            - start a game",
+        "  snake-difficulty - set difficulty and speed",
+        "  snake-seed       - set the random seed",
+        "  snake-help       - show this help",
+        "",
+        "In game:",
+        "  any key            - start (while waiting)",
+        "  W/A/S/D or I/J/K/L - steer the snake",
+        "  Space              - pause/resume",
+        "  R                  - restart after game over",
+        "  Q                  - quit",
+        "",
+        "Scoring:",
+        "  • base score: 10",
+        "  • colour bonus: up to +20",
+        "  • one level every 100 points",
+        "",
+        "Colours:",
+        "  • background: dark, low saturation, changes over time",
+        "  • snake: at least 60° away from the background",
+        "  • food: at least 30° away from background and snake",
+        "  • head: 20% brighter than the body",
+        "",
+        "Animation:",
+        "  • food: 0.85-1.15x pulsing",
+        "  • driven by a state machine, no busy waiting",
+        "  • partial redraws for speed",
+        "",
+        "Difficulty (saved, applies to the next game):",
+        "  easy   - speed 2",
+        "  normal - speed 5",
+        "  hard   - speed 8",
+        "",
+        "Speed (1-10):",
+        "  1: slowest (230 ms/frame), 10: fastest (50 ms/frame)",
+        "",
+        "Random seed:",
+        "  snake-seed <hex> sets the seed",
+        "  the same seed gives the same colour sequence",
+    ],
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SEED_SET: Msg = Msg::new("设置随机种子: {}", "Random seed: {}");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SEED_NEXT: Msg = Msg::new(
+    "下次启动游戏时将使用此种子",
+    "The next game will use this seed",
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SEED_NUMBERS: Msg = Msg::new("示例随机数:", "Sample random numbers:");
+#[cfg(feature = "cmd-snake")]
+pub(crate) const SNAKE_SEED_COLORS: Msg = Msg::new("示例颜色 (HSL格式):", "Sample colours (HSL):");
+
+// ===========================================
+// 命令说明（help）
+// ============================================
+//
+// 每条第一行是用法，第二行是一句话说明（`help` 列表里只打这一句），后面是参数
+
+pub(crate) const HELP_HEADER: Msg = Msg::new(
+    "可用命令（`help <命令>` 查看参数）:",
+    "Commands (`help <command>` shows the arguments):",
+);
+pub(crate) const HELP_UNKNOWN: Msg = Msg::new(
+    "没有这个命令: {}，`help` 列出全部",
+    "No such command: {}, `help` lists them all",
+);
+pub(crate) const HELP_HELP: Lines = Lines::new(
+    &["help [命令]", "列出所有命令，带命令名时查看它的参数"],
+    &[
+        "help [command]",
+        "List all commands, or show the arguments of one",
+    ],
+);
+
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELP_HELLO: Lines = Lines::new(
+    &[
+        "hello [-n <名字>] [-c <次数>]",
+        "打印欢迎消息",
+        "  -n, --name <名字>   要问候的名字，可选",
+        "  -c, --count <次数>  重复次数",
+    ],
+    &[
+        "hello [-n <name>] [-c <count>]",
+        "Print a greeting",
+        "  -n, --name <name>   who to greet, optional",
+        "  -c, --count <count> how many times",
+    ],
+);
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELP_PLAIN: Lines = Lines::new(
+    &["plain", "普普通通的命令，无参数"],
+    &["plain", "A plain command without arguments"],
+);
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELP_ARRAY: Lines = Lines::new(
+    &[
+        "array <数字...> [-o <操作>]",
+        "数组操作命令：解析多个数字",
+        "  <数字...>               多个数字（逗号或空格分隔）",
+        "  -o, --operation <操作>  sum、avg、max 或 min，默认 sum",
+    ],
+    &[
+        "array <numbers...> [-o <operation>]",
+        "Parse a list of numbers and work on them",
+        "  <numbers...>                 numbers separated by commas or spaces",
+        "  -o, --operation <operation>  sum, avg, max or min, sum by default",
+    ],
+);
+#[cfg(feature = "cmd-cli")]
+pub(crate) const HELP_POINT: Lines = Lines::new(
+    &["point <x,y>", "点坐标命令", "  <x,y>  点坐标"],
+    &["point <x,y>", "Parse a point", "  <x,y>  the coordinates"],
+);
+
+#[cfg(feature = "cmd-font")]
+pub(crate) const HELP_FONT: Lines = Lines::new(
+    &["font", "启动 TTF 字体演示"],
+    &["font", "Start the TTF font demo"],
+);
+#[cfg(feature = "cmd-font")]
+pub(crate) const HELP_MARQUEE: Lines = Lines::new(
+    &[
+        "marquee [文字]",
+        "整屏滚动显示一段文字，按 q 停止",
+        "  [文字]  要滚动的文字，不填用自带的一段",
+    ],
+    &[
+        "marquee [text]",
+        "Scroll a line of text across the screen, q stops",
+        "  [text]  what to scroll, defaults to a built-in sample",
+    ],
+);
+
+#[cfg(feature = "cmd-text")]
+pub(crate) const HELP_READER: Lines = Lines::new(
+    &["reader", "启动文本阅读器"],
+    &["reader", "Start the text reader"],
+);
+#[cfg(feature = "cmd-text")]
+pub(crate) const HELP_EDITOR: Lines = Lines::new(
+    &["editor", "启动文本编辑器"],
+    &["editor", "Start the text editor"],
+);
+#[cfg(feature = "cmd-text")]
+pub(crate) const HELP_LIST: Lines =
+    Lines::new(&["list", "显示文件列表"], &["list", "List the files"]);
+#[cfg(feature = "cmd-text")]
+pub(crate) const HELP_NEW: Lines = Lines::new(
+    &["new <文件名>", "创建新文件"],
+    &["new <file>", "Create a file"],
+);
+#[cfg(feature = "cmd-text")]
+pub(crate) const HELP_OPEN: Lines = Lines::new(
+    &["open <文件名>", "打开文件"],
+    &["open <file>", "Open a file"],
+);
+
+#[cfg(feature = "cmd-snake")]
+pub(crate) const HELP_SNAKE: Lines = Lines::new(
+    &["snake", "启动贪吃蛇游戏"],
+    &["snake", "Start the snake game"],
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const HELP_SNAKE_DIFFICULTY: Lines = Lines::new(
+    &[
+        "snake-difficulty [-l <难度>] [-s <速度>]",
+        "设置游戏难度",
+        "  -l, --level <难度>  easy、normal 或 hard",
+        "  -s, --speed <速度>  1-10",
+    ],
+    &[
+        "snake-difficulty [-l <level>] [-s <speed>]",
+        "Set the difficulty",
+        "  -l, --level <level>  easy, normal or hard",
+        "  -s, --speed <speed>  1-10",
+    ],
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const HELP_SNAKE_HELP: Lines = Lines::new(
+    &["snake-help", "显示游戏帮助"],
+    &["snake-help", "Show how to play"],
+);
+#[cfg(feature = "cmd-snake")]
+pub(crate) const HELP_SNAKE_SEED: Lines = Lines::new(
+    &[
+        "snake-seed <种子>",
+        "设置随机种子",
+        "  <种子>  随机种子（十六进制）",
+    ],
+    &[
+        "snake-seed <seed>",
+        "Set the random seed",
+        "  <seed>  the seed (hex)",
+    ],
+);
+
+#[cfg(feature = "cmd-image")]
+pub(crate) const HELP_SHOW: Lines = Lines::new(
+    &[
+        "show [图片名]",
+        "显示固件里的图片，不带名字列出所有图片",
+        "  [图片名]  display/images 下的文件名，不带后缀",
+    ],
+    &[
+        "show [name]",
+        "Show an image from the firmware, or list them without a name",
+        "  [name]  file name under display/images, without the extension",
+    ],
+);
+
+pub(crate) const HELP_MENU: Lines = Lines::new(
+    &["menu", "回到屏幕上的主菜单"],
+    &["menu", "Go back to the on-screen menu"],
+);
+pub(crate) const HELP_FPS: Lines = Lines::new(
+    &[
+        "fps [on|off]",
+        "显示刷新帧率，`fps on`/`fps off` 开关每秒打印",
+        "  [on|off]  不填只打印一次",
+    ],
+    &[
+        "fps [on|off]",
+        "Show the frame rate, `fps on`/`fps off` toggles printing it every second",
+        "  [on|off]  leave out to print once",
+    ],
+);
+pub(crate) const HELP_BRIGHTNESS: Lines = Lines::new(
+    &[
+        "brightness [亮度]",
+        "查看或设置背光亮度（0-100）",
+        "  [亮度]  亮度百分比，不填只打印当前亮度",
+    ],
+    &[
+        "brightness [level]",
+        "Show or set the backlight brightness (0-100)",
+        "  [level]  percentage, leave out to print the current one",
+    ],
+);
+pub(crate) const HELP_IDLE: Lines = Lines::new(
+    &[
+        "idle [变暗秒数] [关屏秒数]",
+        "查看或设置闲置多少秒后变暗、关背光，0 表示不变暗/不关",
+        "  [变暗秒数]  变暗前的闲置秒数",
+        "  [关屏秒数]  关背光前的闲置秒数",
+    ],
+    &[
+        "idle [dim] [sleep]",
+        "Show or set the idle seconds before dimming and turning the backlight off, 0 means never",
+        "  [dim]    idle seconds before dimming",
+        "  [sleep]  idle seconds before the backlight goes off",
+    ],
+);
+pub(crate) const HELP_THEME: Lines = Lines::new(
+    &[
+        "theme [主题名]",
+        "查看或切换配色主题",
+        "  [主题名]  不填列出所有主题",
+    ],
+    &[
+        "theme [name]",
+        "Show or switch the colour theme",
+        "  [name]  leave out to list the themes",
+    ],
+);
+pub(crate) const HELP_SCREENSHOT: Lines = Lines::new(
+    &[
+        "screenshot",
+        "把当前屏幕内容从串口发出去，主机上用 shot2png 转成图片",
+    ],
+    &[
+        "screenshot",
+        "Send the screen contents over the serial port, turn them into a PNG with shot2png",
+    ],
+);
+pub(crate) const HELP_RECORD: Lines = Lines::new(
+    &[
+        "record <start|stop> [文件名]",
+        "录屏（模拟器），`record start` 开始，`record stop` 结束并写出 GIF",
+        "  [文件名]  输出文件，默认 record.gif",
+    ],
+    &[
+        "record <start|stop> [file]",
+        "Record the screen (simulator), `record start` begins, `record stop` writes the GIF",
+        "  [file]  output file, record.gif by default",
+    ],
+);
+
+pub(crate) const HELP_GET: Lines = Lines::new(
+    &["get <键名>", "查看一项设置", "  <键名>  settings 列出全部"],
+    &[
+        "get <key>",
+        "Show one setting",
+        "  <key>  settings lists them all",
+    ],
+);
+pub(crate) const HELP_SET: Lines = Lines::new(
+    &[
+        "set <键名> <值>",
+        "修改一项设置并保存",
+        "  <值>  新的值，default 恢复默认",
+    ],
+    &[
+        "set <key> <value>",
+        "Change a setting and save it",
+        "  <value>  the new value, default restores the default",
+    ],
+);
+pub(crate) const HELP_SETTINGS: Lines = Lines::new(
+    &[
+        "settings [reset|export]",
+        "列出所有设置，`settings reset` 全部恢复默认，`settings export` 打印存储内容",
+    ],
+    &[
+        "settings [reset|export]",
+        "List all settings, `settings reset` restores the defaults, `settings export` prints what is stored",
+    ],
+);
+
+pub(crate) const HELP_LOG: Lines = Lines::new(
+    &[
+        "log [级别]",
+        "查看或修改串口日志级别",
+        "  [级别]  off、error、warn、info、debug 或 trace，不填查看当前级别",
+    ],
+    &[
+        "log [level]",
+        "Show or change the serial log level",
+        "  [level]  off, error, warn, info, debug or trace, leave out to show the current one",
+    ],
+);
+pub(crate) const HELP_DMESG: Lines = Lines::new(
+    &[
+        "dmesg [clear]",
+        "查看最近的日志（记到 debug 级），`dmesg clear` 清空",
+    ],
+    &[
+        "dmesg [clear]",
+        "Show the recent log (kept down to debug), `dmesg clear` empties it",
+    ],
+);
+
+pub(crate) const HELP_FREE: Lines =
+    Lines::new(&["free", "堆用量概况"], &["free", "Heap usage at a glance"]);
+pub(crate) const HELP_MEMINFO: Lines = Lines::new(
+    &["meminfo", "堆的详细统计：用量、峰值、分配次数"],
+    &[
+        "meminfo",
+        "Detailed heap statistics: usage, peak, allocation counts",
+    ],
+);
+pub(crate) const HELP_PERF: Lines = Lines::new(
+    &[
+        "perf [reset]",
+        "查看各段代码的耗时（次数、最短、平均、最长），`perf reset` 清零",
+    ],
+    &[
+        "perf [reset]",
+        "Show how long timed code took (count, min, avg, max), `perf reset` clears it",
+    ],
+);
+
+#[cfg(feature = "panic-handler")]
+pub(crate) const HELP_LASTPANIC: Lines = Lines::new(
+    &[
+        "lastpanic [clear]",
+        "查看上次 panic 的原因和位置，`lastpanic clear` 清掉记录",
+    ],
+    &[
+        "lastpanic [clear]",
+        "Show the reason and location of the last panic, `lastpanic clear` forgets it",
+    ],
+);
+
+#[cfg(feature = "target-ui-sim")]
+pub(crate) const HELP_QUIT: Lines = Lines::new(
+    &["quit", "退出程序（也可以用 exit、close）"],
+    &["quit", "Exit the program (exit and close work too)"],
+);
@@ -0,0 +1,88 @@
+//! 闲置变暗和休眠
+//!
+//! 一段时间没有串口输入先把背光调暗，再久一点直接关掉，有输入就恢复。
+//! 屏幕关掉时唤醒它的那个按键会被吞掉，免得摸黑敲进命令行或者游戏里。
+//! 只在主循环里驱动，演示也都在主循环里跑。动画在放、贪吃蛇在玩的时候（[`App::is_busy`]）主循环每轮都当作有输入，
+//! 不会变暗，玩到一半按的键也不会被吞；暂停了、停在阅读器或编辑器里没人动才会变暗关屏。
+//!
+//! [`App::is_busy`]: crate::app::App::is_busy
+
+use crate::*;
+
+/// 变暗时的亮度是原来的几分之一
+const DIM_DIVISOR: u8 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IdleState {
+    Awake,
+    /// 变暗了，记着原来的亮度
+    Dimmed(u8),
+    /// 背光关了，记着原来的亮度
+    Asleep(u8),
+}
+
+/// 闲置计时
+pub(crate) struct IdleDimmer {
+    /// 闲置多久变暗，0 表示不变暗
+    pub dim_after_ms: u32,
+    /// 闲置多久关背光，0 表示不关
+    pub sleep_after_ms: u32,
+    last_activity: u32,
+    state: IdleState,
+}
+
+impl IdleDimmer {
+    pub fn new(now: u32) -> Self {
+        Self {
+            dim_after_ms: 60_000,
+            sleep_after_ms: 300_000,
+            last_activity: now,
+            state: IdleState::Awake,
+        }
+    }
+
+    /// 有输入时调用，恢复亮度；如果屏幕刚才是关着的返回 true，这次输入应该丢掉
+    pub fn wake<B: DisplayBackend>(&mut self, backend: &mut B) -> bool {
+        self.last_activity = backend.now_ms();
+        match self.state {
+            IdleState::Awake => false,
+            IdleState::Dimmed(level) => {
+                backend.set_brightness(level);
+                self.state = IdleState::Awake;
+                false
+            }
+            IdleState::Asleep(level) => {
+                backend.set_brightness(level);
+                self.state = IdleState::Awake;
+                true
+            }
+        }
+    }
+
+    /// 每轮主循环调用，到时间了就变暗或者关背光
+    pub fn tick<B: DisplayBackend>(&mut self, backend: &mut B) {
+        let idle = backend.now_ms().wrapping_sub(self.last_activity);
+        let sleep = self.sleep_after_ms > 0 && idle >= self.sleep_after_ms;
+        let dim = self.dim_after_ms > 0 && idle >= self.dim_after_ms;
+
+        match self.state {
+            IdleState::Awake if sleep => {
+                if let Some(level) = backend.brightness() {
+                    backend.set_brightness(0);
+                    self.state = IdleState::Asleep(level);
+                }
+            }
+            IdleState::Awake if dim => {
+                if let Some(level) = backend.brightness() {
+                    backend.set_brightness(level / DIM_DIVISOR);
+                    self.state = IdleState::Dimmed(level);
+                }
+            }
+            IdleState::Dimmed(level) if sleep => {
+                backend.set_brightness(0);
+                self.state = IdleState::Asleep(level);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! 图片
+//!
+//! 放进固件的图片由 build.rs 从 `display/images/*.png` 转成 RLE 压缩的 Rgb565（后缀 `.r565`），
+//! 同目录下的 `.bmp` 原样打包，方便直接用电脑上的工具导出的图。
+//! 两种格式都直接从 flash 里边解边画，不用先解到内存。
+//!
+//! RLE565 格式（多字节整数都是小端）：
+//!
+//! ```text
+//! "R565" | 版本 u8 = 1 | 标志 u8（bit0: 有透明像素） | 宽 u16 | 高 u16 | 数据包...
+//! ```
+//!
+//! 每个数据包一个字节的头，高 2 位是类型，低 6 位是像素数减一（1-64 个），包可以跨行：
+//!
+//! - `00` 原样：后面跟着这么多个像素，每个 2 字节大端 Rgb565
+//! - `01` 重复：后面跟 1 个像素，重复这么多次
+//! - `10` 透明：这么多个像素不画
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+
+use embedded_cli::Command;
+use embedded_graphics::{
+    image::{Image, ImageDrawable},
+    pixelcolor::{Rgb565, raw::RawU16},
+    prelude::*,
+    primitives::Rectangle,
+};
+use tinybmp::Bmp;
+
+const RLE_MAGIC: &[u8; 4] = b"R565";
+const RLE_VERSION: u8 = 1;
+const RLE_HEADER_LEN: usize = 10;
+const RLE_FLAG_TRANSPARENT: u8 = 0x01;
+
+// build.rs 生成的图片表，没跑 build.rs（比如模拟器）时是空的
+#[cfg(hanzi_images)]
+include!(concat!(env!("OUT_DIR"), "/images.rs"));
+#[cfg(not(hanzi_images))]
+static IMAGES: &[(&str, &[u8])] = &[];
+
+#[derive(Command, Debug)]
+pub(crate) enum ImageCommand<'a> {
+    /// 显示固件里的图片，不带名字列出所有图片
+    #[command(name = "show")]
+    Show {
+        /// 图片名（display/images 下的文件名，不带后缀）
+        name: Option<&'a str>,
+    },
+}
+
+pub(crate) fn handle_image_command(
+    manager: &mut DisplayManager,
+    command: ImageCommand<'_>,
+) -> Result<(), core::convert::Infallible> {
+    match command {
+        ImageCommand::Show { name: None } => {
+            if IMAGES.is_empty() {
+                println!("\r\n{}\r\n", tr!(i18n::IMAGE_NONE));
+                return Ok(());
+            }
+            println!("\r\n{}\r", tr!(i18n::IMAGE_LIST));
+            for (name, data) in IMAGES {
+                match Picture::parse(data) {
+                    Ok(picture) => {
+                        let size = picture.size();
+                        println!(
+                            "{}\r",
+                            tr!(
+                                i18n::IMAGE_ENTRY,
+                                format!("{:<16}", name),
+                                format!("{}x{}", size.width, size.height),
+                                picture.format(),
+                                data.len()
+                            )
+                        );
+                    }
+                    Err(e) => println!(
+                        "{}\r",
+                        tr!(i18n::IMAGE_ENTRY_BROKEN, format!("{:<16}", name), e)
+                    ),
+                }
+            }
+            println!("\r");
+        }
+        ImageCommand::Show { name: Some(name) } => match find(name) {
+            Some(Ok(picture)) => {
+                // 居中显示，比屏幕大的图裁掉四边
+                let screen = manager.size();
+                let size = picture.size();
+                let offset = Point::new(
+                    (screen.width as i32 - size.width as i32) / 2,
+                    (screen.height as i32 - size.height as i32) / 2,
+                );
+                let target = manager.target();
+                let _ = target.clear(DisplayColor::BLACK);
+                let _ = Image::new(&picture, offset).draw(target);
+                println!(
+                    "\r\n{}\r\n",
+                    tr!(
+                        i18n::IMAGE_SHOWN,
+                        name,
+                        format!("{}x{}", size.width, size.height),
+                        picture.format()
+                    )
+                );
+            }
+            Some(Err(e)) => println!("\r\n{}\r\n", tr!(i18n::IMAGE_BROKEN, name, e)),
+            None => println!("\r\n{}\r\n", tr!(i18n::IMAGE_UNKNOWN, name)),
+        },
+    }
+    Ok(())
+}
+
+/// 按名字找图片
+pub(crate) fn find(name: &str) -> Option<Result<Picture<'static>, &'static str>> {
+    IMAGES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, data)| Picture::parse(data))
+}
+
+/// 一张图片，按文件头自动识别格式
+pub(crate) enum Picture<'a> {
+    Rle(Rle565<'a>),
+    Bmp(Bmp<'a, DisplayColor>),
+}
+
+impl<'a> Picture<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, &'static str> {
+        if data.starts_with(RLE_MAGIC) {
+            Rle565::parse(data).map(Picture::Rle)
+        } else if data.starts_with(b"BM") {
+            Bmp::from_slice(data)
+                .map(Picture::Bmp)
+                .map_err(|_| tr!(i18n::IMAGE_BMP_UNSUPPORTED))
+        } else {
+            Err(tr!(i18n::IMAGE_UNKNOWN_FORMAT))
+        }
+    }
+
+    /// 格式名，打印用
+    pub fn format(&self) -> &'static str {
+        match self {
+            Picture::Rle(_) => "rle565",
+            Picture::Bmp(_) => "bmp",
+        }
+    }
+}
+
+impl OriginDimensions for Picture<'_> {
+    fn size(&self) -> Size {
+        match self {
+            Picture::Rle(image) => image.size(),
+            Picture::Bmp(image) => image.size(),
+        }
+    }
+}
+
+impl ImageDrawable for Picture<'_> {
+    type Color = DisplayColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        match self {
+            Picture::Rle(image) => image.draw(target),
+            Picture::Bmp(image) => image.draw(target),
+        }
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        match self {
+            Picture::Rle(image) => image.draw_sub_image(target, area),
+            Picture::Bmp(image) => image.draw_sub_image(target, area),
+        }
+    }
+}
+
+// ===========================================
+// RLE565
+// ============================================
+
+/// RLE 压缩的 Rgb565 图片，数据直接引用 flash
+#[derive(Clone, Copy)]
+pub(crate) struct Rle565<'a> {
+    size: Size,
+    transparent: bool,
+    packets: &'a [u8],
+}
+
+impl<'a> Rle565<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, &'static str> {
+        if data.len() < RLE_HEADER_LEN || !data.starts_with(RLE_MAGIC) {
+            return Err(tr!(i18n::IMAGE_NOT_RLE));
+        }
+        if data[4] != RLE_VERSION {
+            return Err(tr!(i18n::IMAGE_RLE_VERSION));
+        }
+        let width = u16::from_le_bytes([data[6], data[7]]) as u32;
+        let height = u16::from_le_bytes([data[8], data[9]]) as u32;
+        Ok(Self {
+            size: Size::new(width, height),
+            transparent: data[5] & RLE_FLAG_TRANSPARENT != 0,
+            packets: &data[RLE_HEADER_LEN..],
+        })
+    }
+
+    /// 按行优先逐个吐出像素，透明的是 `None`；数据截断时提前结束
+    pub fn pixels(&self) -> RlePixels<'a> {
+        RlePixels {
+            data: self.packets,
+            kind: 0,
+            left: 0,
+            run: None,
+        }
+    }
+}
+
+impl OriginDimensions for Rle565<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl ImageDrawable for Rle565<'_> {
+    type Color = DisplayColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = Rectangle::new(Point::zero(), self.size);
+        if self.transparent {
+            target.draw_iter(
+                area.points()
+                    .zip(self.pixels())
+                    .filter_map(|(point, color)| color.map(|c| Pixel(point, c))),
+            )
+        } else {
+            // 不透明的图整块写，帧缓冲和成块写屏都能走快速路径
+            target.fill_contiguous(
+                &area,
+                self.pixels().map(|c| c.unwrap_or(DisplayColor::BLACK)),
+            )
+        }
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bounds = Rectangle::new(Point::zero(), self.size);
+        target.draw_iter(
+            bounds
+                .points()
+                .zip(self.pixels())
+                .filter(|(point, _)| area.contains(*point))
+                .filter_map(|(point, color)| color.map(|c| Pixel(point - area.top_left, c))),
+        )
+    }
+}
+
+/// RLE565 解码器
+pub(crate) struct RlePixels<'a> {
+    data: &'a [u8],
+    kind: u8,
+    left: u8,
+    run: Option<DisplayColor>,
+}
+
+impl RlePixels<'_> {
+    fn read_color(&mut self) -> Option<DisplayColor> {
+        let (bytes, rest) = self.data.split_first_chunk::<2>()?;
+        self.data = rest;
+        let color = Rgb565::from(RawU16::new(u16::from_be_bytes(*bytes)));
+        Some(color.into())
+    }
+}
+
+impl Iterator for RlePixels<'_> {
+    type Item = Option<DisplayColor>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 {
+            let (&header, rest) = self.data.split_first()?;
+            self.data = rest;
+            self.kind = header >> 6;
+            self.left = (header & 0x3F) + 1;
+            self.run = match self.kind {
+                0b01 => Some(self.read_color()?),
+                _ => None,
+            };
+        }
+
+        self.left -= 1;
+        match self.kind {
+            0b00 => Some(Some(self.read_color()?)),
+            0b01 => Some(self.run),
+            0b10 => Some(None),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! 主菜单
+//!
+//! 开机后屏幕上列出编进固件的演示，方向键（或 w/s、数字键）选择，回车进入，
+//! 不接电脑也能玩；演示退出后回到菜单。按 q 回命令行，命令行里输入 `menu` 再回来。
+//! 和 C 版 snake_lcd 的 `show_menu` 一个意思。
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+
+use crate::theme;
+use crate::widget::{KeyDecoder, List, Response, StatusBar, Widget};
+use embedded_cli::Command;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// 比这矮的屏（比如 LED 点阵）放不下菜单，开机直接进命令行
+const MIN_HEIGHT: u32 = 64;
+
+#[derive(Command, Debug)]
+pub(crate) enum MenuCommand {
+    /// 回到屏幕上的主菜单
+    #[command(name = "menu")]
+    Open,
+}
+
+/// 菜单里能启动的东西
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Entry {
+    #[cfg(feature = "cmd-font")]
+    FontDemo,
+    #[cfg(feature = "cmd-font")]
+    Marquee,
+    #[cfg(feature = "cmd-text")]
+    Reader,
+    #[cfg(feature = "cmd-text")]
+    Editor,
+    #[cfg(feature = "cmd-snake")]
+    Snake,
+    /// 回命令行
+    Shell,
+}
+
+impl Entry {
+    /// 按功能开关列出所有入口
+    fn all() -> Vec<Entry> {
+        vec![
+            #[cfg(feature = "cmd-font")]
+            Entry::FontDemo,
+            #[cfg(feature = "cmd-font")]
+            Entry::Marquee,
+            #[cfg(feature = "cmd-text")]
+            Entry::Reader,
+            #[cfg(feature = "cmd-text")]
+            Entry::Editor,
+            #[cfg(feature = "cmd-snake")]
+            Entry::Snake,
+            Entry::Shell,
+        ]
+    }
+
+    /// 菜单上显示的名字（屏幕字体只有 ASCII）
+    fn title(self) -> &'static str {
+        match self {
+            #[cfg(feature = "cmd-font")]
+            Entry::FontDemo => "Font demo",
+            #[cfg(feature = "cmd-font")]
+            Entry::Marquee => "Marquee",
+            #[cfg(feature = "cmd-text")]
+            Entry::Reader => "Text reader",
+            #[cfg(feature = "cmd-text")]
+            Entry::Editor => "Text editor",
+            #[cfg(feature = "cmd-snake")]
+            Entry::Snake => "Snake",
+            Entry::Shell => "Shell",
+        }
+    }
+
+    /// 对应的命令，打印到串口
+    fn command(self) -> &'static str {
+        match self {
+            #[cfg(feature = "cmd-font")]
+            Entry::FontDemo => "font",
+            #[cfg(feature = "cmd-font")]
+            Entry::Marquee => "marquee",
+            #[cfg(feature = "cmd-text")]
+            Entry::Reader => "reader",
+            #[cfg(feature = "cmd-text")]
+            Entry::Editor => "editor",
+            #[cfg(feature = "cmd-snake")]
+            Entry::Snake => "snake",
+            Entry::Shell => "",
+        }
+    }
+}
+
+/// 主菜单
+pub(crate) struct Launcher {
+    entries: Vec<Entry>,
+    list: List,
+    keys: KeyDecoder,
+}
+
+impl Launcher {
+    pub fn new(size: Size) -> Self {
+        let entries = Entry::all();
+        let list = List::new(
+            Rectangle::new(
+                Point::zero(),
+                size.saturating_sub(Size::new(0, StatusBar::HEIGHT)),
+            ),
+            entries.iter().map(|e| e.title().to_string()).collect(),
+        )
+        .title(splash::FIRMWARE_NAME);
+        Self {
+            entries,
+            list,
+            keys: KeyDecoder::new(),
+        }
+    }
+
+    /// 屏幕放不放得下菜单
+    pub fn fits(size: Size) -> bool {
+        size.height >= MIN_HEIGHT
+    }
+
+    /// 画出整个菜单，串口上也列一份
+    pub fn show<B: DisplayBackend>(&self, backend: &mut B) {
+        println!("\r\n{}\r", tr!(i18n::MENU_TITLE));
+        for (i, entry) in self.entries.iter().enumerate() {
+            println!("  {} - {}\r", i + 1, entry.title());
+        }
+        println!("{}\r\n", tr!(i18n::MENU_HINT));
+
+        let size = backend.size();
+        let target = backend.target();
+        let _ = target.clear(theme::current().background);
+        self.list.draw(target);
+
+        let mut bar = StatusBar::bottom(size);
+        bar.set_left("Enter:run");
+        bar.set_right("q:shell");
+        bar.draw(target);
+    }
+
+    /// 回命令行前把菜单擦掉
+    pub fn hide<B: DisplayBackend>(&self, backend: &mut B) {
+        let _ = backend.target().clear(theme::current().background);
+        println!("\r\n{}\r\n", tr!(i18n::MENU_CLOSED));
+    }
+
+    /// 处理一个字节，选中入口时返回它；按 q/Esc 返回 [`Entry::Shell`]
+    pub fn handle_input<B: DisplayBackend>(&mut self, backend: &mut B, byte: u8) -> Option<Entry> {
+        let key = self.keys.feed(byte)?;
+        match self.list.handle_key(key) {
+            Response::Changed => {
+                self.list.draw(backend.target());
+                None
+            }
+            Response::Activated(index) => {
+                let entry = self.entries[index];
+                if entry != Entry::Shell {
+                    println!(
+                        "{}\r",
+                        tr!(i18n::MENU_LAUNCH, entry.title(), entry.command())
+                    );
+                }
+                Some(entry)
+            }
+            Response::Cancelled => Some(Entry::Shell),
+            Response::Ignored => None,
+        }
+    }
+}
+
+pub(crate) fn handle_menu_command(
+    manager: &mut DisplayManager,
+    launcher: &mut Option<Launcher>,
+    command: MenuCommand,
+) -> Result<(), core::convert::Infallible> {
+    match command {
+        MenuCommand::Open => {
+            let size = manager.size();
+            if Launcher::fits(size) {
+                let menu = Launcher::new(size);
+                menu.show(manager);
+                *launcher = Some(menu);
+            } else {
+                let size = format!("{}x{}", size.width, size.height);
+                println!("\r\n{}\r\n", tr!(i18n::MENU_TOO_SMALL, size));
+            }
+        }
+    }
+    Ok(())
+}
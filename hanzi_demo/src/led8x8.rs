@@ -0,0 +1,74 @@
+//! 8x8 LED 点阵的 GPIO 驱动
+//!
+//! 画布和缩放在 [`crate::matrix`] 里，这里只管把一帧点到灯上。
+
+use crate::matrix::{MATRIX_SIZE, MatrixDriver, MatrixFrame};
+
+/// GPIO 逐行扫描驱动
+///
+/// 行接 8 个引脚、列接 8 个引脚，每次 `refresh` 只点亮一行，
+/// 刷得够快就看不出闪烁。
+pub(crate) struct GpioMatrix {
+    rows: [u32; MATRIX_SIZE],
+    cols: [u32; MATRIX_SIZE],
+    // 点亮时行/列引脚的电平，共阳/共阴的点阵正好相反
+    row_on: bool,
+    col_on: bool,
+    frame: MatrixFrame,
+    current_row: usize,
+}
+
+impl GpioMatrix {
+    // gpio_config 里 mode 为 0 时置方向寄存器，也就是输出
+    const GPIO_MODE_OUTPUT: u32 = 0;
+
+    /// `rows`/`cols` 是排针号（1-16）
+    pub fn new(
+        rows: [u32; MATRIX_SIZE],
+        cols: [u32; MATRIX_SIZE],
+        row_on: bool,
+        col_on: bool,
+    ) -> Self {
+        let mask = rows
+            .iter()
+            .chain(cols.iter())
+            .fold(0u16, |mask, pin| mask | 1 << (pin - 1));
+        ecos_ssc1::GpioPin::config_pins(mask, Self::GPIO_MODE_OUTPUT);
+
+        let matrix = Self {
+            rows,
+            cols,
+            row_on,
+            col_on,
+            frame: [0; MATRIX_SIZE],
+            current_row: 0,
+        };
+        for row in 0..MATRIX_SIZE {
+            matrix.set_row(row, false);
+        }
+        matrix
+    }
+
+    fn set_row(&self, row: usize, on: bool) {
+        let _ = ecos_ssc1::GpioPin::set_level(self.rows[row], on == self.row_on);
+    }
+}
+
+impl MatrixDriver for GpioMatrix {
+    fn show(&mut self, frame: &MatrixFrame) {
+        self.frame = *frame;
+    }
+
+    fn refresh(&mut self) {
+        // 先关掉上一行，再换列数据，避免串到下一行
+        self.set_row(self.current_row, false);
+        self.current_row = (self.current_row + 1) % MATRIX_SIZE;
+
+        let bits = self.frame[self.current_row];
+        for (col, pin) in self.cols.iter().enumerate() {
+            let on = bits & (0x80 >> col) != 0;
+            let _ = ecos_ssc1::GpioPin::set_level(*pin, on == self.col_on);
+        }
+        self.set_row(self.current_row, true);
+    }
+}
@@ -0,0 +1,272 @@
+//! 日志
+//!
+//! 实现 `log` 门面，自己的诊断信息和依赖库的日志都走这里，用 `log::info!` 这些宏打。
+//! 每条带开机以来的时间和模块名，够级别的打到串口，同时记进内存里的环形缓冲，`dmesg` 随时翻出来看。
+//!
+//! 串口级别用 `log <级别>` 改，会记到设置里；环形缓冲固定记到 debug，串口安静的时候出了事也能回头查。
+//! 命令的正常输出还是直接 `println!`，不算日志。
+
+#![allow(static_mut_refs)]
+
+#[allow(unused)]
+use crate::*;
+
+use crate::i18n::{self, tr};
+use crate::settings::{self, Key};
+
+use core::fmt::Write;
+use core::sync::atomic::{AtomicU8, Ordering};
+use embedded_cli::Command;
+use log::{Level, LevelFilter, Metadata, Record};
+
+/// 环形缓冲大小，满了从最旧的开始覆盖
+const RING_SIZE: usize = 4096;
+
+/// 环形缓冲记到哪一级
+const RING_LEVEL: LevelFilter = LevelFilter::Debug;
+
+/// 去掉模块名里的 crate 名，标签短一点
+const CRATE_PREFIX: &str = "hanzi_demo::";
+
+/// 级别名，命令和设置里用
+const LEVELS: [(&str, LevelFilter); 6] = [
+    ("off", LevelFilter::Off),
+    ("error", LevelFilter::Error),
+    ("warn", LevelFilter::Warn),
+    ("info", LevelFilter::Info),
+    ("debug", LevelFilter::Debug),
+    ("trace", LevelFilter::Trace),
+];
+
+// 串口级别，存的是 LEVELS 的下标
+static CONSOLE: AtomicU8 = AtomicU8::new(3);
+
+static LOGGER: Logger = Logger;
+
+// 最近的日志
+static mut RING: Ring = Ring::new();
+
+fn ring() -> &'static mut Ring {
+    unsafe { &mut RING }
+}
+
+/// 开机最先调用，之前打的日志都丢掉
+pub(crate) fn init() {
+    // SDK 说过在板子上用原子的 compare_exchange 装 logger 有特权级问题，这里只在开机时单线程装一次，用不着原子
+    unsafe {
+        let _ = log::set_logger_racy(&LOGGER);
+    }
+    update_max_level();
+}
+
+/// 级别名转成过滤器
+pub(crate) fn parse_level(name: &str) -> Option<LevelFilter> {
+    LEVELS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|&(_, level)| level)
+}
+
+/// 当前串口级别的名字
+pub(crate) fn level_name() -> &'static str {
+    LEVELS[CONSOLE.load(Ordering::Relaxed) as usize].0
+}
+
+/// 改串口级别，不认识的名字返回 `false`
+pub(crate) fn set_level(name: &str) -> bool {
+    match LEVELS
+        .iter()
+        .position(|(n, _)| n.eq_ignore_ascii_case(name))
+    {
+        Some(index) => {
+            CONSOLE.store(index as u8, Ordering::Relaxed);
+            update_max_level();
+            true
+        }
+        None => false,
+    }
+}
+
+fn console_level() -> LevelFilter {
+    LEVELS[CONSOLE.load(Ordering::Relaxed) as usize].1
+}
+
+// 门面那边按串口和环形缓冲里更细的那个过滤，省得格式化用不上的日志
+fn update_max_level() {
+    unsafe {
+        log::set_max_level_racy(console_level().max(RING_LEVEL));
+    }
+}
+
+/// 开机以来的毫秒数
+#[cfg(feature = "need-ecos")]
+fn uptime_ms() -> u32 {
+    ecos_ssc1::Timer::get_tick()
+}
+
+/// 开机以来的毫秒数，模拟器从第一次调用算起
+#[cfg(not(feature = "need-ecos"))]
+fn uptime_ms() -> u32 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_millis() as u32
+}
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= console_level() || metadata.level() <= RING_LEVEL
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let now = uptime_ms();
+        let target = record.target();
+        let tag = match target.strip_prefix(CRATE_PREFIX) {
+            Some(module) => module,
+            None if target == "hanzi_demo" => "main",
+            None => target,
+        };
+
+        let mut line = String::with_capacity(64);
+        let _ = write!(
+            line,
+            "[{:>5}.{:03}] {:<5} {}: {}",
+            now / 1000,
+            now % 1000,
+            level_label(record.level()),
+            tag,
+            record.args()
+        );
+
+        if record.level() <= console_level() {
+            println!("{}\r", line);
+        }
+        if record.level() <= RING_LEVEL {
+            let ring = ring();
+            ring.push(line.as_bytes());
+            ring.push(b"\n");
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn level_label(level: Level) -> &'static str {
+    match level {
+        Level::Error => "ERROR",
+        Level::Warn => "WARN",
+        Level::Info => "INFO",
+        Level::Debug => "DEBUG",
+        Level::Trace => "TRACE",
+    }
+}
+
+/// 字节环形缓冲，按行存日志
+struct Ring {
+    buf: [u8; RING_SIZE],
+    start: usize,
+    len: usize,
+}
+
+impl Ring {
+    const fn new() -> Self {
+        Self {
+            buf: [0; RING_SIZE],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            let end = (self.start + self.len) % RING_SIZE;
+            self.buf[end] = b;
+            if self.len == RING_SIZE {
+                self.start = (self.start + 1) % RING_SIZE;
+            } else {
+                self.len += 1;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+
+    /// 按时间顺序取出来，绕过一圈时最前面那行被截断了，丢掉
+    fn contents(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.len);
+        for i in 0..self.len {
+            data.push(self.buf[(self.start + i) % RING_SIZE]);
+        }
+        if self.len == RING_SIZE {
+            let cut = data.iter().position(|&b| b == b'\n').map_or(0, |i| i + 1);
+            data.drain(..cut);
+        }
+        data
+    }
+}
+
+#[derive(Command, Debug)]
+pub(crate) enum LogCommand<'a> {
+    /// 查看或修改串口日志级别
+    #[command(name = "log")]
+    Level {
+        /// off、error、warn、info、debug 或 trace，不填查看当前级别
+        level: Option<&'a str>,
+    },
+
+    /// 查看最近的日志（记到 debug 级），`dmesg clear` 清空
+    #[command(name = "dmesg")]
+    Dmesg {
+        /// clear 清空，不填打印全部
+        action: Option<&'a str>,
+    },
+}
+
+pub(crate) fn handle_log_command(command: LogCommand<'_>) -> Result<(), core::convert::Infallible> {
+    match command {
+        LogCommand::Level { level: None } => {
+            println!("\r\n{}\r\n", tr!(i18n::LOG_LEVEL_NOW, level_name()));
+        }
+        LogCommand::Level { level: Some(name) } => {
+            if set_level(name) {
+                let _ =
+                    settings::set_text(Key::LogLevel, level_name()).and_then(|_| settings::save());
+                println!("\r\n{}\r\n", tr!(i18n::LOG_LEVEL_SET, level_name()));
+            } else {
+                println!("\r\n{}\r\n", tr!(i18n::LOG_LEVEL_UNKNOWN, name));
+            }
+        }
+        LogCommand::Dmesg { action: None } => {
+            let data = ring().contents();
+            if data.is_empty() {
+                println!("\r\n{}\r\n", tr!(i18n::DMESG_EMPTY));
+                return Ok(());
+            }
+            println!("\r");
+            for line in String::from_utf8_lossy(&data).lines() {
+                println!("{}\r", line);
+            }
+            println!("\r");
+        }
+        LogCommand::Dmesg {
+            action: Some("clear"),
+        } => {
+            ring().clear();
+            println!("\r\n{}\r\n", tr!(i18n::DMESG_CLEARED));
+        }
+        LogCommand::Dmesg {
+            action: Some(other),
+        } => println!("\r\n{}\r\n", tr!(i18n::DMESG_BAD_ACTION, other)),
+    }
+    Ok(())
+}
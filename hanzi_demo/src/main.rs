@@ -1,31 +1,94 @@
 #![cfg_attr(feature = "need-ecos", no_std)]
 #![cfg_attr(feature = "need-ecos", no_main)]
 
-#[cfg(feature = "target-st7735")]
-use ecos_ebui::{St7735Config as DisplayConfig, St7735Manager as DisplayManager};
 #[cfg(feature = "target-ui-sim")]
 use embedded_cli::Command;
 #[cfg(feature = "target-ui-sim")]
 use embedded_graphics::pixelcolor::Rgb888;
 #[cfg(feature = "target-ui-sim")]
-use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
-#[cfg(feature = "target-ui-sim")]
 pub use std::collections::HashMap;
 #[cfg(feature = "target-ui-sim")]
 pub use std::mem;
 
-#[cfg(feature = "target-st7735")]
+#[cfg(any(feature = "target-st7735", feature = "target-led8x8"))]
 pub type DisplayColor = embedded_graphics::pixelcolor::Rgb565;
 #[cfg(feature = "target-ui-sim")]
 pub type DisplayColor = Rgb888;
 
 #[cfg(feature = "need-ecos")]
 use ecos_ssc1::{Uart, ecos_main};
+
+// SDK 的 `alloc` 特性关了（分配器在 heap.rs），它的 prelude 里就少了这些，自己补上
+#[cfg(feature = "need-ecos")]
+extern crate alloc;
+#[cfg(feature = "need-ecos")]
+#[allow(unused_imports)]
+pub use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 #[cfg(not(feature = "need-ecos"))]
 pub use uart_simulator::UartSimulator as Uart;
 
 use embedded_cli::{CommandGroup, cli::CliBuilder, command::RawCommand};
 
+mod app;
+mod backend;
+#[cfg(feature = "target-st7735")]
+mod backlight;
+#[cfg(feature = "target-st7735")]
+mod burst;
+mod color;
+#[cfg(feature = "panic-handler")]
+mod crash;
+#[cfg(feature = "framebuffer")]
+mod framebuffer;
+mod geometry;
+mod heap;
+mod help;
+mod i18n;
+mod idle;
+mod launcher;
+#[cfg(feature = "target-led8x8")]
+mod led8x8;
+mod logging;
+#[cfg(any(feature = "target-led8x8", test))]
+mod matrix;
+mod perf;
+#[cfg(feature = "target-ui-sim")]
+mod record;
+
+use app::{Flow, Scheduler};
+pub(crate) use backend::{DisplayBackend, DisplayManager};
+use i18n::tr;
+
+#[cfg(feature = "panic-handler")]
+use crash::{PanicCommand, handle_panic_command};
+
+mod screen;
+mod screenshot;
+mod settings;
+mod splash;
+#[cfg(feature = "target-st7735")]
+mod st7735;
+mod theme;
+mod widget;
+use heap::{HeapCommand, handle_heap_command};
+use help::WithHelp;
+use launcher::{Entry, Launcher, MenuCommand, handle_menu_command};
+use logging::{LogCommand, handle_log_command};
+use perf::{PerfCommand, handle_perf_command};
+use screen::{ScreenCommand, handle_screen_command};
+use settings::{SettingsCommand, handle_settings_command};
+
 #[cfg(feature = "cmd-cli")]
 mod cli;
 #[cfg(feature = "cmd-cli")]
@@ -34,10 +97,17 @@ use cli::{CmdSample, handle_sample};
 #[cfg(all(feature = "cmd-cli", feature = "need-ecos"))]
 use cli::EbdWriter;
 
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+mod fonts;
+
+#[cfg(feature = "cmd-font")]
+mod anim;
 #[cfg(feature = "cmd-font")]
 mod font;
 #[cfg(feature = "cmd-font")]
-use font::{FontSample, handle_font_display};
+mod marquee;
+#[cfg(feature = "cmd-font")]
+use font::{FontSample, handle_font_display, handle_font_marquee};
 
 #[cfg(feature = "cmd-text")]
 mod text;
@@ -49,6 +119,11 @@ mod snake;
 #[cfg(feature = "cmd-snake")]
 use snake::{SnakeSample, handle_snake_sample};
 
+#[cfg(feature = "cmd-image")]
+mod image;
+#[cfg(feature = "cmd-image")]
+use image::{ImageCommand, handle_image_command};
+
 #[cfg(feature = "target-ui-sim")]
 use uart_simulator::{UartSimulatorWriter, read_byte_nonblock_sim};
 
@@ -57,55 +132,26 @@ enum Group<'a> {
     #[cfg(feature = "cmd-cli")]
     Cmd(CmdSample<'a>),
     #[cfg(feature = "cmd-font")]
-    Font(FontSample),
+    Font(FontSample<'a>),
     #[cfg(feature = "cmd-text")]
     Text(TextSample<'a>),
     #[cfg(feature = "cmd-snake")]
     Snake(SnakeSample<'a>),
+    #[cfg(feature = "cmd-image")]
+    Image(ImageCommand<'a>),
+    Menu(MenuCommand),
+    Screen(ScreenCommand<'a>),
+    Settings(SettingsCommand<'a>),
+    Log(LogCommand<'a>),
+    Heap(HeapCommand),
+    Perf(PerfCommand<'a>),
+    #[cfg(feature = "panic-handler")]
+    Panic(PanicCommand<'a>),
     #[cfg(feature = "target-ui-sim")]
     Quit(QuitCommand),
     Others(RawCommand<'a>),
 }
 
-#[cfg(feature = "target-ui-sim")]
-pub struct DisplayManager {
-    pub display: SimulatorDisplay<DisplayColor>,
-    pub window: Window,
-    #[cfg(feature = "need-ecos")]
-    pub delay: ecos_ssc1::delay::Delay,
-    #[cfg(not(feature = "need-ecos"))]
-    pub delay: SimulatorDelay,
-}
-
-#[cfg(feature = "target-ui-sim")]
-impl DisplayManager {
-    pub fn update_window(&mut self) {
-        self.window.update(&self.display);
-    }
-
-    pub fn display_mut(&mut self) -> &mut SimulatorDisplay<DisplayColor> {
-        &mut self.display
-    }
-
-    pub fn window_mut(&mut self) -> &mut Window {
-        &mut self.window
-    }
-}
-
-#[cfg(feature = "target-ui-sim")]
-pub struct SimulatorDelay;
-
-#[cfg(feature = "target-ui-sim")]
-impl SimulatorDelay {
-    pub fn delay_ms(&self, ms: u32) {
-        std::thread::sleep(std::time::Duration::from_millis(ms as u64));
-    }
-
-    pub fn delay_us(&self, us: u32) {
-        std::thread::sleep(std::time::Duration::from_micros(us as u64));
-    }
-}
-
 #[cfg(feature = "need-ecos")]
 #[ecos_main(tick, qspi(0))]
 fn main() -> ! {
@@ -118,60 +164,32 @@ fn main() -> ! {
 }
 
 fn run_main() -> ! {
-    // 初始化显示管理器
-    #[cfg(feature = "target-st7735")]
-    let config = DisplayConfig {
-        dc_pin: 14,
-        rst_pin: None,
-        width: 128,
-        height: 128,
-        rgb: true,
-        inverted: false,
-    };
+    logging::init();
 
-    #[cfg(feature = "target-st7735")]
-    let mut manager = match DisplayManager::new(config) {
-        Ok(manager) => {
-            println!("显示管理器创建成功");
-            manager
-        }
-        Err(e) => {
-            println!("显示管理器创建失败: {:?}", e);
-            loop {}
-        }
-    };
+    // 设置先读，开机提示就是选好的语言
+    settings::load();
+    #[cfg(feature = "panic-handler")]
+    crash::report();
 
-    #[cfg(feature = "target-ui-sim")]
-    let mut manager = {
-        let display =
-            SimulatorDisplay::<DisplayColor>::new(embedded_graphics::prelude::Size::new(128, 128));
-        let output_settings = OutputSettingsBuilder::new().scale(2).build();
-        let window = Window::new("ECOS Simulator", &output_settings);
-
-        DisplayManager {
-            display,
-            window,
-            #[cfg(feature = "need-ecos")]
-            delay: ecos_ssc1::delay::Delay,
-            #[cfg(not(feature = "need-ecos"))]
-            delay: SimulatorDelay,
-        }
-    };
+    // 初始化显示管理器
+    let mut manager = backend::init(backend::default_geometry());
+    let mut splash = splash::Splash::show(&mut manager);
 
-    #[cfg(feature = "target-st7735")]
-    match manager.init() {
-        Ok(_) => println!("显示初始化成功！"),
-        Err(e) => {
-            println!("显示初始化失败: {:?}", e);
-            loop {}
+    // 字体解析最慢，开机时先做掉，进演示就不用等了
+    #[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+    {
+        splash.progress(&mut manager, 10, tr!(i18n::BOOT_LOADING_FONT));
+        let start = manager.now_ms();
+        match fonts::harmony_sans() {
+            Some(_) => log::info!(
+                "{}",
+                tr!(i18n::BOOT_FONT_LOADED, manager.now_ms().wrapping_sub(start))
+            ),
+            None => log::error!("{}", tr!(i18n::BOOT_FONT_FAILED)),
         }
     }
 
-    #[cfg(feature = "target-ui-sim")]
-    {
-        println!("模拟器显示初始化成功！");
-        manager.window.update(&manager.display);
-    }
+    splash.progress(&mut manager, 80, tr!(i18n::BOOT_INIT_CLI));
 
     #[allow(static_mut_refs)]
     let (command_buffer, history_buffer) = unsafe {
@@ -193,10 +211,24 @@ fn run_main() -> ! {
         })
         .command_buffer(command_buffer)
         .history_buffer(history_buffer)
-        .prompt("\u{001b}[92mheke1228\u{001b}[0m@\u{001b}[94mecos-ssc1\u{001b}[0m$ ")
+        .prompt(settings::prompt())
         .build()
         .unwrap();
 
+    splash.finish(&mut manager);
+    let mut idle = idle::IdleDimmer::new(manager.now_ms());
+    settings::apply(&mut manager, &mut idle);
+
+    // 屏幕放得下就开机进主菜单，按 q 才回命令行
+    let mut launcher = Launcher::fits(manager.size()).then(|| Launcher::new(manager.size()));
+    if let Some(menu) = &launcher {
+        menu.show(&mut manager);
+        manager.present();
+    }
+
+    // 字体演示、阅读器、贪吃蛇这些交互应用都由调度器驱动：进入后按键交给它，每轮循环推进一帧
+    let mut apps = Scheduler::new();
+
     loop {
         let byte = {
             #[cfg(feature = "need-ecos")]
@@ -209,68 +241,147 @@ fn run_main() -> ! {
             }
         };
 
+        // 有输入先把屏幕点亮，屏幕关着时这个键只用来唤醒
+        let byte = match byte {
+            Some(_) if idle.wake(&mut manager) => None,
+            byte => byte,
+        };
+
+        // 应用运行时按键不进命令行，应用退出后回到主菜单或命令行
+        let byte = match byte {
+            Some(byte) if apps.is_running() => {
+                if apps.handle_key(&mut manager, byte) == Flow::Exit {
+                    match &launcher {
+                        Some(menu) => {
+                            menu.show(&mut manager);
+                            manager.present();
+                        }
+                        None => println!("\r\n{}\r\n", tr!(i18n::BACK_TO_SHELL)),
+                    }
+                }
+                None
+            }
+            byte => byte,
+        };
+
+        // 主菜单打开时按键交给菜单，选中的入口和对应的命令走同一个处理函数
+        let byte = match (byte, launcher.as_mut()) {
+            (Some(byte), Some(menu)) => {
+                match menu.handle_input(&mut manager, byte) {
+                    Some(Entry::Shell) => {
+                        menu.hide(&mut manager);
+                        launcher = None;
+                    }
+                    #[cfg(feature = "cmd-font")]
+                    Some(Entry::FontDemo) => {
+                        let _ = handle_font_display::<DisplayColor>(&mut manager, &mut apps);
+                    }
+                    #[cfg(feature = "cmd-font")]
+                    Some(Entry::Marquee) => {
+                        let _ = handle_font_marquee(&mut manager, &mut apps, None);
+                    }
+                    #[cfg(feature = "cmd-text")]
+                    Some(Entry::Reader) => {
+                        let _ = handle_text_display::<DisplayColor>(
+                            &mut manager,
+                            &mut apps,
+                            TextSample::Reader,
+                        );
+                    }
+                    #[cfg(feature = "cmd-text")]
+                    Some(Entry::Editor) => {
+                        let _ = handle_text_display::<DisplayColor>(
+                            &mut manager,
+                            &mut apps,
+                            TextSample::Editor,
+                        );
+                    }
+                    #[cfg(feature = "cmd-snake")]
+                    Some(Entry::Snake) => {
+                        let _ = handle_snake_sample::<DisplayColor>(
+                            &mut manager,
+                            &mut apps,
+                            SnakeSample::Start,
+                        );
+                    }
+                    None => {}
+                }
+                manager.present();
+                None
+            }
+            (byte, _) => byte,
+        };
+
         if let Some(byte) = byte {
-            // 在闭包内部使用 &mut manager
+            // 在闭包内部使用 &mut manager；帮助请求在解析前由 WithHelp 截下
             let _ = cli.process_byte::<Group, _>(
                 byte,
-                &mut Group::processor(|_cli, command| match command {
+                &mut WithHelp(Group::processor(|handle, command| match command {
                     #[cfg(feature = "cmd-cli")]
                     Group::Cmd(cmd) => handle_sample(cmd),
                     #[cfg(feature = "cmd-font")]
                     Group::Font(cmd) => match cmd {
                         FontSample::Start => {
-                            // 启动字体演示，直接使用 &mut manager
-                            handle_font_display::<DisplayColor>(&mut manager)
+                            // 进入字体演示模式，由主循环驱动
+                            handle_font_display::<DisplayColor>(&mut manager, &mut apps)
+                        }
+                        FontSample::Marquee { text } => {
+                            handle_font_marquee(&mut manager, &mut apps, text)
                         }
                     },
                     #[cfg(feature = "cmd-text")]
                     Group::Text(cmd) => {
-                        // 处理文本命令，直接使用 &mut manager
-                        handle_text_display::<DisplayColor>(&mut manager, cmd)
+                        // 处理文本命令，阅读器和编辑器交给调度器
+                        handle_text_display::<DisplayColor>(&mut manager, &mut apps, cmd)
                     }
                     #[cfg(feature = "cmd-snake")]
-                    Group::Snake(cmd) => handle_snake_sample::<DisplayColor>(&mut manager, cmd),
+                    Group::Snake(cmd) => {
+                        handle_snake_sample::<DisplayColor>(&mut manager, &mut apps, cmd)
+                    }
+                    #[cfg(feature = "cmd-image")]
+                    Group::Image(cmd) => handle_image_command(&mut manager, cmd),
+                    Group::Menu(cmd) => handle_menu_command(&mut manager, &mut launcher, cmd),
+                    Group::Screen(cmd) => handle_screen_command(&mut manager, &mut idle, cmd),
+                    Group::Settings(cmd) => {
+                        let prompt = cmd.changes_prompt();
+                        let result = handle_settings_command(&mut manager, &mut idle, cmd);
+                        if prompt {
+                            handle.set_prompt(settings::prompt());
+                        }
+                        result
+                    }
+                    Group::Log(cmd) => handle_log_command(cmd),
+                    Group::Heap(cmd) => handle_heap_command(cmd),
+                    Group::Perf(cmd) => handle_perf_command(cmd),
+                    #[cfg(feature = "panic-handler")]
+                    Group::Panic(cmd) => handle_panic_command(cmd),
                     #[cfg(feature = "target-ui-sim")]
                     Group::Quit(cmd) => match cmd {
                         QuitCommand::Quit | QuitCommand::Exit | QuitCommand::Close => {
-                            println!("正在退出程序...");
+                            println!("{}", tr!(i18n::QUITTING));
                             std::process::exit(0);
                         }
                     },
                     Group::Others(cmd) => {
-                        println!("暂时不支持命令：\n{:#?}", cmd);
+                        println!("{}", tr!(i18n::UNSUPPORTED_COMMAND, format!("{:#?}", cmd)));
                         Ok(())
                     }
-                }),
+                })),
             );
 
-            #[cfg(feature = "target-ui-sim")]
-            {
-                // 直接使用 manager，不需要单独的引用
-                manager.window.update(&manager.display);
-            }
+            manager.present();
         }
 
-        #[cfg(feature = "target-ui-sim")]
-        {
-            // 处理SDL事件，避免窗口无响应
-            for event in manager.window.events() {
-                use embedded_graphics_simulator::SimulatorEvent;
-                #[cfg(feature = "target-ui-sim")]
-                use embedded_graphics_simulator::sdl2::Keycode;
-
-                match event {
-                    SimulatorEvent::Quit => std::process::exit(0),
-                    SimulatorEvent::KeyDown {
-                        keycode: Keycode::Escape,
-                        ..
-                    } => {
-                        std::process::exit(0);
-                    }
-                    _ => {}
-                }
-            }
+        apps.tick(&mut manager);
+        if apps.is_busy() {
+            // 动画在放、游戏在玩说明有人在看，不算闲置
+            idle.wake(&mut manager);
         }
+
+        idle.tick(&mut manager);
+
+        // 处理窗口事件，避免模拟器窗口无响应
+        manager.poll_events();
     }
 }
 
@@ -278,7 +389,7 @@ fn run_main() -> ! {
 mod uart_simulator {
     use core::fmt;
     use std::collections::VecDeque;
-    use std::io::{self, Read, Write};
+    use std::io::{self, BufRead, Write};
     use std::sync::Mutex;
     use std::time::{Duration, Instant};
 
@@ -288,7 +399,7 @@ mod uart_simulator {
 
     impl UartSimulator {
         pub fn init() {
-            println!("UART 模拟器初始化完成");
+            println!("{}", crate::i18n::tr!(crate::i18n::UART_SIM_READY));
         }
 
         pub fn write_byte(b: u8) {
@@ -312,7 +423,6 @@ mod uart_simulator {
             // 使用带超时的非阻塞读取
             let start = Instant::now();
             let timeout = Duration::from_millis(10);
-            let mut buffer = [0u8; 1];
 
             // 尝试读取，但只等待一小段时间
             while start.elapsed() < timeout {
@@ -333,10 +443,7 @@ mod uart_simulator {
                     let ret = unsafe { poll(fds.as_mut_ptr(), 1, remaining_timeout) };
 
                     if ret > 0 && (fds[0].revents & POLLIN) != 0 {
-                        match io::stdin().read(&mut buffer) {
-                            Ok(1) => return Some(buffer[0]),
-                            _ => return None,
-                        }
+                        return read_available();
                     }
                 }
 
@@ -353,10 +460,7 @@ mod uart_simulator {
                             let mut events = 0u32;
                             if GetNumberOfConsoleInputEvents(handle, &mut events) != 0 && events > 0
                             {
-                                match io::stdin().read(&mut buffer) {
-                                    Ok(1) => return Some(buffer[0]),
-                                    _ => return None,
-                                }
+                                return read_available();
                             }
                         }
                     }
@@ -365,6 +469,9 @@ mod uart_simulator {
                 #[cfg(not(any(unix, windows)))]
                 {
                     // 通用方案：直接尝试读取
+                    use std::io::Read;
+
+                    let mut buffer = [0u8; 1];
                     match io::stdin().read(&mut buffer) {
                         Ok(1) => return Some(buffer[0]),
                         Ok(_) => return None,
@@ -393,6 +500,17 @@ mod uart_simulator {
         }
     }
 
+    // 标准输入自带缓冲，一次读进来的都放进队列；只取一个的话缓冲里剩下的 poll 看不到，就卡住了
+    fn read_available() -> Option<u8> {
+        let mut stdin = io::stdin().lock();
+        let data = stdin.fill_buf().ok()?;
+        let len = data.len();
+        let mut queue = INPUT_QUEUE.lock().ok()?;
+        queue.extend(data);
+        stdin.consume(len);
+        queue.pop_front()
+    }
+
     pub struct UartSimulatorWriter;
 
     impl fmt::Write for UartSimulatorWriter {
@@ -420,21 +538,10 @@ mod uart_simulator {
         }
     }
 
+    /// 主循环每轮都调，不能阻塞：和 [`UartSimulator::read_byte_nonblock`] 一样最多等 10ms，
+    /// 没有按键时动画、游戏和窗口事件照样往前走
     pub fn read_byte_nonblock_sim() -> Option<u8> {
-        // 首先检查是否有缓存的输入
-        if let Ok(mut queue) = INPUT_QUEUE.lock() {
-            if !queue.is_empty() {
-                return queue.pop_front();
-            }
-        }
-
-        // 检查标准输入是否有数据
-        let mut buffer = [0u8; 1];
-        if let Ok(1) = io::stdin().read(&mut buffer) {
-            Some(buffer[0])
-        } else {
-            None
-        }
+        UartSimulator::read_byte_nonblock()
     }
 }
 
@@ -0,0 +1,295 @@
+//! 跑马灯（滚动字幕）控件
+//!
+//! 文字先画进一块离屏的行缓冲，再整块推到屏幕上，
+//! 不用再"擦掉上一帧的矩形再重画"，也就不会闪。
+
+use crate::*;
+
+use crate::perf;
+
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, renderer::TextRenderer},
+};
+
+/// 滚动方向
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MarqueeDirection {
+    /// 从右往左（文字从右边进入）
+    Left,
+    /// 从左往右（文字从左边进入）
+    Right,
+}
+
+/// 循环方式
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MarqueeRepeat {
+    /// 滚完一遍就停
+    Once,
+    /// 滚指定遍数
+    Times(u16),
+    /// 一直滚
+    Forever,
+}
+
+/// 跑马灯控件
+pub(crate) struct Marquee<S> {
+    text: String,
+    style: S,
+    area: Rectangle,
+    background: DisplayColor,
+    direction: MarqueeDirection,
+    repeat: MarqueeRepeat,
+    /// 滚动速度（像素/秒）
+    speed: u32,
+    /// 两遍之间的空白（像素）
+    gap: u32,
+    /// 已滚动的距离（千分之一像素）
+    progress: u64,
+    /// 每个字符的起始 x 坐标，最后一项是整段文字的宽度
+    char_x: Vec<i32>,
+    buffer: LineBuffer,
+}
+
+impl<S> Marquee<S>
+where
+    S: TextRenderer<Color = DisplayColor> + Clone,
+{
+    pub fn new(text: &str, area: Rectangle, style: S) -> Self {
+        let mut marquee = Self {
+            text: String::new(),
+            style,
+            area,
+            background: DisplayColor::BLACK,
+            direction: MarqueeDirection::Left,
+            repeat: MarqueeRepeat::Forever,
+            speed: 40,
+            gap: area.size.width,
+            progress: 0,
+            char_x: Vec::new(),
+            buffer: LineBuffer::new(area.size),
+        };
+        marquee.set_text(text);
+        marquee
+    }
+
+    /// 滚动速度（像素/秒）
+    pub fn speed(mut self, pixels_per_second: u32) -> Self {
+        self.speed = pixels_per_second;
+        self
+    }
+
+    pub fn direction(mut self, direction: MarqueeDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn repeat(mut self, repeat: MarqueeRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// 两遍之间的空白，默认等于区域宽度
+    pub fn gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn background(mut self, color: DisplayColor) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// 初始滚动距离，用来错开多行跑马灯
+    pub fn start_offset(mut self, pixels: u32) -> Self {
+        self.progress = pixels as u64 * 1000;
+        self
+    }
+
+    /// 替换文字并从头开始滚动
+    pub fn set_text(&mut self, text: &str) {
+        self.text.clear();
+        self.text.push_str(text);
+        self.progress = 0;
+
+        // 逐字测量一次，之后每帧只画可见的那几个字
+        self.char_x.clear();
+        let mut x = 0;
+        let mut buf = [0u8; 4];
+        for ch in self.text.chars() {
+            self.char_x.push(x);
+            let metrics =
+                self.style
+                    .measure_string(ch.encode_utf8(&mut buf), Point::zero(), Baseline::Top);
+            x += metrics.next_position.x;
+        }
+        self.char_x.push(x);
+    }
+
+    /// 文字总宽度（像素）
+    pub fn text_width(&self) -> u32 {
+        self.char_x.last().copied().unwrap_or(0) as u32
+    }
+
+    /// 推进时间
+    pub fn update(&mut self, elapsed_ms: u32) {
+        if self.is_finished() {
+            return;
+        }
+        self.progress += self.speed as u64 * elapsed_ms as u64;
+    }
+
+    /// 是否已经滚完（`Forever` 永远返回 false）
+    pub fn is_finished(&self) -> bool {
+        let offset = self.offset() as u64;
+        let period = self.period() as u64;
+        let single = (self.area.size.width + self.text_width()) as u64;
+        match self.repeat {
+            MarqueeRepeat::Once => offset >= single,
+            MarqueeRepeat::Times(n) => offset >= period * (n.max(1) as u64 - 1) + single,
+            MarqueeRepeat::Forever => false,
+        }
+    }
+
+    /// 绘制当前帧
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = DisplayColor>,
+    {
+        let _ = self.buffer.clear(self.background);
+
+        let width = self.area.size.width as i32;
+        let text_width = self.text_width() as i32;
+        let period = self.period() as i32;
+        let offset = self.offset() as i32;
+
+        // 第 j 遍比第 0 遍晚出发 j 个周期，滚过“区域宽度 + 文字宽度”才完全出去，
+        // 所以屏幕上是最近出发的这遍往前数 span / period 遍
+        let span = width + text_width;
+        let (offset, latest) = match self.repeat {
+            MarqueeRepeat::Forever => (offset % period, 0),
+            _ => (offset, offset / period),
+        };
+        let earliest = latest - span / period;
+        let (first, last) = match self.repeat {
+            MarqueeRepeat::Once => (earliest.max(0), latest.min(0)),
+            MarqueeRepeat::Times(n) => (earliest.max(0), latest.min(n.max(1) as i32 - 1)),
+            MarqueeRepeat::Forever => (earliest, latest),
+        };
+        for j in first..=last {
+            let pass = offset - j * period;
+            let x = match self.direction {
+                MarqueeDirection::Left => width - pass,
+                MarqueeDirection::Right => pass - text_width,
+            };
+            self.draw_text_at(x);
+        }
+
+        self.buffer.draw_at(target, self.area.top_left)
+    }
+
+    fn offset(&self) -> u32 {
+        (self.progress / 1000) as u32
+    }
+
+    fn period(&self) -> u32 {
+        (self.text_width() + self.gap).max(1)
+    }
+
+    // 只把落在缓冲区里的字画出来
+    fn draw_text_at(&mut self, x: i32) {
+        let width = self.area.size.width as i32;
+        let text_width = self.text_width() as i32;
+        if x >= width || x + text_width <= 0 {
+            return;
+        }
+
+        let first = match self.char_x.binary_search(&-x) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        let last = match self.char_x.binary_search(&(width - x)) {
+            Ok(i) => i,
+            Err(i) => i,
+        }
+        .min(self.char_x.len() - 1);
+
+        if first >= last {
+            return;
+        }
+
+        let start = self.byte_index(first);
+        let end = self.byte_index(last);
+        let _t = perf::scope("glyph");
+        let _ = self.style.draw_string(
+            &self.text[start..end],
+            Point::new(x + self.char_x[first], 0),
+            Baseline::Top,
+            &mut self.buffer,
+        );
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len())
+    }
+}
+
+/// 离屏行缓冲，坐标原点在缓冲区左上角，越界的像素直接丢掉
+pub(crate) struct LineBuffer {
+    size: Size,
+    pixels: Vec<DisplayColor>,
+}
+
+impl LineBuffer {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            pixels: vec![DisplayColor::BLACK; (size.width * size.height) as usize],
+        }
+    }
+
+    /// 把整块缓冲推到屏幕上，左上角放在 `top_left`
+    pub fn draw_at<D>(&self, target: &mut D, top_left: Point) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = DisplayColor>,
+    {
+        target.fill_contiguous(
+            &Rectangle::new(top_left, self.size),
+            self.pixels.iter().copied(),
+        )
+    }
+}
+
+impl DrawTarget for LineBuffer {
+    type Color = DisplayColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (w, h) = (self.size.width as i32, self.size.height as i32);
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 && point.x < w && point.y < h {
+                self.pixels[(point.y * w + point.x) as usize] = color;
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.pixels.fill(color);
+        Ok(())
+    }
+}
+
+impl OriginDimensions for LineBuffer {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
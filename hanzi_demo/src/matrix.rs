@@ -0,0 +1,319 @@
+//! 8x8 点阵模型
+//!
+//! 应用照常往一块逻辑画布上画（默认 16x16），[`LedMatrix`] 把画布按块缩小到 8x8：
+//! 一块里有四分之一以上的像素"亮"就点亮对应的灯。
+//! 真正点灯交给 [`MatrixDriver`]，板子上是 GPIO 逐行扫描（见 `led8x8`），测试里用 [`MemoryMatrix`]。
+//!
+//! 这里不碰硬件，不开 `need-ecos` 也能编译，主机上 `cargo test` 就能跑。
+
+use crate::*;
+
+use crate::geometry::{Geometry, Rotation};
+
+use embedded_graphics::prelude::*;
+
+/// 点阵行数/列数
+pub(crate) const MATRIX_SIZE: usize = 8;
+
+/// 一帧点阵数据，每行一个字节，最高位是最左边的灯
+pub(crate) type MatrixFrame = [u8; MATRIX_SIZE];
+
+/// 点阵驱动
+pub(crate) trait MatrixDriver {
+    /// 换一帧新的内容
+    fn show(&mut self, frame: &MatrixFrame);
+
+    /// 刷新扫描，需要动态扫描的驱动要尽量频繁地调用，自带锁存的驱动什么都不用做
+    fn refresh(&mut self) {}
+}
+
+// ===========================================
+// 逻辑画布 -> 8x8
+// ============================================
+
+/// 点阵绘制目标
+pub(crate) struct LedMatrix {
+    size: Size,
+    lit: Vec<bool>,
+}
+
+impl LedMatrix {
+    /// `size` 是逻辑画布大小，宽高都应该是 8 的整数倍
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            lit: vec![false; (size.width * size.height) as usize],
+        }
+    }
+
+    // 任一通道够亮就算亮，暗色背景算灭；各通道先按自己的最大值换算到 8 位
+    fn is_lit(color: DisplayColor) -> bool {
+        let level = |value: u8, max: u8| value as u16 * 255 / max as u16;
+        level(color.r(), DisplayColor::MAX_R)
+            .max(level(color.g(), DisplayColor::MAX_G))
+            .max(level(color.b(), DisplayColor::MAX_B))
+            >= 96
+    }
+
+    /// 读回画布上的一行，亮的点读成白色，灭的读成黑色
+    pub fn read_row(&self, y: u32, row: &mut [DisplayColor]) {
+        let start = (y * self.size.width) as usize;
+        let lit = &self.lit[start..start + self.size.width as usize];
+        for (color, &on) in row.iter_mut().zip(lit) {
+            *color = if on {
+                DisplayColor::WHITE
+            } else {
+                DisplayColor::BLACK
+            };
+        }
+    }
+
+    /// 把画布缩小成一帧点阵数据
+    pub fn frame(&self) -> MatrixFrame {
+        let block_w = (self.size.width as usize / MATRIX_SIZE).max(1);
+        let block_h = (self.size.height as usize / MATRIX_SIZE).max(1);
+        let threshold = (block_w * block_h).div_ceil(4);
+        let width = self.size.width as usize;
+
+        let mut frame = [0u8; MATRIX_SIZE];
+        for (row, bits) in frame.iter_mut().enumerate() {
+            for col in 0..MATRIX_SIZE {
+                let mut count = 0;
+                for y in row * block_h..(row + 1) * block_h {
+                    for x in col * block_w..(col + 1) * block_w {
+                        if self.lit.get(y * width + x).copied().unwrap_or(false) {
+                            count += 1;
+                        }
+                    }
+                }
+                if count >= threshold {
+                    *bits |= 0x80 >> col;
+                }
+            }
+        }
+        frame
+    }
+}
+
+impl DrawTarget for LedMatrix {
+    type Color = DisplayColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let (w, h) = (self.size.width as i32, self.size.height as i32);
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 && point.x < w && point.y < h {
+                self.lit[(point.y * w + point.x) as usize] = Self::is_lit(color);
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.lit.fill(Self::is_lit(color));
+        Ok(())
+    }
+}
+
+impl OriginDimensions for LedMatrix {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+/// 内存里的点阵模型，测试用
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryMatrix {
+    pub frame: MatrixFrame,
+    /// 收到过多少帧
+    pub frames: u32,
+}
+
+#[cfg(test)]
+impl MemoryMatrix {
+    /// 用 `#`/`.` 画出当前帧，一行一个字符串
+    pub fn to_ascii(&self) -> String {
+        let mut out = String::new();
+        for bits in self.frame {
+            for col in 0..MATRIX_SIZE {
+                out.push(if bits & (0x80 >> col) != 0 { '#' } else { '.' });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+impl MatrixDriver for MemoryMatrix {
+    fn show(&mut self, frame: &MatrixFrame) {
+        self.frame = *frame;
+        self.frames += 1;
+    }
+}
+
+/// 按安装方向把一帧顺时针旋转
+fn rotate_frame(frame: &MatrixFrame, rotation: Rotation) -> MatrixFrame {
+    const LAST: usize = MATRIX_SIZE - 1;
+    let mut rotated = [0u8; MATRIX_SIZE];
+    for (row, bits) in frame.iter().enumerate() {
+        for col in 0..MATRIX_SIZE {
+            if bits & (0x80 >> col) == 0 {
+                continue;
+            }
+            let (r, c) = match rotation {
+                Rotation::Deg0 => (row, col),
+                Rotation::Deg90 => (col, LAST - row),
+                Rotation::Deg180 => (LAST - row, LAST - col),
+                Rotation::Deg270 => (LAST - col, row),
+            };
+            rotated[r] |= 0x80 >> c;
+        }
+    }
+    rotated
+}
+
+/// 点阵显示管理器：画布 + 驱动
+pub(crate) struct LedMatrixManager<M: MatrixDriver> {
+    pub matrix: LedMatrix,
+    pub driver: M,
+    pub geometry: Geometry,
+}
+
+impl<M: MatrixDriver> LedMatrixManager<M> {
+    /// `geometry` 是逻辑画布的几何，旋转在送到点阵前做
+    pub fn new(geometry: Geometry, driver: M) -> Self {
+        Self {
+            matrix: LedMatrix::new(geometry.size()),
+            driver,
+            geometry,
+        }
+    }
+
+    /// 把画布内容送到点阵
+    pub fn flush(&mut self) {
+        let frame = rotate_frame(&self.matrix.frame(), self.geometry.rotation);
+        self.driver.show(&frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 一个 8x8 的箭头，每行一个字节
+    const ARROW: MatrixFrame = [0x18, 0x3C, 0x7E, 0xFF, 0x18, 0x18, 0x18, 0x18];
+
+    /// 把 8x8 的字形放大两倍画到 16x16 画布上，左上角在 (x, 0)
+    fn draw_glyph(matrix: &mut LedMatrix, glyph: &MatrixFrame, x: i32) {
+        matrix.clear(DisplayColor::BLACK).unwrap();
+        let pixels = glyph.iter().enumerate().flat_map(|(row, bits)| {
+            (0..MATRIX_SIZE)
+                .filter(move |col| bits & (0x80 >> col) != 0)
+                .flat_map(move |col| {
+                    let origin = Point::new(x + col as i32 * 2, row as i32 * 2);
+                    [(0, 0), (1, 0), (0, 1), (1, 1)]
+                        .map(|(dx, dy)| Pixel(origin + Point::new(dx, dy), DisplayColor::WHITE))
+                })
+        });
+        matrix.draw_iter(pixels).unwrap();
+    }
+
+    #[test]
+    fn glyph_maps_to_frame() {
+        let mut matrix = LedMatrix::new(Size::new(16, 16));
+        draw_glyph(&mut matrix, &ARROW, 0);
+        assert_eq!(matrix.frame(), ARROW);
+    }
+
+    #[test]
+    fn scrolling_glyph_shifts_rows() {
+        let mut matrix = LedMatrix::new(Size::new(16, 16));
+        for step in 0..=MATRIX_SIZE {
+            // 每步往左挪一盏灯（画布上两个像素）
+            draw_glyph(&mut matrix, &ARROW, -(step as i32) * 2);
+            let expected = ARROW.map(|bits| ((bits as u16) << step) as u8);
+            assert_eq!(matrix.frame(), expected, "step {step}");
+        }
+    }
+
+    #[test]
+    fn quarter_of_block_lights_led() {
+        let mut matrix = LedMatrix::new(Size::new(16, 16));
+        // 2x2 的块里亮一个像素就够四分之一
+        Pixel(Point::new(15, 15), DisplayColor::WHITE)
+            .draw(&mut matrix)
+            .unwrap();
+        // 太暗的颜色不算亮
+        Pixel(Point::new(0, 0), color::rgb(40, 40, 40))
+            .draw(&mut matrix)
+            .unwrap();
+        assert_eq!(matrix.frame(), [0, 0, 0, 0, 0, 0, 0, 0x01]);
+    }
+
+    #[test]
+    fn read_row_returns_lit_pixels() {
+        let mut matrix = LedMatrix::new(Size::new(16, 16));
+        draw_glyph(&mut matrix, &ARROW, 0);
+        let mut row = [DisplayColor::BLUE; 16];
+        matrix.read_row(0, &mut row);
+        // 第一行是 0x18，放大后第 6-9 列亮
+        for (x, color) in row.iter().enumerate() {
+            let expected = if (6..10).contains(&x) {
+                DisplayColor::WHITE
+            } else {
+                DisplayColor::BLACK
+            };
+            assert_eq!(*color, expected, "x {x}");
+        }
+    }
+
+    #[test]
+    fn rotate_frame_turns_clockwise() {
+        let corner: MatrixFrame = [0x80, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            rotate_frame(&corner, Rotation::Deg90),
+            [0x01, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            rotate_frame(&corner, Rotation::Deg180),
+            [0, 0, 0, 0, 0, 0, 0, 0x01]
+        );
+        assert_eq!(
+            rotate_frame(&corner, Rotation::Deg270),
+            [0, 0, 0, 0, 0, 0, 0, 0x80]
+        );
+
+        let mut frame = ARROW;
+        for _ in 0..4 {
+            frame = rotate_frame(&frame, Rotation::Deg90);
+        }
+        assert_eq!(frame, ARROW);
+    }
+
+    #[test]
+    fn manager_flushes_rotated_frame() {
+        let geometry = Geometry::new(16, 16).rotation(Rotation::Deg180);
+        let mut manager = LedMatrixManager::new(geometry, MemoryMatrix::default());
+        draw_glyph(&mut manager.matrix, &ARROW, 0);
+        manager.flush();
+        // 自带锁存的驱动刷新什么都不用做
+        manager.driver.refresh();
+        assert_eq!(manager.driver.frames, 1);
+        assert_eq!(
+            manager.driver.to_ascii(),
+            "...##...\n\
+             ...##...\n\
+             ...##...\n\
+             ...##...\n\
+             ########\n\
+             .######.\n\
+             ..####..\n\
+             ...##...\n"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! 性能计时
+//!
+//! 在想知道耗时的地方放一个 `let _t = perf::scope("名字");`，离开作用域时把这段时间记到同名的条目里。
+//! `perf` 命令列出每个条目的次数和最短、平均、最长耗时，`perf reset` 清零。
+//!
+//! 板子上读 RISC-V 的 `cycle` 计数器（`rdcycle`），单位是 CPU 周期；模拟器用 `Instant`，单位是纳秒。
+//! 每次计时只是两次读计数器加一次按名字查表，热路径上也可以放。
+
+#![allow(static_mut_refs)]
+
+#[allow(unused)]
+use crate::*;
+
+use crate::i18n::{self, tr};
+
+use embedded_cli::Command;
+
+/// 最多记多少个条目，满了新名字就不记了
+const MAX_ENTRIES: usize = 16;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    name: &'static str,
+    count: u32,
+    total: u64,
+    min: u64,
+    max: u64,
+}
+
+// 计时点都在演示里，一个演示都没开时这些用不上
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+impl Entry {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            count: 0,
+            total: 0,
+            min: u64::MAX,
+            max: 0,
+        }
+    }
+
+    fn add(&mut self, elapsed: u64) {
+        self.count = self.count.saturating_add(1);
+        self.total = self.total.saturating_add(elapsed);
+        self.min = self.min.min(elapsed);
+        self.max = self.max.max(elapsed);
+    }
+}
+
+// 只在主循环里用，没有并发访问
+static mut ENTRIES: [Option<Entry>; MAX_ENTRIES] = [None; MAX_ENTRIES];
+
+/// 计时守卫，丢掉时记一笔
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+pub(crate) struct Scope {
+    name: &'static str,
+    start: u64,
+}
+
+/// 从现在开始计时，返回的守卫离开作用域时记到 `name` 下
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+pub(crate) fn scope(name: &'static str) -> Scope {
+    Scope { name, start: now() }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        record(self.name, now().wrapping_sub(self.start));
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+fn record(name: &'static str, elapsed: u64) {
+    let entries = unsafe { &mut ENTRIES };
+    for slot in entries.iter_mut() {
+        match slot {
+            Some(entry) if entry.name == name => {
+                entry.add(elapsed);
+                return;
+            }
+            Some(_) => {}
+            None => {
+                let mut entry = Entry::new(name);
+                entry.add(elapsed);
+                *slot = Some(entry);
+                return;
+            }
+        }
+    }
+}
+
+fn reset() {
+    unsafe {
+        ENTRIES = [None; MAX_ENTRIES];
+    }
+}
+
+/// 周期计数器，rv32 上高低两半分开读，读的中间低位进位了就重读
+#[cfg(all(feature = "need-ecos", target_arch = "riscv32"))]
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+fn now() -> u64 {
+    loop {
+        let hi: u32;
+        let lo: u32;
+        let hi2: u32;
+        unsafe {
+            core::arch::asm!(
+                "rdcycleh {0}",
+                "rdcycle {1}",
+                "rdcycleh {2}",
+                out(reg) hi,
+                out(reg) lo,
+                out(reg) hi2,
+            );
+        }
+        if hi == hi2 {
+            return ((hi as u64) << 32) | lo as u64;
+        }
+    }
+}
+
+/// 不是 RISC-V 时没有周期计数器，退到毫秒，只为在别的架构上也能编译检查
+#[cfg(all(feature = "need-ecos", not(target_arch = "riscv32")))]
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+fn now() -> u64 {
+    ecos_ssc1::Timer::get_tick() as u64
+}
+
+/// 模拟器上的纳秒数，从第一次调用算起
+#[cfg(not(feature = "need-ecos"))]
+#[cfg_attr(
+    not(any(feature = "cmd-font", feature = "cmd-text", feature = "cmd-snake")),
+    allow(dead_code)
+)]
+fn now() -> u64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+/// 计时的单位
+fn unit() -> &'static str {
+    if cfg!(feature = "need-ecos") {
+        tr!(i18n::PERF_UNIT_CYCLES)
+    } else {
+        "ns"
+    }
+}
+
+#[derive(Command, Debug)]
+pub(crate) enum PerfCommand<'a> {
+    /// 查看各段代码的耗时（次数、最短、平均、最长），`perf reset` 清零
+    #[command(name = "perf")]
+    Perf {
+        /// reset 清零，不填查看
+        action: Option<&'a str>,
+    },
+}
+
+pub(crate) fn handle_perf_command(
+    command: PerfCommand<'_>,
+) -> Result<(), core::convert::Infallible> {
+    match command {
+        PerfCommand::Perf { action: None } => {
+            let entries = unsafe { &ENTRIES };
+            if entries.iter().all(Option::is_none) {
+                println!("\r\n{}\r\n", tr!(i18n::PERF_EMPTY));
+                return Ok(());
+            }
+            println!("\r\n{}\r", tr!(i18n::PERF_UNIT, unit()));
+            for entry in entries.iter().flatten() {
+                let avg = entry.total / entry.count.max(1) as u64;
+                println!(
+                    "{}\r",
+                    tr!(
+                        i18n::PERF_ROW,
+                        entry.name,
+                        entry.count,
+                        entry.min,
+                        avg,
+                        entry.max
+                    )
+                );
+            }
+            println!("\r");
+        }
+        PerfCommand::Perf {
+            action: Some("reset"),
+        } => {
+            reset();
+            println!("\r\n{}\r\n", tr!(i18n::PERF_RESET));
+        }
+        PerfCommand::Perf {
+            action: Some(other),
+        } => println!("\r\n{}\r\n", tr!(i18n::PERF_BAD_ACTION, other)),
+    }
+    Ok(())
+}
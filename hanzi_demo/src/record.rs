@@ -0,0 +1,110 @@
+//! 模拟器录屏
+//!
+//! `record start` 之后每次 `present` 都把整屏交给 [`Recorder`]，和上一帧一样的直接丢掉，
+//! `record stop` 时写完 GIF。帧间隔按真实时间算，贪吃蛇和字体动画录出来速度不变。
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use embedded_graphics::prelude::*;
+
+/// GIF 的帧间隔以 10ms 为单位，小于 20ms 的浏览器会当成 100ms 放，太密的帧只留最后一张
+const MIN_DELAY_MS: u32 = 20;
+
+/// 颜色量化的速度，1 最慢最准，30 最快，10 对这些纯色界面已经足够
+const QUANTIZE_SPEED: i32 = 10;
+
+/// GIF 录像
+pub(crate) struct Recorder {
+    encoder: gif::Encoder<BufWriter<File>>,
+    size: Size,
+    /// 还没写出去的一帧和它开始显示的时间，要等下一帧来了才知道它显示了多久
+    pending: Option<(Vec<u8>, u32)>,
+    frames: u32,
+}
+
+impl Recorder {
+    /// 创建 `path`，开始录制 `size` 大小的画面
+    pub fn create(path: &str, size: Size) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| tr!(i18n::RECORD_CREATE_FAILED, path, e))?;
+        let mut encoder = gif::Encoder::new(
+            BufWriter::new(file),
+            size.width as u16,
+            size.height as u16,
+            &[],
+        )
+        .map_err(|e| e.to_string())?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            encoder,
+            size,
+            pending: None,
+            frames: 0,
+        })
+    }
+
+    /// 记一帧，`rgb` 是每像素 3 字节的整屏数据
+    pub fn frame(&mut self, rgb: Vec<u8>, now: u32) -> Result<(), String> {
+        match self.pending.take() {
+            None => self.pending = Some((rgb, now)),
+            // 画面没变，上一帧接着显示
+            Some((last, start)) if last == rgb => self.pending = Some((last, start)),
+            // 上一帧显示得太短，直接被这一帧顶掉
+            Some((_, start)) if now.wrapping_sub(start) < MIN_DELAY_MS => {
+                self.pending = Some((rgb, start))
+            }
+            Some((last, start)) => {
+                self.write(&last, now.wrapping_sub(start))?;
+                self.pending = Some((rgb, now));
+            }
+        }
+        Ok(())
+    }
+
+    /// 写出最后一帧并关闭文件，返回一共写了多少帧
+    pub fn finish(mut self, now: u32) -> Result<u32, String> {
+        if let Some((last, start)) = self.pending.take() {
+            let shown = now.wrapping_sub(start).max(MIN_DELAY_MS);
+            self.write(&last, shown)?;
+        }
+        let mut file = self.encoder.into_inner().map_err(|e| e.to_string())?;
+        file.flush().map_err(|e| e.to_string())?;
+        Ok(self.frames)
+    }
+
+    fn write(&mut self, rgb: &[u8], shown_ms: u32) -> Result<(), String> {
+        let mut frame = gif::Frame::from_rgb_speed(
+            self.size.width as u16,
+            self.size.height as u16,
+            rgb,
+            QUANTIZE_SPEED,
+        );
+        frame.delay = (shown_ms / 10).min(u16::MAX as u32) as u16;
+        self.encoder
+            .write_frame(&frame)
+            .map_err(|e| e.to_string())?;
+        self.frames += 1;
+        Ok(())
+    }
+}
+
+/// 把模拟器屏幕读成每像素 3 字节的 RGB
+pub(crate) fn capture<B: DisplayBackend>(backend: &B) -> Vec<u8> {
+    let size = backend.size();
+    let mut row = vec![DisplayColor::BLACK; size.width as usize];
+    let mut rgb = Vec::with_capacity((size.width * size.height * 3) as usize);
+    for y in 0..size.height {
+        backend.read_row(y, &mut row);
+        for color in row.iter() {
+            rgb.extend_from_slice(&[color.r(), color.g(), color.b()]);
+        }
+    }
+    rgb
+}
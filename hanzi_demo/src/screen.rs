@@ -0,0 +1,184 @@
+//! 屏幕相关的命令
+//!
+//! 和具体演示无关、直接操作显示后端的命令都放在这里。
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+use crate::idle::IdleDimmer;
+use crate::settings::{self, Key};
+use crate::theme;
+
+use embedded_cli::Command;
+
+/// `record start` 不给文件名时写到这里
+const DEFAULT_RECORD_PATH: &str = "record.gif";
+
+#[derive(Command, Debug)]
+pub(crate) enum ScreenCommand<'a> {
+    /// 显示刷新帧率，`fps on`/`fps off` 开关每秒打印
+    #[command(name = "fps")]
+    Fps {
+        /// on 或 off，不填只打印一次
+        state: Option<&'a str>,
+    },
+
+    /// 查看或设置背光亮度（0-100）
+    #[command(name = "brightness")]
+    Brightness {
+        /// 亮度百分比，不填只打印当前亮度
+        level: Option<u8>,
+    },
+
+    /// 查看或设置闲置多少秒后变暗、关背光，0 表示不变暗/不关
+    #[command(name = "idle")]
+    Idle {
+        /// 变暗前的闲置秒数
+        dim: Option<u32>,
+        /// 关背光前的闲置秒数
+        sleep: Option<u32>,
+    },
+
+    /// 查看或切换配色主题
+    #[command(name = "theme")]
+    Theme {
+        /// 主题名，不填列出所有主题
+        name: Option<&'a str>,
+    },
+
+    /// 把当前屏幕内容从串口发出去，主机上用 shot2png 转成图片
+    #[command(name = "screenshot")]
+    Screenshot,
+
+    /// 录屏（模拟器），`record start [文件名]` 开始，`record stop` 结束并写出 GIF
+    #[command(name = "record")]
+    Record {
+        /// start 或 stop
+        action: &'a str,
+        /// 输出文件，默认 record.gif
+        path: Option<&'a str>,
+    },
+}
+
+pub(crate) fn handle_screen_command(
+    manager: &mut DisplayManager,
+    idle: &mut IdleDimmer,
+    command: ScreenCommand<'_>,
+) -> Result<(), core::convert::Infallible> {
+    match command {
+        ScreenCommand::Fps { state } => {
+            let on = match state {
+                None => {
+                    match manager.frame_stats() {
+                        Some(stats) => println!("\r\n{}\r\n", tr!(i18n::FPS_LAST_SECOND, stats)),
+                        None => println!("\r\n{}\r\n", tr!(i18n::FPS_UNSUPPORTED)),
+                    }
+                    return Ok(());
+                }
+                Some("on") => true,
+                Some("off") => false,
+                Some(other) => {
+                    println!("\r\n{}\r\n", tr!(i18n::BAD_ON_OFF, other));
+                    return Ok(());
+                }
+            };
+
+            if manager.set_fps_report(on) {
+                let msg = if on {
+                    i18n::FPS_REPORT_ON
+                } else {
+                    i18n::FPS_REPORT_OFF
+                };
+                println!("\r\n{}\r\n", tr!(msg));
+            } else {
+                println!("\r\n{}\r\n", tr!(i18n::FPS_UNSUPPORTED));
+            }
+            Ok(())
+        }
+        ScreenCommand::Brightness { level } => {
+            match level {
+                None => match manager.brightness() {
+                    Some(level) => println!("\r\n{}\r\n", tr!(i18n::BRIGHTNESS_NOW, level)),
+                    None => println!("\r\n{}\r\n", tr!(i18n::BRIGHTNESS_UNSUPPORTED)),
+                },
+                Some(level) if level > 100 => println!("\r\n{}\r\n", tr!(i18n::BRIGHTNESS_RANGE)),
+                Some(level) => {
+                    if manager.set_brightness(level) {
+                        remember(Key::Brightness, level as u32);
+                        println!("\r\n{}\r\n", tr!(i18n::BRIGHTNESS_SET, level));
+                    } else {
+                        println!("\r\n{}\r\n", tr!(i18n::BRIGHTNESS_UNSUPPORTED));
+                    }
+                }
+            }
+            Ok(())
+        }
+        ScreenCommand::Idle { dim, sleep } => {
+            if let Some(dim) = dim {
+                idle.dim_after_ms = dim.saturating_mul(1000);
+                remember(Key::IdleDim, dim);
+            }
+            if let Some(sleep) = sleep {
+                idle.sleep_after_ms = sleep.saturating_mul(1000);
+                remember(Key::IdleSleep, sleep);
+            }
+            println!(
+                "\r\n{}\r\n",
+                tr!(
+                    i18n::IDLE_STATUS,
+                    idle.dim_after_ms / 1000,
+                    idle.sleep_after_ms / 1000
+                )
+            );
+            Ok(())
+        }
+        ScreenCommand::Theme { name: None } => {
+            println!("\r\n{}\r", tr!(i18n::THEME_LIST));
+            let current = theme::current().name;
+            for t in theme::THEMES {
+                let mark = if t.name == current { '*' } else { ' ' };
+                println!("  {} {}\r", mark, t.name);
+            }
+            println!("\r");
+            Ok(())
+        }
+        ScreenCommand::Theme { name: Some(name) } => {
+            if theme::set(name) {
+                let _ = settings::set_text(Key::Theme, name).and_then(|_| settings::save());
+                println!("\r\n{}\r\n", tr!(i18n::THEME_SET, name));
+            } else {
+                println!("\r\n{}\r\n", tr!(i18n::THEME_UNKNOWN, name));
+            }
+            Ok(())
+        }
+        ScreenCommand::Screenshot => {
+            println!("\r\n");
+            if !screenshot::send(manager) {
+                println!("{}\r\n", tr!(i18n::SCREENSHOT_UNSUPPORTED));
+            }
+            Ok(())
+        }
+        ScreenCommand::Record { action, path } => {
+            match action {
+                "start" => {
+                    let path = path.unwrap_or(DEFAULT_RECORD_PATH);
+                    match manager.start_recording(path) {
+                        Ok(()) => println!("\r\n{}\r\n", tr!(i18n::RECORD_STARTED, path)),
+                        Err(e) => println!("\r\n{}\r\n", e),
+                    }
+                }
+                "stop" => match manager.stop_recording() {
+                    Ok(frames) => println!("\r\n{}\r\n", tr!(i18n::RECORD_STOPPED, frames)),
+                    Err(e) => println!("\r\n{}\r\n", e),
+                },
+                other => println!("\r\n{}\r\n", tr!(i18n::BAD_START_STOP, other)),
+            }
+            Ok(())
+        }
+    }
+}
+
+// 顺手记到设置里，下次开机还是这个值；存不下（比如板子上）也不影响这次
+fn remember(key: Key, value: u32) {
+    let _ = settings::set_number(key, value).and_then(|_| settings::save());
+}
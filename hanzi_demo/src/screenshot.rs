@@ -0,0 +1,152 @@
+//! 截图：把屏幕内容从串口发出去
+//!
+//! 串口和命令行共用，所以全部用可打印字符，一行一帧，方便从日志里捞出来：
+//!
+//! ```text
+//! @@SHOT 1 <宽> <高> <像素格式> <总字节数>
+//! @@D <序号> <base64 数据> <crc32>
+//! ...
+//! @@END <块数> <整张图的 crc32>
+//! ```
+//!
+//! 除了最后一块，每块都是 48 字节。像素按行从上到下、每行从左到右排列，每个像素按大端字节序写，
+//! 像素格式是 `rgb565`（2 字节）或 `rgb888`（3 字节）。
+//! crc32 是 IEEE 802.3 那种，用 8 位十六进制写。主机上用 `hanzi_tools` 里的 `shot2png` 转成 PNG。
+
+use crate::*;
+
+use embedded_graphics::{pixelcolor::raw::ToBytes, prelude::*};
+
+/// 协议版本，格式有不兼容的改动时加一
+const VERSION: u32 = 1;
+
+/// 每行数据带多少字节，base64 后正好 64 个字符
+const CHUNK_BYTES: usize = 48;
+
+/// 把当前屏幕内容发到串口，后端读不回像素时返回 false
+pub(crate) fn send<B: DisplayBackend>(backend: &B) -> bool {
+    let size = backend.size();
+    let mut row = vec![DisplayColor::BLACK; size.width as usize];
+    if !backend.read_row(0, &mut row) {
+        return false;
+    }
+
+    let bytes_per_pixel = DisplayColor::BLACK.to_be_bytes().as_ref().len();
+    let total = size.width as usize * size.height as usize * bytes_per_pixel;
+    println!(
+        "@@SHOT {} {} {} {} {}",
+        VERSION,
+        size.width,
+        size.height,
+        color::PIXEL_FORMAT,
+        total
+    );
+
+    let mut writer = ChunkWriter::new();
+    for y in 0..size.height {
+        if y > 0 {
+            backend.read_row(y, &mut row);
+        }
+        for color in row.iter() {
+            writer.push(color.to_be_bytes().as_ref());
+        }
+    }
+    writer.finish();
+
+    true
+}
+
+// 攒满一块就发一行
+struct ChunkWriter {
+    chunk: [u8; CHUNK_BYTES],
+    len: usize,
+    seq: u32,
+    total_crc: Crc32,
+}
+
+impl ChunkWriter {
+    fn new() -> Self {
+        Self {
+            chunk: [0; CHUNK_BYTES],
+            len: 0,
+            seq: 0,
+            total_crc: Crc32::new(),
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.chunk[self.len] = byte;
+            self.len += 1;
+            if self.len == CHUNK_BYTES {
+                self.flush();
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+        let data = &self.chunk[..self.len];
+        self.total_crc.update(data);
+
+        let mut crc = Crc32::new();
+        crc.update(data);
+        println!("@@D {} {} {:08x}", self.seq, base64(data), crc.value());
+
+        self.seq += 1;
+        self.len = 0;
+    }
+
+    fn finish(mut self) {
+        self.flush();
+        println!("@@END {} {:08x}", self.seq, self.total_crc.value());
+    }
+}
+
+/// CRC-32（IEEE 802.3），按位算，不占查表的 1KB；设置存储也用它
+pub(crate) struct Crc32(u32);
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 ^= byte as u32;
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        !self.0
+    }
+}
+
+// 标准 base64，带 `=` 填充
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for group in data.chunks(3) {
+        let b = [
+            group[0],
+            group.get(1).copied().unwrap_or(0),
+            group.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= group.len() {
+                out.push(TABLE[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
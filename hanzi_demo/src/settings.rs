@@ -0,0 +1,791 @@
+//! 设置
+//!
+//! 提示符颜色、阅读器字号、贪吃蛇速度和最高分、上次看的文档、主题、亮度、界面语言、日志级别、屏幕几何这些重启后还想留着的东西都在这里。
+//! 每一项有固定的键名、类型、取值范围和默认值，没设过的就是默认值；`set`/`get`/`settings` 命令查看和修改。
+//!
+//! 模拟器上存成文件，板子上存在 flash 最后一个扇区（怎么擦写见板子的 `storage`），改了马上写回。
+//!
+//! 存储格式（整数都是小端）：
+//!
+//! ```text
+//! "HZST" 版本(1) 项数(1) { 键号(1) 类型(1) 长度(1) 数据 }... crc32(4)
+//! ```
+//!
+//! 类型 0 是 4 字节整数，1 是 UTF-8 文本。键号定了就不再改，读到不认识的键号跳过，
+//! 值不合法的那项用默认值，版本比固件新或者 crc 不对就全部用默认值。
+//! crc32 和截图用的是同一个。
+
+#![allow(static_mut_refs)]
+
+use crate::*;
+
+use crate::backend;
+use crate::i18n::{self, tr};
+use crate::idle::IdleDimmer;
+use crate::logging;
+use crate::screenshot::Crc32;
+use crate::theme;
+
+use embedded_cli::Command;
+
+/// 格式有不兼容的改动时加一
+const VERSION: u8 = 1;
+
+const MAGIC: &[u8; 4] = b"HZST";
+
+const TYPE_NUMBER: u8 = 0;
+const TYPE_TEXT: u8 = 1;
+
+/// 一项设置
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Key {
+    Theme,
+    Brightness,
+    IdleDim,
+    IdleSleep,
+    PromptUser,
+    PromptHost,
+    FontSize,
+    SnakeSpeed,
+    SnakeHighScore,
+    LastDocument,
+    Language,
+    LogLevel,
+    PanicRestart,
+    Display,
+}
+
+/// 取值的类型和范围
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Number {
+        min: u32,
+        max: u32,
+        default: u32,
+    },
+    /// 终端颜色代码，30-37 或 90-97
+    Ansi {
+        default: u32,
+    },
+    Text {
+        max_len: usize,
+        default: &'static str,
+    },
+}
+
+const KEY_COUNT: usize = 14;
+
+impl Key {
+    pub const ALL: [Key; KEY_COUNT] = [
+        Key::Theme,
+        Key::Brightness,
+        Key::IdleDim,
+        Key::IdleSleep,
+        Key::PromptUser,
+        Key::PromptHost,
+        Key::FontSize,
+        Key::SnakeSpeed,
+        Key::SnakeHighScore,
+        Key::LastDocument,
+        Key::Language,
+        Key::LogLevel,
+        Key::PanicRestart,
+        Key::Display,
+    ];
+
+    /// 存储里的键号，定了就不能改
+    fn id(self) -> u8 {
+        match self {
+            Key::Theme => 1,
+            Key::Brightness => 2,
+            Key::IdleDim => 3,
+            Key::IdleSleep => 4,
+            Key::PromptUser => 5,
+            Key::PromptHost => 6,
+            Key::FontSize => 7,
+            Key::SnakeSpeed => 8,
+            Key::SnakeHighScore => 9,
+            Key::LastDocument => 10,
+            Key::Language => 11,
+            Key::LogLevel => 12,
+            Key::PanicRestart => 13,
+            Key::Display => 14,
+        }
+    }
+
+    /// 命令里用的名字
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::Theme => "theme",
+            Key::Brightness => "brightness",
+            Key::IdleDim => "idle.dim",
+            Key::IdleSleep => "idle.sleep",
+            Key::PromptUser => "prompt.user",
+            Key::PromptHost => "prompt.host",
+            Key::FontSize => "font.size",
+            Key::SnakeSpeed => "snake.speed",
+            Key::SnakeHighScore => "snake.high",
+            Key::LastDocument => "reader.last",
+            Key::Language => "lang",
+            Key::LogLevel => "log.level",
+            Key::PanicRestart => "panic.restart",
+            Key::Display => "display",
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            Key::Theme => tr!(i18n::KEY_THEME),
+            Key::Brightness => tr!(i18n::KEY_BRIGHTNESS),
+            Key::IdleDim => tr!(i18n::KEY_IDLE_DIM),
+            Key::IdleSleep => tr!(i18n::KEY_IDLE_SLEEP),
+            Key::PromptUser => tr!(i18n::KEY_PROMPT_USER),
+            Key::PromptHost => tr!(i18n::KEY_PROMPT_HOST),
+            Key::FontSize => tr!(i18n::KEY_FONT_SIZE),
+            Key::SnakeSpeed => tr!(i18n::KEY_SNAKE_SPEED),
+            Key::SnakeHighScore => tr!(i18n::KEY_SNAKE_HIGH),
+            Key::LastDocument => tr!(i18n::KEY_LAST_DOCUMENT),
+            Key::Language => tr!(i18n::KEY_LANGUAGE),
+            Key::LogLevel => tr!(i18n::KEY_LOG_LEVEL),
+            Key::PanicRestart => tr!(i18n::KEY_PANIC_RESTART),
+            Key::Display => tr!(i18n::KEY_DISPLAY),
+        }
+    }
+
+    fn kind(self) -> Kind {
+        match self {
+            Key::Theme => Kind::Text {
+                max_len: 16,
+                default: "dark",
+            },
+            Key::Brightness => Kind::Number {
+                min: 0,
+                max: 100,
+                default: 100,
+            },
+            Key::IdleDim => Kind::Number {
+                min: 0,
+                max: 86_400,
+                default: 60,
+            },
+            Key::IdleSleep => Kind::Number {
+                min: 0,
+                max: 86_400,
+                default: 300,
+            },
+            Key::PromptUser => Kind::Ansi { default: 92 },
+            Key::PromptHost => Kind::Ansi { default: 94 },
+            Key::FontSize => Kind::Number {
+                min: 12,
+                max: 48,
+                default: 24,
+            },
+            Key::SnakeSpeed => Kind::Number {
+                min: 1,
+                max: 10,
+                default: 3,
+            },
+            Key::SnakeHighScore => Kind::Number {
+                min: 0,
+                max: u32::MAX,
+                default: 0,
+            },
+            Key::LastDocument => Kind::Text {
+                max_len: 32,
+                default: "demo.txt",
+            },
+            Key::Language => Kind::Text {
+                max_len: 8,
+                default: i18n::DEFAULT.code(),
+            },
+            Key::LogLevel => Kind::Text {
+                max_len: 5,
+                default: "info",
+            },
+            Key::PanicRestart => Kind::Number {
+                min: 0,
+                max: 1,
+                default: 1,
+            },
+            Key::Display => Kind::Text {
+                max_len: 16,
+                default: backend::DEFAULT_GEOMETRY,
+            },
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Key> {
+        Key::ALL.into_iter().find(|key| key.name() == name)
+    }
+
+    fn from_id(id: u8) -> Option<Key> {
+        Key::ALL.into_iter().find(|key| key.id() == id)
+    }
+
+    fn index(self) -> usize {
+        Key::ALL.iter().position(|&key| key == self).unwrap_or(0)
+    }
+
+    fn default_value(self) -> Value {
+        match self.kind() {
+            Kind::Number { default, .. } | Kind::Ansi { default } => Value::Number(default),
+            Kind::Text { default, .. } => Value::Text(default.to_string()),
+        }
+    }
+
+    /// 检查值的类型和范围
+    fn check(self, value: &Value) -> Result<(), String> {
+        match (self.kind(), value) {
+            (Kind::Number { min, max, .. }, Value::Number(n)) => {
+                if (min..=max).contains(n) {
+                    Ok(())
+                } else {
+                    Err(tr!(i18n::SETTINGS_RANGE, self.name(), min, max))
+                }
+            }
+            (Kind::Ansi { .. }, Value::Number(n)) => {
+                if (30..=37).contains(n) || (90..=97).contains(n) {
+                    Ok(())
+                } else {
+                    Err(tr!(i18n::SETTINGS_ANSI, self.name()))
+                }
+            }
+            (Kind::Text { max_len, .. }, Value::Text(text)) => {
+                if text.is_empty() || text.len() > max_len {
+                    Err(tr!(i18n::SETTINGS_LENGTH, self.name(), max_len))
+                } else if self == Key::Theme && !theme::THEMES.iter().any(|t| t.name == text) {
+                    Err(tr!(i18n::SETTINGS_NO_THEME, text))
+                } else if self == Key::Language && i18n::Lang::from_code(text).is_none() {
+                    Err(tr!(i18n::SETTINGS_NO_LANG, text))
+                } else if self == Key::LogLevel && logging::parse_level(text).is_none() {
+                    Err(tr!(i18n::LOG_LEVEL_UNKNOWN, text))
+                } else if self == Key::Display && backend::parse_geometry(text).is_none() {
+                    Err(tr!(i18n::SETTINGS_BAD_GEOMETRY, text))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Err(tr!(i18n::SETTINGS_WRONG_TYPE, self.name())),
+        }
+    }
+
+    /// 把命令行上的文字转成这一项的值
+    fn parse(self, text: &str) -> Result<Value, String> {
+        let value = match self.kind() {
+            Kind::Number { .. } | Kind::Ansi { .. } => match text.parse() {
+                Ok(n) => Value::Number(n),
+                Err(_) => return Err(tr!(i18n::SETTINGS_NOT_NUMBER, self.name())),
+            },
+            Kind::Text { .. } => Value::Text(text.to_string()),
+        };
+        self.check(&value)?;
+        Ok(value)
+    }
+}
+
+/// 设置的值
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Value {
+    Number(u32),
+    Text(String),
+}
+
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+// 设过的值，None 就是默认值
+static mut VALUES: [Option<Value>; KEY_COUNT] = [const { None }; KEY_COUNT];
+
+fn values() -> &'static mut [Option<Value>; KEY_COUNT] {
+    unsafe { &mut VALUES }
+}
+
+/// 当前值
+pub(crate) fn get(key: Key) -> Value {
+    values()[key.index()]
+        .clone()
+        .unwrap_or_else(|| key.default_value())
+}
+
+/// 整数项的值
+pub(crate) fn number(key: Key) -> u32 {
+    match get(key) {
+        Value::Number(n) => n,
+        Value::Text(_) => 0,
+    }
+}
+
+/// 文本项的值
+pub(crate) fn text(key: Key) -> String {
+    get(key).to_string()
+}
+
+/// 改整数项，只改内存，要留住得再 [`save`]
+pub(crate) fn set_number(key: Key, value: u32) -> Result<(), String> {
+    set(key, Value::Number(value))
+}
+
+/// 改文本项，只改内存，要留住得再 [`save`]
+pub(crate) fn set_text(key: Key, value: &str) -> Result<(), String> {
+    set(key, Value::Text(value.to_string()))
+}
+
+fn set(key: Key, value: Value) -> Result<(), String> {
+    key.check(&value)?;
+    values()[key.index()] = (value != key.default_value()).then_some(value);
+    Ok(())
+}
+
+/// 是不是还是默认值
+fn is_default(key: Key) -> bool {
+    values()[key.index()].is_none()
+}
+
+/// 全部恢复默认值（只改内存）
+fn reset() {
+    for value in values().iter_mut() {
+        *value = None;
+    }
+}
+
+/// 开机时从存储读一次
+pub(crate) fn load() {
+    reset();
+    let Some(data) = storage::load() else {
+        log::info!("{}", tr!(i18n::SETTINGS_NONE));
+        return;
+    };
+    match decode(&data) {
+        Ok(count) => {
+            // 语言和日志级别最先生效，后面开机的提示就按选好的来
+            i18n::set(&text(Key::Language));
+            logging::set_level(&text(Key::LogLevel));
+            log::info!("{}", tr!(i18n::SETTINGS_LOADED, count, storage::location()));
+        }
+        Err(e) => {
+            reset();
+            log::warn!("{}", tr!(i18n::SETTINGS_LOAD_FAILED, e));
+        }
+    }
+}
+
+/// 把设过的值写到存储
+pub(crate) fn save() -> Result<(), String> {
+    storage::save(&encode())
+}
+
+/// 按设置调整主题、亮度、闲置时间、语言和日志级别，开机和 `settings reset` 时调用
+pub(crate) fn apply(manager: &mut DisplayManager, idle: &mut IdleDimmer) {
+    for key in [
+        Key::Theme,
+        Key::Brightness,
+        Key::IdleDim,
+        Key::IdleSleep,
+        Key::Language,
+        Key::LogLevel,
+    ] {
+        apply_key(manager, idle, key);
+    }
+}
+
+fn apply_key(manager: &mut DisplayManager, idle: &mut IdleDimmer, key: Key) {
+    match key {
+        Key::Theme => {
+            theme::set(&text(key));
+        }
+        Key::Brightness => {
+            manager.set_brightness(number(key) as u8);
+        }
+        Key::IdleDim => idle.dim_after_ms = number(key).saturating_mul(1000),
+        Key::IdleSleep => idle.sleep_after_ms = number(key).saturating_mul(1000),
+        Key::Language => {
+            i18n::set(&text(key));
+        }
+        Key::LogLevel => {
+            logging::set_level(&text(key));
+        }
+        // 屏幕开机时按几何初始化，换不了
+        Key::Display => println!("\r\n{}\r", tr!(i18n::SETTINGS_DISPLAY_RESTART)),
+        _ => {}
+    }
+}
+
+/// 按设置的颜色拼出命令行提示符
+///
+/// 命令行只收 `&'static str`，所以每次都漏掉一份，只在开机和改颜色时调用。
+pub(crate) fn prompt() -> &'static str {
+    format!(
+        "\u{001b}[{}mheke1228\u{001b}[0m@\u{001b}[{}mecos-ssc1\u{001b}[0m$ ",
+        number(Key::PromptUser),
+        number(Key::PromptHost)
+    )
+    .leak()
+}
+
+fn encode() -> Vec<u8> {
+    let mut data = Vec::with_capacity(64);
+    data.extend_from_slice(MAGIC);
+    data.push(VERSION);
+    data.push(0);
+
+    let mut count = 0;
+    for key in Key::ALL {
+        let Some(value) = &values()[key.index()] else {
+            continue;
+        };
+        data.push(key.id());
+        match value {
+            Value::Number(n) => {
+                data.push(TYPE_NUMBER);
+                data.push(4);
+                data.extend_from_slice(&n.to_le_bytes());
+            }
+            Value::Text(text) => {
+                data.push(TYPE_TEXT);
+                data.push(text.len() as u8);
+                data.extend_from_slice(text.as_bytes());
+            }
+        }
+        count += 1;
+    }
+    data[5] = count;
+
+    let mut crc = Crc32::new();
+    crc.update(&data);
+    data.extend_from_slice(&crc.value().to_le_bytes());
+    data
+}
+
+/// 解出存储里的值，返回读到几项
+fn decode(data: &[u8]) -> Result<usize, String> {
+    if data.len() < 10 || &data[..4] != MAGIC {
+        return Err(tr!(i18n::SETTINGS_NOT_DATA).into());
+    }
+    if data[4] > VERSION {
+        return Err(tr!(i18n::SETTINGS_TOO_NEW, data[4]));
+    }
+
+    // 先走一遍找到结尾，校验通过了再改
+    let count = data[5] as usize;
+    let mut records = Vec::with_capacity(count);
+    let mut pos = 6;
+    for _ in 0..count {
+        let header = data
+            .get(pos..pos + 3)
+            .ok_or(tr!(i18n::SETTINGS_TRUNCATED))?;
+        let (id, kind, len) = (header[0], header[1], header[2] as usize);
+        let payload = data
+            .get(pos + 3..pos + 3 + len)
+            .ok_or(tr!(i18n::SETTINGS_TRUNCATED))?;
+        records.push((id, kind, payload));
+        pos += 3 + len;
+    }
+    let stored = data
+        .get(pos..pos + 4)
+        .ok_or(tr!(i18n::SETTINGS_TRUNCATED))?;
+    let mut crc = Crc32::new();
+    crc.update(&data[..pos]);
+    if crc.value().to_le_bytes() != stored {
+        return Err(tr!(i18n::SETTINGS_BAD_CRC).into());
+    }
+
+    let mut loaded = 0;
+    for (id, kind, payload) in records {
+        let Some(key) = Key::from_id(id) else {
+            continue;
+        };
+        let value = match (kind, payload) {
+            (TYPE_NUMBER, &[a, b, c, d]) => Value::Number(u32::from_le_bytes([a, b, c, d])),
+            (TYPE_TEXT, bytes) => match core::str::from_utf8(bytes) {
+                Ok(text) => Value::Text(text.to_string()),
+                Err(_) => continue,
+            },
+            _ => continue,
+        };
+        if set(key, value).is_ok() {
+            loaded += 1;
+        }
+    }
+    Ok(loaded)
+}
+
+/// 模拟器：存成文件
+#[cfg(not(feature = "need-ecos"))]
+mod storage {
+    use crate::i18n::{self, tr};
+
+    pub fn location() -> String {
+        tr!(i18n::SETTINGS_FILE, path())
+    }
+
+    fn path() -> String {
+        std::env::var("HANZI_SETTINGS").unwrap_or_else(|_| "settings.bin".to_string())
+    }
+
+    pub fn load() -> Option<Vec<u8>> {
+        std::fs::read(path()).ok()
+    }
+
+    pub fn save(data: &[u8]) -> Result<(), String> {
+        let path = path();
+        std::fs::write(&path, data).map_err(|e| tr!(i18n::SETTINGS_WRITE_FAILED, path, e))
+    }
+
+    pub fn export_hint() -> &'static str {
+        tr!(i18n::SETTINGS_FILE_EXPORT)
+    }
+}
+
+/// 板子：flash 最后一个扇区
+///
+/// flash 映射在 0x3000_0000（固件也从这里跑），最后 4KB 留给设置，固件长不到那里。
+/// 读直接读映射地址。写走映射 flash 的 SPI 控制器（0x0200_0000）的手动模式：
+/// 板子的 start.s 里带了 `flashio_worker`，把一串字节逐位发给 flash 再切回映射模式。
+/// 手动模式下和擦写没完成时都不能从 flash 取指令，所以：
+/// - `flashio_worker` 先拷到 RAM 里再调；
+/// - 发命令和等 flash 忙完放在 [`flash_command`] 里，它链接进 `.data`，开机时拷到 RAM 跑；
+/// - 期间关中断，中断处理在 flash 里。
+///
+/// 一次保存先擦整个扇区（0x20），再按 256 字节一页编程（0x02），最后从映射地址读回来比对。
+#[cfg(feature = "need-ecos")]
+mod storage {
+    use crate::*;
+
+    use crate::i18n::{self, tr};
+
+    pub fn location() -> String {
+        "flash 0x30FFF000".into()
+    }
+
+    const FLASH_BASE: usize = 0x3000_0000;
+    const SECTOR: usize = 0x30FF_F000;
+    const SECTOR_SIZE: usize = 4096;
+    const PAGE_SIZE: usize = 256;
+
+    // SPI NOR 命令
+    const CMD_WRITE_ENABLE: u8 = 0x06;
+    const CMD_READ_STATUS: u8 = 0x05;
+    const CMD_SECTOR_ERASE: u8 = 0x20;
+    const CMD_PAGE_PROGRAM: u8 = 0x02;
+    /// 状态寄存器里的忙标志
+    const STATUS_BUSY: u8 = 0x01;
+
+    pub fn load() -> Option<Vec<u8>> {
+        // SAFETY: 地址在 flash 映射范围内，只读
+        let data = unsafe { core::slice::from_raw_parts(SECTOR as *const u8, SECTOR_SIZE) };
+        // 擦过没写过是全 0xFF
+        (data[0] != 0xFF).then(|| data.to_vec())
+    }
+
+    pub fn save(data: &[u8]) -> Result<(), String> {
+        if data.len() > SECTOR_SIZE {
+            return Err(tr!(i18n::SETTINGS_FLASH_TOO_LARGE, data.len(), SECTOR_SIZE));
+        }
+        let worker = worker();
+        let offset = SECTOR - FLASH_BASE;
+
+        // 命令 + 24 位地址 + 数据，worker 会把收到的字节写回这块缓冲区，所以每次都重新填
+        let mut buffer = [0u8; 4 + PAGE_SIZE];
+        let mut command = |cmd: u8, address: usize, payload: &[u8]| {
+            buffer[0] = cmd;
+            buffer[1..4].copy_from_slice(&(address as u32).to_be_bytes()[1..]);
+            buffer[4..4 + payload.len()].copy_from_slice(payload);
+            // SAFETY: worker 已经拷到 RAM，缓冲区在栈上
+            unsafe { flash_command(worker, buffer.as_mut_ptr(), 4 + payload.len()) };
+        };
+
+        command(CMD_SECTOR_ERASE, offset, &[]);
+        for (page, chunk) in data.chunks(PAGE_SIZE).enumerate() {
+            command(CMD_PAGE_PROGRAM, offset + page * PAGE_SIZE, chunk);
+        }
+
+        // SAFETY: 写完已经回到映射模式
+        let written = unsafe { core::slice::from_raw_parts(SECTOR as *const u8, data.len()) };
+        if written != data {
+            return Err(tr!(i18n::SETTINGS_FLASH_VERIFY_FAILED).into());
+        }
+        Ok(())
+    }
+
+    pub fn export_hint() -> &'static str {
+        tr!(i18n::SETTINGS_FLASH_EXPORT)
+    }
+
+    /// `flashio_worker(data, len, wren)`：发 `len` 个字节，收到的写回 `data`，`wren` 不为 0 时先单独发一个写使能
+    type Worker = extern "C" fn(*mut u8, usize, u32);
+
+    unsafe extern "C" {
+        // 都在 start.s 里
+        static flashio_worker_begin: u32;
+        static flashio_worker_end: u32;
+    }
+
+    // worker 在 RAM 里的副本，它只有二十几条指令
+    static mut WORKER: [u32; 64] = [0; 64];
+
+    #[allow(static_mut_refs)]
+    fn worker() -> Worker {
+        unsafe {
+            let begin = &raw const flashio_worker_begin;
+            let len = (&raw const flashio_worker_end).offset_from(begin) as usize;
+            let len = len.min(WORKER.len());
+            for (i, word) in WORKER[..len].iter_mut().enumerate() {
+                *word = begin.add(i).read_volatile();
+            }
+            core::mem::transmute::<*const u32, Worker>(WORKER.as_ptr())
+        }
+    }
+
+    /// 写使能后发一条命令，再等 flash 忙完
+    ///
+    /// 整个函数在 RAM 里跑，里面不能调 flash 里的任何东西（包括 memcpy 之类编译器插的调用）。
+    #[inline(never)]
+    #[unsafe(link_section = ".data.settings_flash")]
+    unsafe fn flash_command(worker: Worker, data: *mut u8, len: usize) {
+        #[cfg(target_arch = "riscv32")]
+        let mstatus: usize;
+        #[cfg(target_arch = "riscv32")]
+        unsafe {
+            // 关中断，记下原来的状态
+            core::arch::asm!("csrrci {0}, mstatus, 8", out(reg) mstatus);
+        }
+
+        worker(data, len, CMD_WRITE_ENABLE as u32);
+        let mut status = [CMD_READ_STATUS, 0];
+        loop {
+            status[0] = CMD_READ_STATUS;
+            worker(status.as_mut_ptr(), 2, 0);
+            if unsafe { core::ptr::read_volatile(&status[1]) } & STATUS_BUSY == 0 {
+                break;
+            }
+        }
+
+        #[cfg(target_arch = "riscv32")]
+        unsafe {
+            core::arch::asm!("csrs mstatus, {0}", in(reg) mstatus & 8);
+        }
+    }
+}
+
+#[derive(Command, Debug)]
+pub(crate) enum SettingsCommand<'a> {
+    /// 查看一项设置
+    #[command(name = "get")]
+    Get {
+        /// 键名，settings 列出全部
+        key: &'a str,
+    },
+
+    /// 修改一项设置并保存
+    #[command(name = "set")]
+    Set {
+        /// 键名
+        key: &'a str,
+        /// 新的值，default 恢复默认
+        value: &'a str,
+    },
+
+    /// 列出所有设置，`settings reset` 全部恢复默认，`settings export` 打印存储内容
+    #[command(name = "settings")]
+    List {
+        /// reset 或 export，不填列出全部
+        action: Option<&'a str>,
+    },
+}
+
+impl SettingsCommand<'_> {
+    /// 执行后要不要重新设提示符
+    pub fn changes_prompt(&self) -> bool {
+        match self {
+            SettingsCommand::Set { key, .. } => {
+                matches!(Key::from_name(key), Some(Key::PromptUser | Key::PromptHost))
+            }
+            SettingsCommand::List { action } => *action == Some("reset"),
+            SettingsCommand::Get { .. } => false,
+        }
+    }
+}
+
+pub(crate) fn handle_settings_command(
+    manager: &mut DisplayManager,
+    idle: &mut IdleDimmer,
+    command: SettingsCommand<'_>,
+) -> Result<(), core::convert::Infallible> {
+    match command {
+        SettingsCommand::Get { key } => match Key::from_name(key) {
+            Some(key) => println!("\r\n{} = {}\r\n", key.name(), get(key)),
+            None => println!("\r\n{}\r\n", tr!(i18n::SETTINGS_UNKNOWN_KEY, key)),
+        },
+        SettingsCommand::Set { key, value } => {
+            let Some(key) = Key::from_name(key) else {
+                println!("\r\n{}\r\n", tr!(i18n::SETTINGS_UNKNOWN_KEY, key));
+                return Ok(());
+            };
+            let value = match value {
+                "default" => Ok(key.default_value()),
+                text => key.parse(text),
+            };
+            match value.and_then(|value| set(key, value)) {
+                Ok(()) => {
+                    apply_key(manager, idle, key);
+                    println!("\r\n{} = {}\r", key.name(), get(key));
+                    report_save();
+                }
+                Err(e) => println!("\r\n{}\r\n", e),
+            }
+        }
+        SettingsCommand::List { action: None } => {
+            println!("\r\n{}\r", tr!(i18n::SETTINGS_LIST));
+            for key in Key::ALL {
+                let mark = if is_default(key) { ' ' } else { '*' };
+                println!(
+                    "  {} {:<12} {:<10} {}\r",
+                    mark,
+                    key.name(),
+                    get(key).to_string(),
+                    key.help()
+                );
+            }
+            println!("{}\r\n", tr!(i18n::SETTINGS_STORAGE, storage::location()));
+        }
+        SettingsCommand::List {
+            action: Some("reset"),
+        } => {
+            reset();
+            apply(manager, idle);
+            println!("\r\n{}\r", tr!(i18n::SETTINGS_RESET));
+            report_save();
+        }
+        SettingsCommand::List {
+            action: Some("export"),
+        } => {
+            let data = encode();
+            println!(
+                "\r\n{}\r",
+                tr!(i18n::SETTINGS_EXPORT, data.len(), storage::export_hint())
+            );
+            for line in data.chunks(16) {
+                let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+                println!("{}\r", hex.join(" "));
+            }
+            println!("\r");
+        }
+        SettingsCommand::List {
+            action: Some(other),
+        } => println!("\r\n{}\r\n", tr!(i18n::SETTINGS_BAD_ACTION, other)),
+    }
+    Ok(())
+}
+
+fn report_save() {
+    match save() {
+        Ok(()) => println!("{}\r\n", tr!(i18n::SETTINGS_SAVED)),
+        Err(e) => println!("{}\r\n", tr!(i18n::SETTINGS_NOT_SAVED, e)),
+    }
+}
@@ -2,14 +2,18 @@
 
 use crate::*;
 
-#[cfg(feature = "need-ecos")]
-use ecos_ssc1::bindings;
 use embedded_cli::Command;
 use embedded_graphics::prelude::*;
-#[allow(unused)] // 硬件真实环境需要
-use embedded_hal::delay::DelayNs;
 use rand::{Rng, SeedableRng, rngs::SmallRng};
 
+use crate::app::{App, Flow, Scheduler};
+use crate::color::Hsl;
+use crate::i18n::{self, tr};
+use crate::perf;
+use crate::settings::{self, Key};
+use crate::theme;
+use crate::widget::{Dialog, Label, StatusBar, Widget};
+
 // 贪吃蛇命令定义
 #[derive(Command, Debug)]
 pub(crate) enum SnakeSample<'a> {
@@ -45,94 +49,34 @@ pub(crate) enum SnakeSample<'a> {
 // 贪吃蛇游戏处理函数
 pub(crate) fn handle_snake_sample<'a, T>(
     manager: &mut DisplayManager,
+    apps: &mut Scheduler,
     command: SnakeSample<'a>,
 ) -> Result<(), core::convert::Infallible> {
     match command {
         SnakeSample::Start => {
-            println!("\r\n=== 启动贪吃蛇游戏 ===");
-            start_snake_game(manager)
+            println!("\r\n{}", tr!(i18n::SNAKE_START_TITLE));
+            start_snake_game(manager, apps)
         }
         SnakeSample::Difficulty { level, speed } => {
-            println!("\r\n=== 设置游戏难度 ===");
+            println!("\r\n{}", tr!(i18n::SNAKE_DIFFICULTY_TITLE));
             set_game_difficulty(level, speed)
         }
         SnakeSample::Help => {
-            println!("\r\n=== 贪吃蛇游戏帮助 ===");
+            println!("\r\n{}", tr!(i18n::SNAKE_HELP_TITLE));
             show_snake_help()
         }
         SnakeSample::Seed { seed } => {
-            println!("\r\n=== 设置随机种子 ===");
+            println!("\r\n{}", tr!(i18n::SNAKE_SEED_TITLE));
             set_random_seed(seed)
         }
     }
 }
 
 // ===========================================
-// HSL颜色表示和转换
+// HSL随机取色（颜色转换在 color 模块）
 // ============================================
 
-#[derive(Debug, Clone, Copy)]
-struct Hsl {
-    h: f32, // 色调 0-360
-    s: f32, // 饱和度 0-1
-    l: f32, // 亮度 0-1
-}
-
 impl Hsl {
-    fn new(h: f32, s: f32, l: f32) -> Self {
-        Self {
-            h: h.max(0.0).min(360.0),
-            s: s.max(0.0).min(1.0),
-            l: l.max(0.0).min(1.0),
-        }
-    }
-
-    // HSL转RGB
-    fn to_rgb(&self) -> DisplayColor {
-        let (r, g, b) = self.to_rgb888();
-        DisplayColor::new(
-            (r as u32 * 31 / 255) as u8,
-            (g as u32 * 63 / 255) as u8,
-            (b as u32 * 31 / 255) as u8,
-        )
-    }
-
-    fn to_rgb888(&self) -> (u8, u8, u8) {
-        let h = self.h / 360.0;
-        let s = self.s;
-        let l = self.l;
-
-        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
-        let x = c * (1.0 - ((h * 6.0) % 2.0 - 1.0).abs());
-        let m = l - c / 2.0;
-
-        let (r1, g1, b1) = if h < 1.0 / 6.0 {
-            (c, x, 0.0)
-        } else if h < 2.0 / 6.0 {
-            (x, c, 0.0)
-        } else if h < 3.0 / 6.0 {
-            (0.0, c, x)
-        } else if h < 4.0 / 6.0 {
-            (0.0, x, c)
-        } else if h < 5.0 / 6.0 {
-            (x, 0.0, c)
-        } else {
-            (c, 0.0, x)
-        };
-
-        let r = ((r1 + m) * 255.0) as u8;
-        let g = ((g1 + m) * 255.0) as u8;
-        let b = ((b1 + m) * 255.0) as u8;
-
-        (r, g, b)
-    }
-
-    // 计算两个HSL颜色的角度差
-    fn angle_difference(&self, other: &Hsl) -> f32 {
-        let diff = (self.h - other.h).abs();
-        diff.min(360.0 - diff) // 取最短弧
-    }
-
     // 生成与给定颜色有足够角度差的随机颜色
     fn random_with_min_difference(rng: &mut SmallRng, base: &Hsl, min_diff: f32) -> Hsl {
         let mut attempts = 0;
@@ -153,11 +97,6 @@ impl Hsl {
             }
         }
     }
-
-    // 调整亮度
-    fn lighter(&self, amount: f32) -> Hsl {
-        Hsl::new(self.h, self.s, (self.l + amount).min(0.9).max(0.1))
-    }
 }
 
 // ===========================================
@@ -492,6 +431,8 @@ pub struct SnakeGame<const MAX_SNAKE_SIZE: usize> {
     snake_hsl: Hsl,
     last_bg_change: u32,
     bg_change_interval: u32,
+    // 是否在屏幕上画文字提示，点阵上放不下
+    show_hints: bool,
 }
 
 impl<const MAX_SIZE: usize> SnakeGame<MAX_SIZE> {
@@ -538,6 +479,7 @@ impl<const MAX_SIZE: usize> SnakeGame<MAX_SIZE> {
             snake_hsl,
             last_bg_change: 0,
             bg_change_interval: 50, // 临时值，后面会更新
+            show_hints: true,
         };
 
         // 更新bg_change_interval
@@ -642,7 +584,7 @@ impl<const MAX_SIZE: usize> SnakeGame<MAX_SIZE> {
             scale_y: self.scale_y,
         };
 
-        if self.waiting_for_start {
+        if self.waiting_for_start && self.show_hints {
             // 显示等待开始提示
             use embedded_graphics::{
                 mono_font::{MonoTextStyle, ascii::FONT_6X9},
@@ -656,7 +598,7 @@ impl<const MAX_SIZE: usize> SnakeGame<MAX_SIZE> {
             };
 
             let style = MonoTextStyle::new(&FONT_6X9, text_color);
-            let _ = Text::new("按任意键开始", Point::new(30, 60), style).draw(&mut scaled_display);
+            let _ = Text::new("PRESS ANY KEY", Point::new(30, 60), style).draw(&mut scaled_display);
             return;
         }
 
@@ -727,6 +669,12 @@ impl<const MAX_SIZE: usize> SnakeGame<MAX_SIZE> {
         self.game_over
     }
 
+    /// 是否画文字提示，默认画
+    pub fn show_hints(mut self, show: bool) -> Self {
+        self.show_hints = show;
+        self
+    }
+
     pub fn is_waiting_for_start(&self) -> bool {
         self.waiting_for_start
     }
@@ -788,20 +736,31 @@ impl<'a, T: DrawTarget> DrawTarget for ScaledDisplay<'a, T> {
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        for pixel in pixels {
-            let style = embedded_graphics::primitives::PrimitiveStyle::with_fill(pixel.1);
-            Rectangle::new(
-                Point::new(
-                    pixel.0.x * self.scale_x as i32,
-                    pixel.0.y * self.scale_y as i32,
-                ),
-                embedded_graphics::prelude::Size::new(self.scale_x as u32, self.scale_y as u32),
-            )
-            .into_styled(style)
-            .draw(self.target)?;
+        // 每个点放大成一个方块，直接 fill_solid，屏幕驱动可以整块写
+        for Pixel(point, color) in pixels {
+            let block = Rectangle::new(
+                Point::new(point.x * self.scale_x as i32, point.y * self.scale_y as i32),
+                Size::new(self.scale_x as u32, self.scale_y as u32),
+            );
+            self.target.fill_solid(&block, color)?;
         }
         Ok(())
     }
+
+    // 整块区域放大后还是一整块
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let scaled = Rectangle::new(
+            Point::new(
+                area.top_left.x * self.scale_x as i32,
+                area.top_left.y * self.scale_y as i32,
+            ),
+            Size::new(
+                area.size.width * self.scale_x as u32,
+                area.size.height * self.scale_y as u32,
+            ),
+        );
+        self.target.fill_solid(&scaled, color)
+    }
 }
 
 impl<'a, T: DrawTarget> embedded_graphics_core::geometry::Dimensions for ScaledDisplay<'a, T> {
@@ -826,27 +785,47 @@ struct SnakeGameState {
     update_interval: u32,  // 更新间隔（毫秒）
     last_update_time: u64, // 上次更新时间
     frame_counter: u32,    // 简单的帧计数器
+    compact: bool,         // 小屏（点阵）：8x8 格子，不画状态栏和提示
+    size: Size,            // 屏幕尺寸，状态栏和提示框按它布局
+    start_time: u32,       // 进入游戏的时间
+    last_score: u32,       // 上次打印的分数
 }
 
 impl SnakeGameState {
-    fn new(seed: u64, speed: u32) -> Self {
+    fn new(seed: u64, speed: u32, size: Size) -> Self {
+        let compact = size.width < 64 || size.height < 64;
+        // 小屏上一个格子正好对应一颗灯
+        let (scale_x, scale_y) = if compact {
+            (
+                (size.width / 8).max(1) as u8,
+                (size.height / 8).max(1) as u8,
+            )
+        } else {
+            (2, 2)
+        };
         let game = SnakeGame::<200>::new(
-            128, // 显示宽度
-            128, // 显示高度
-            2,   // 横向缩放
-            2,   // 纵向缩放
-            seed, 100, // 食物寿命
-        );
+            size.width.min(255) as u8,  // 显示宽度
+            size.height.min(255) as u8, // 显示高度
+            scale_x,
+            scale_y,
+            seed,
+            100, // 食物寿命
+        )
+        .show_hints(!compact);
 
         Self {
             game,
             level: 1,
             is_paused: false,
-            high_score: 0,
+            high_score: settings::number(Key::SnakeHighScore),
             current_seed: seed,
             update_interval: speed.max(50), // 最小50ms
             last_update_time: 0,
             frame_counter: 0,
+            compact,
+            size,
+            start_time: 0,
+            last_score: 0,
         }
     }
 
@@ -870,55 +849,35 @@ impl SnakeGameState {
         let new_level = (self.game.get_score() / 100) as u8 + 1;
         if new_level > self.level {
             self.level = new_level;
-            println!("🎮 升级到 Level {}!", self.level);
+            println!("{}", tr!(i18n::SNAKE_LEVEL_UP, self.level));
         }
     }
 
-    fn draw<D: DrawTarget<Color = DisplayColor>>(&mut self, display: &mut D) {
+    fn render<D: DrawTarget<Color = DisplayColor>>(&mut self, display: &mut D) {
         // 绘制游戏
         self.game.draw(display);
 
-        // 绘制UI
-        use embedded_graphics::{
-            mono_font::{MonoTextStyle, ascii::FONT_6X9},
-            primitives::{PrimitiveStyle, Rectangle},
-            text::Text,
-        };
-
-        // 清除UI区域
-        let ui_bg = DisplayColor::new(0, 0, 0);
-        let ui_rect = Rectangle::new(Point::new(0, 0), Size::new(128, 20))
-            .into_styled(PrimitiveStyle::with_fill(ui_bg));
-        let _ = ui_rect.draw(display);
-
-        let style = MonoTextStyle::new(&FONT_6X9, DisplayColor::WHITE);
-
-        // 绘制分数
-        let score_text = format!("S:{}", self.game.get_score());
-        let _ = Text::new(&score_text, Point::new(2, 8), style).draw(display);
-
-        // 绘制等级
-        let level_text = format!("L:{}", self.level);
-        let _ = Text::new(&level_text, Point::new(50, 8), style).draw(display);
+        if self.compact {
+            return;
+        }
 
-        // 绘制最高分
-        let high_text = format!("H:{}", self.high_score);
-        let _ = Text::new(&high_text, Point::new(98, 8), style).draw(display);
+        // 顶部状态栏：分数、等级、最高分
+        let mut hud = StatusBar::top(self.size).background(theme::current().background);
+        hud.set_left(&format!("S:{}", self.game.get_score()));
+        hud.set_center(&format!("L:{}", self.level));
+        hud.set_right(&format!("H:{}", self.high_score));
+        hud.draw(display);
 
-        // 显示特殊状态
+        // 特殊状态在屏幕中间弹框
         if self.game.is_waiting_for_start() {
-            let _ = Text::new("按任意键开始", Point::new(30, 60), style).draw(display);
+            Label::centered(self.size, "PRESS ANY KEY")
+                .background(theme::current().background)
+                .draw(display);
         } else if self.is_paused {
-            let pause_rect = Rectangle::new(Point::new(30, 50), Size::new(70, 30))
-                .into_styled(PrimitiveStyle::with_fill(DisplayColor::BLACK));
-            let _ = pause_rect.draw(display);
-            let _ = Text::new("PAUSED", Point::new(40, 60), style).draw(display);
+            Dialog::new(self.size, "PAUSED", &["SPACE to resume"], &[]).draw(display);
         } else if self.game.is_game_over() {
-            let game_over_rect = Rectangle::new(Point::new(10, 50), Size::new(110, 50))
-                .into_styled(PrimitiveStyle::with_fill(DisplayColor::BLACK));
-            let _ = game_over_rect.draw(display);
-            let _ = Text::new("GAME OVER", Point::new(30, 60), style).draw(display);
-            let _ = Text::new("Press R to restart", Point::new(10, 80), style).draw(display);
+            let score = format!("Score {}", self.game.get_score());
+            Dialog::new(self.size, "GAME OVER", &[&score, "Press R to restart"], &[]).draw(display);
         }
     }
 
@@ -943,7 +902,12 @@ impl SnakeGameState {
             b' ' => {
                 if !self.game.is_waiting_for_start() {
                     self.is_paused = !self.is_paused;
-                    println!("游戏 {}", if self.is_paused { "暂停" } else { "继续" });
+                    let msg = if self.is_paused {
+                        i18n::SNAKE_PAUSED
+                    } else {
+                        i18n::SNAKE_RESUMED
+                    };
+                    println!("{}", tr!(msg));
                 }
                 false
             }
@@ -952,18 +916,18 @@ impl SnakeGameState {
                     let final_score = self.game.get_score();
                     if final_score > self.high_score {
                         self.high_score = final_score;
-                        println!("🎉 新纪录！最高分: {}", self.high_score);
+                        println!("{}", tr!(i18n::SNAKE_NEW_RECORD, self.high_score));
                     }
                     self.game.reset(self.current_seed);
                     self.level = 1;
                     self.is_paused = false;
                     self.frame_counter = 0;
-                    println!("重新开始游戏...");
+                    println!("{}", tr!(i18n::SNAKE_RESTART));
                 }
                 false
             }
             b'q' | b'Q' => {
-                println!("退出贪吃蛇游戏");
+                println!("{}", tr!(i18n::SNAKE_QUIT));
                 if self.game.get_score() > self.high_score {
                     self.high_score = self.game.get_score();
                 }
@@ -987,204 +951,161 @@ impl SnakeGameState {
     }
 }
 
-// 启动贪吃蛇游戏
-fn start_snake_game(manager: &mut DisplayManager) -> Result<(), core::convert::Infallible> {
-    println!("\r\n=== 贪吃蛇游戏开始 ===");
-    println!("游戏特性:");
-    println!("  • 随机变化的背景颜色");
-    println!("  • 动态缩放的食物 (0.85-1.15倍)");
-    println!("  • 颜色差异越大，分数越高");
-    println!("");
-    println!("游戏控制:");
-    println!("  W/A/S/D 或 I/J/K/L - 控制方向");
-    println!("  空格键              - 暂停/继续");
-    println!("  R                  - 重新开始");
-    println!("  Q                  - 退出游戏");
-    println!("======================\r\n");
-
-    // 创建游戏状态 - 200ms更新一次
-    let mut game_state = SnakeGameState::new(0x10086, 200);
-
-    // 获取初始时间（毫秒）
-    #[cfg(feature = "target-ui-sim")]
-    let start_time = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64;
-    #[cfg(feature = "need-ecos")]
-    let start_time = unsafe { bindings::get_sys_tick() } as u64;
-
-    let mut last_score = 0;
-
-    loop {
-        // 获取当前时间
-        #[cfg(feature = "target-ui-sim")]
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64
-            - start_time;
-
-        #[cfg(feature = "need-ecos")]
-        let current_time = unsafe { bindings::get_sys_tick() } as u64 - start_time;
-
-        // 处理用户输入
-        if let Some(byte) = crate::Uart::read_byte_nonblock() {
-            if game_state.handle_input(byte) {
-                break;
-            }
-        }
-
-        // 更新游戏状态
-        game_state.update(current_time);
+impl App for SnakeGameState {
+    fn name(&self) -> &'static str {
+        tr!(i18n::SNAKE_APP)
+    }
 
-        // 绘制游戏
-        game_state.draw(&mut manager.display);
+    fn on_enter(&mut self, manager: &mut DisplayManager) -> bool {
+        self.start_time = manager.now_ms();
+        true
+    }
 
-        // 更新窗口显示
-        #[cfg(feature = "target-ui-sim")]
-        {
-            manager.update_window();
+    fn on_key(&mut self, _manager: &mut DisplayManager, byte: u8) -> Flow {
+        if self.handle_input(byte) {
+            Flow::Exit
+        } else {
+            Flow::Continue
         }
+    }
+
+    // 每帧都重画，大约 60FPS
+    fn on_tick(&mut self, _manager: &mut DisplayManager, now: u32) -> bool {
+        self.update(now.wrapping_sub(self.start_time) as u64);
 
         // 显示分数变化
-        let current_score = game_state.get_score();
-        if current_score != last_score {
+        let current_score = self.get_score();
+        if current_score != self.last_score {
             println!(
-                "当前得分: {}, 等级: {}",
-                current_score,
-                game_state.get_level()
+                "{}",
+                tr!(i18n::SNAKE_SCORE, current_score, self.get_level())
             );
-            last_score = current_score;
+            self.last_score = current_score;
+        }
+        true
+    }
+
+    fn draw(&mut self, manager: &mut DisplayManager) {
+        let _t = perf::scope("snake.frame");
+        self.render(manager.target());
+    }
+
+    fn on_exit(&mut self, manager: &mut DisplayManager) {
+        // 最高分记到设置里，下次开机还在
+        self.high_score = self.high_score.max(self.get_score());
+        if self.high_score > settings::number(Key::SnakeHighScore) {
+            let _ = settings::set_number(Key::SnakeHighScore, self.high_score)
+                .and_then(|_| settings::save());
         }
 
-        // 控制帧率（大约60FPS）
-        #[cfg(feature = "target-ui-sim")]
-        std::thread::sleep(std::time::Duration::from_millis(16));
-        #[cfg(not(feature = "target-ui-sim"))]
-        manager.delay.delay_ms(16);
+        // 显示最终结果
+        println!("\r\n{}", tr!(i18n::SNAKE_GAME_OVER));
+        println!("{}", tr!(i18n::SNAKE_FINAL_SCORE, self.get_score()));
+        println!("{}", tr!(i18n::SNAKE_HIGH_SCORE, self.high_score));
+        println!("{}", tr!(i18n::SNAKE_LEVEL_REACHED, self.get_level()));
+        println!("======================\r\n");
+
+        // 最后清屏
+        let _ = manager.target().clear(theme::current().background);
     }
 
-    // 显示最终结果
-    println!("\r\n游戏结束！");
-    println!("最终得分: {}", game_state.get_score());
-    println!("最高分: {}", game_state.high_score);
-    println!("达到等级: {}", game_state.get_level());
-    println!("======================\r\n");
+    fn is_busy(&self) -> bool {
+        !self.is_paused && !self.game.is_waiting_for_start() && !self.game.is_game_over()
+    }
+}
+
+// 启动贪吃蛇游戏
+fn start_snake_game(
+    manager: &mut DisplayManager,
+    apps: &mut Scheduler,
+) -> Result<(), core::convert::Infallible> {
+    i18n::SNAKE_INTRO.print();
 
-    // 最后清屏
-    let _ = manager.display.clear(DisplayColor::BLACK);
+    // 创建游戏状态，更新间隔按设置里的速度算，之后交给调度器
+    let speed = settings::number(Key::SnakeSpeed) as u8;
+    let game_state = SnakeGameState::new(0x10086, speed_interval(speed), manager.size());
+    apps.start(manager, Box::new(game_state));
 
     Ok(())
 }
 
-// 设置游戏难度
+/// 速度（1-10）对应的更新间隔，1=230ms，10=50ms
+fn speed_interval(speed: u8) -> u32 {
+    250 - (speed.clamp(1, 10) as u32 * 20)
+}
+
+// 设置游戏难度，记到设置里，下次开局生效
 fn set_game_difficulty<'a>(
     level: Option<&'a str>,
     speed: Option<u8>,
 ) -> Result<(), core::convert::Infallible> {
-    println!("当前设置:");
-
-    if let Some(lvl) = level {
-        match lvl.to_lowercase().as_str() {
-            "easy" => println!("  难度: 简单 (速度慢，颜色变化平缓)"),
-            "normal" => println!("  难度: 普通 (速度中等，颜色变化正常)"),
-            "hard" => println!("  难度: 困难 (速度快，颜色变化频繁)"),
-            _ => println!("  难度: 未知 (使用 easy/normal/hard)"),
+    // 难度就是几档预设的速度，同时给了速度以速度为准
+    let preset = match level.map(|lvl| lvl.to_lowercase()) {
+        None => None,
+        Some(lvl) => match lvl.as_str() {
+            "easy" => Some(2),
+            "normal" => Some(5),
+            "hard" => Some(8),
+            _ => {
+                println!("{}", tr!(i18n::SNAKE_UNKNOWN_LEVEL));
+                None
+            }
+        },
+    };
+
+    match speed.or(preset) {
+        Some(spd) if (1..=10).contains(&spd) => {
+            let _ = settings::set_number(Key::SnakeSpeed, spd as u32);
+            match settings::save() {
+                Ok(()) => println!("{}", tr!(i18n::SNAKE_SPEED_SET, spd, speed_interval(spd))),
+                Err(e) => println!("{}", tr!(i18n::SNAKE_SPEED_NOT_SAVED, spd, e)),
+            }
         }
-    } else {
-        println!("  难度: 未设置");
+        Some(_) => println!("{}", tr!(i18n::SNAKE_SPEED_INVALID)),
+        None => {}
     }
 
-    if let Some(spd) = speed {
-        if spd >= 1 && spd <= 10 {
-            let actual_speed = 250 - (spd as u32 * 20); // 1=230ms, 10=50ms
-            println!("  速度: {} ({}ms/帧)", spd, actual_speed);
-        } else {
-            println!("  速度: 无效 (应为 1-10)");
-        }
-    } else {
-        println!("  速度: 未设置");
-    }
+    let spd = settings::number(Key::SnakeSpeed) as u8;
+    println!("{}", tr!(i18n::SNAKE_CURRENT));
+    println!(
+        "{}",
+        tr!(i18n::SNAKE_CURRENT_SPEED, spd, speed_interval(spd))
+    );
+    println!(
+        "{}",
+        tr!(
+            i18n::SNAKE_CURRENT_HIGH,
+            settings::number(Key::SnakeHighScore)
+        )
+    );
 
-    println!("\r\n使用示例:");
-    println!("  snake-difficulty --level easy --speed 3");
-    println!("  snake-difficulty -l normal -s 6");
+    i18n::SNAKE_DIFFICULTY_EXAMPLES.print();
 
     Ok(())
 }
 
 // 显示游戏帮助
 fn show_snake_help() -> Result<(), core::convert::Infallible> {
-    println!("贪吃蛇游戏说明:");
-    println!("================");
-    println!("游戏特性:");
-    println!("  • 背景颜色随机变化");
-    println!("  • 蛇颜色与背景保持60°以上差异");
-    println!("  • 食物颜色与背景/蛇都保持30°以上差异");
-    println!("  • 食物动态缩放 (0.85-1.15倍)");
-    println!("  • 颜色差异越大，分数加成越高");
-    println!("  • 状态机模式，流畅动画");
-    println!("");
-    println!("基本命令:");
-    println!("  snake            - 开始游戏");
-    println!("  snake-difficulty - 设置游戏难度和速度");
-    println!("  snake-seed       - 设置随机种子");
-    println!("  snake-help       - 显示此帮助信息");
-    println!("");
-    println!("游戏内控制:");
-    println!("  任意键           - 开始游戏（等待状态时）");
-    println!("  W/A/S/D 或 I/J/K/L - 控制蛇的移动方向");
-    println!("  空格键              - 暂停/继续游戏");
-    println!("  R                  - 游戏结束后重新开始");
-    println!("  Q                  - 退出游戏");
-    println!("");
-    println!("计分规则:");
-    println!("  • 基础分: 10分");
-    println!("  • 颜色差异加成: 最多+20分");
-    println!("  • 每100分升一级");
-    println!("");
-    println!("颜色系统:");
-    println!("  • 背景: 暗色，低饱和度，定期变化");
-    println!("  • 蛇: 与背景差异60°以上");
-    println!("  • 食物: 与背景和蛇都差异30°以上");
-    println!("  • 蛇头: 比蛇身亮20%");
-    println!("");
-    println!("动画效果:");
-    println!("  • 食物: 0.85-1.15倍动态缩放");
-    println!("  • 状态机驱动，不依赖延时");
-    println!("  • 局部刷新，性能优化");
-    println!("");
-    println!("难度设置:");
-    println!("  简单 (easy)   - 速度慢，颜色变化少");
-    println!("  普通 (normal) - 默认难度");
-    println!("  困难 (hard)   - 速度快，颜色变化多");
-    println!("");
-    println!("速度设置 (1-10):");
-    println!("  1: 最慢 (230ms/帧)，10: 最快 (50ms/帧)");
-    println!("");
-    println!("随机种子:");
-    println!("  使用 snake-seed <十六进制数> 设置随机种子");
-    println!("  相同的种子会产生相同的颜色序列");
+    i18n::SNAKE_HELP.print();
 
     Ok(())
 }
 
 // 设置随机种子
 fn set_random_seed(seed: u64) -> Result<(), core::convert::Infallible> {
-    println!("设置随机种子: 0x{:X}", seed);
-    println!("下次启动游戏时将使用此种子");
+    println!("{}", tr!(i18n::SNAKE_SEED_SET, format!("0x{:X}", seed)));
+    println!("{}", tr!(i18n::SNAKE_SEED_NEXT));
 
     // 显示一些示例随机数
     let mut rng = SmallRng::seed_from_u64(seed);
-    println!("示例随机数:");
+    println!("{}", tr!(i18n::SNAKE_SEED_NUMBERS));
     for i in 0..5 {
         let value: i32 = rng.random_range(-1000..1000);
         println!("  [{}]: {}", i + 1, value);
     }
 
     // 显示示例颜色
-    println!("示例颜色 (HSL格式):");
+    println!("{}", tr!(i18n::SNAKE_SEED_COLORS));
     for i in 0..3 {
         let hsl = Hsl::new(
             rng.random_range(0.0..360.0),
@@ -0,0 +1,149 @@
+//! 开机画面
+//!
+//! 上电后先显示 logo、固件名和版本，底下一条进度条跟着初始化走（最慢的是解析字体）。
+//! logo 用图片表里叫 `logo` 的图（开了 `cmd-image`，display/images 下放 logo.png），没有就把固件名写大一点。
+//! 固件名和版本是 build.rs 从 include/config/auto.conf 里读的，没跑 build.rs 时用 Cargo.toml 里的。
+
+use crate::*;
+
+use crate::i18n::{self, tr};
+
+use crate::widget::{ProgressBar, Widget};
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        ascii::{FONT_6X10, FONT_10X20},
+    },
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+/// 固件名（`CONFIG_FIRMWARE_NAME`）
+pub(crate) const FIRMWARE_NAME: &str = match option_env!("HANZI_FIRMWARE_NAME") {
+    Some(name) => name,
+    None => env!("CARGO_PKG_NAME"),
+};
+
+/// 固件版本（`CONFIG_FIRMWARE_VERSION`）
+pub(crate) const FIRMWARE_VERSION: &str = match option_env!("HANZI_FIRMWARE_VERSION") {
+    Some(version) => version,
+    None => env!("CARGO_PKG_VERSION"),
+};
+
+/// 底部信息栏（固件名 + 进度条）的高度
+const FOOTER_HEIGHT: u32 = 24;
+
+/// 比这矮的屏（比如 LED 点阵）只画进度条
+const MIN_TEXT_HEIGHT: u32 = 64;
+
+/// 开机画面
+pub(crate) struct Splash {
+    bar: ProgressBar,
+}
+
+impl Splash {
+    /// 画出开机画面，进度从 0 开始
+    pub fn show<B: DisplayBackend>(backend: &mut B) -> Self {
+        println!(
+            "{}",
+            tr!(i18n::BOOT_STARTING, FIRMWARE_NAME, FIRMWARE_VERSION)
+        );
+
+        let size = backend.size();
+        let target = backend.target();
+        let _ = target.clear(DisplayColor::BLACK);
+
+        let bar = ProgressBar::new(if size.height < MIN_TEXT_HEIGHT {
+            // 小屏幕最下面两行当进度条
+            Rectangle::new(
+                Point::new(0, size.height as i32 - 2),
+                Size::new(size.width, 2),
+            )
+        } else {
+            let logo_area = Rectangle::new(
+                Point::zero(),
+                Size::new(size.width, size.height - FOOTER_HEIGHT),
+            );
+            draw_logo(target, &logo_area);
+
+            let footer_top = (size.height - FOOTER_HEIGHT) as i32;
+            let _ = Rectangle::new(
+                Point::new(0, footer_top),
+                Size::new(size.width, FOOTER_HEIGHT),
+            )
+            .into_styled(PrimitiveStyle::with_fill(DisplayColor::BLACK))
+            .draw(target);
+
+            let label = format!("{} v{}", FIRMWARE_NAME, FIRMWARE_VERSION);
+            let _ = Text::with_text_style(
+                &label,
+                Point::new(size.width as i32 / 2, footer_top + 2),
+                MonoTextStyle::new(&FONT_6X10, DisplayColor::WHITE),
+                centered(),
+            )
+            .draw(target);
+
+            Rectangle::new(
+                Point::new(6, size.height as i32 - 10),
+                Size::new(size.width.saturating_sub(12), 7),
+            )
+        });
+        bar.draw(backend.target());
+
+        backend.present();
+        Self { bar }
+    }
+
+    /// 更新进度（0-100），`label` 是接下来要做的事，打印到串口
+    pub fn progress<B: DisplayBackend>(&mut self, backend: &mut B, percent: u8, label: &str) {
+        println!("[{:>3}%] {}", percent, label);
+        self.bar.set_value(percent);
+        self.bar.draw(backend.target());
+        backend.present();
+    }
+
+    /// 初始化完成，进度条填满；画面留着，等第一条命令把它盖掉
+    pub fn finish<B: DisplayBackend>(mut self, backend: &mut B) {
+        self.progress(backend, 100, tr!(i18n::BOOT_DONE));
+    }
+}
+
+fn centered() -> embedded_graphics::text::TextStyle {
+    TextStyleBuilder::new()
+        .alignment(Alignment::Center)
+        .baseline(Baseline::Top)
+        .build()
+}
+
+// 在 `area` 里居中画 logo
+fn draw_logo<D>(target: &mut D, area: &Rectangle)
+where
+    D: DrawTarget<Color = DisplayColor>,
+{
+    #[cfg(feature = "cmd-image")]
+    if let Some(Ok(logo)) = crate::image::find("logo") {
+        use embedded_graphics::image::Image;
+
+        let offset = area.center() - Rectangle::new(Point::zero(), logo.size()).center();
+        let _ = Image::new(&logo, offset).draw(&mut target.clipped(area));
+        return;
+    }
+
+    // 没有 logo 图片，写一行大字
+    let font = if FIRMWARE_NAME.len() as u32 * FONT_10X20.character_size.width <= area.size.width {
+        &FONT_10X20
+    } else {
+        &FONT_6X10
+    };
+    let _ = Text::with_text_style(
+        FIRMWARE_NAME,
+        area.center(),
+        MonoTextStyle::new(font, DisplayColor::CYAN),
+        TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build(),
+    )
+    .draw(target);
+}
@@ -0,0 +1,188 @@
+//! ST7735 接到 SDK 上
+//!
+//! st7735-lcd 要的 SPI、DC/RST 引脚和延时，用 `ecos_ssc1` 的 QSPI、GPIO 和定时器实现。
+//! 不用 ecos-ebui：它依赖的 SDK 开着默认特性，会带进 SDK 自己的 panic 处理，和 crash.rs 冲突。
+
+use ecos_ssc1::{GpioPin, Qspi, QspiError, Timer, qspi};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::spi::{self, Operation, SpiDevice};
+use st7735_lcd::ST7735;
+
+/// 板子上的 ST7735
+pub(crate) type St7735Display = ST7735<QspiDevice, Gpio, Gpio>;
+
+/// 屏幕接线和参数
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DisplayConfig {
+    /// DC 引脚（数据/命令选择），排针号
+    pub dc_pin: u32,
+    /// RST 引脚，没接就是 None
+    pub rst_pin: Option<u32>,
+    pub width: u16,
+    pub height: u16,
+    /// true 是 RGB，false 是 BGR
+    pub rgb: bool,
+    /// 颜色反转
+    pub inverted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Error {
+    /// QSPI 还没初始化
+    NotInitialized,
+    Qspi(QspiError),
+    /// 屏幕初始化命令没发出去
+    InitFailed,
+}
+
+impl From<QspiError> for Error {
+    fn from(error: QspiError) -> Self {
+        Error::Qspi(error)
+    }
+}
+
+impl spi::Error for Error {
+    fn kind(&self) -> spi::ErrorKind {
+        spi::ErrorKind::Other
+    }
+}
+
+/// 初始化 QSPI 并创建驱动，屏幕本身还没初始化，见 [`init_display`]
+pub(crate) fn create_display(config: &DisplayConfig) -> Result<St7735Display, Error> {
+    // 分频 0，QSPI 跑满速
+    qspi::init_qspi(0);
+    let spi = QspiDevice::new().ok_or(Error::NotInitialized)?;
+
+    let mut dc = Gpio::new(config.dc_pin);
+    let _ = dc.set_high();
+    // 没接 RST 时给 0 号，SDK 找不到这个引脚，电平设置直接忽略
+    let mut rst = Gpio::new(config.rst_pin.unwrap_or(0));
+    let _ = rst.set_high();
+
+    Ok(ST7735::new(
+        spi,
+        dc,
+        rst,
+        config.rgb,
+        config.inverted,
+        config.width as u32,
+        config.height as u32,
+    ))
+}
+
+/// 发初始化命令，按竖屏初始化
+pub(crate) fn init_display(
+    display: &mut St7735Display,
+    delay: &mut TimerDelay,
+) -> Result<(), Error> {
+    display.init(delay).map_err(|_| Error::InitFailed)?;
+    // 这块屏的显存比可见区域大一圈
+    display.set_offset(2, 1);
+    Ok(())
+}
+
+// ===========================================
+// SPI / GPIO / 延时
+// ============================================
+
+/// QSPI_0 当普通 SPI 用，屏幕是唯一的设备，不用片选
+pub(crate) struct QspiDevice {
+    qspi: &'static mut Qspi,
+}
+
+impl QspiDevice {
+    fn new() -> Option<Self> {
+        qspi::get_qspi().map(|qspi| Self { qspi })
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        match data {
+            [] => Ok(()),
+            [byte] => Ok(self.qspi.write_u8(*byte)?),
+            _ => Ok(self.qspi.write_bytes(data)?),
+        }
+    }
+
+    // QSPI 只会发，读的时候发一个空字节，收到的数据在最高字节
+    fn read(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        for byte in buffer {
+            self.qspi.write_u8(0)?;
+            *byte = (self.qspi.read_u32() >> 24) as u8;
+        }
+        Ok(())
+    }
+}
+
+impl spi::ErrorType for QspiDevice {
+    type Error = Error;
+}
+
+impl SpiDevice<u8> for QspiDevice {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Error> {
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => self.write(data)?,
+                Operation::Read(buffer) => self.read(buffer)?,
+                Operation::Transfer(read, write) => {
+                    self.write(write)?;
+                    self.read(read)?;
+                }
+                Operation::TransferInPlace(buffer) => {
+                    self.write(buffer)?;
+                    self.read(buffer)?;
+                }
+                Operation::DelayNs(ns) => TimerDelay.delay_ns(*ns),
+            }
+        }
+        Ok(self.qspi.wait_transfer_complete_full()?)
+    }
+}
+
+/// 输出引脚，用排针号（1-16）
+pub(crate) struct Gpio {
+    pin: u32,
+}
+
+impl Gpio {
+    fn new(pin: u32) -> Self {
+        Self { pin }
+    }
+}
+
+impl digital::ErrorType for Gpio {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for Gpio {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        let _ = GpioPin::set_level(self.pin, true);
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        let _ = GpioPin::set_level(self.pin, false);
+        Ok(())
+    }
+}
+
+/// SDK 定时器延时
+pub(crate) struct TimerDelay;
+
+impl DelayNs for TimerDelay {
+    // 定时器只到微秒，不足 1us 的按 1us 算
+    fn delay_ns(&mut self, ns: u32) {
+        let us = ns.div_ceil(1_000);
+        if us > 0 {
+            Timer::delay_us(us);
+        }
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        Timer::delay_us(us);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        Timer::delay_ms(ms);
+    }
+}
@@ -4,10 +4,17 @@ use crate::*;
 
 use rusttype::Font;
 
+use crate::app::{App, Flow, Scheduler};
+use crate::i18n::{self, tr};
+use crate::perf;
+use crate::settings::{self, Key};
+use crate::theme;
+use crate::widget::{KeyDecoder, List, Response, ScrollBar, StatusBar, Widget};
+
 use embedded_cli::Command;
 use embedded_graphics::primitives::Rectangle;
-use embedded_graphics::{Drawable, prelude::*, text::Text};
-use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point, pixelcolor::RgbColor};
+use embedded_graphics::{Drawable, prelude::*};
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point};
 use embedded_text::{
     TextBox,
     alignment::HorizontalAlignment,
@@ -15,9 +22,6 @@ use embedded_text::{
 };
 use embedded_ttf::FontTextStyleBuilder;
 
-const HARMONYOS_SANS_SC_LIGHT: &[u8] =
-    include_bytes!("../display/fonts/HarmonyOS_Sans_SC_Regular.ttf");
-
 // 全局文档存储
 static mut DOCUMENTS: Option<HashMap<String, String>> = None;
 
@@ -53,6 +57,14 @@ fn get_documents() -> &'static HashMap<String, String> {
     }
 }
 
+// 光标列（字符下标）换成字节下标，超出行尾时给行尾
+fn byte_offset(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len())
+}
+
 const DEMO_TEXT: &str = r#"
 "在这个世界，没有绝对的光明，也没有绝对的黑暗，只有无尽的灰。"
 
@@ -143,21 +155,22 @@ pub(crate) enum TextSample<'a> {
 // 文本处理函数
 pub(crate) fn handle_text_display<'a, T>(
     manager: &mut DisplayManager,
+    apps: &mut Scheduler,
     command: TextSample<'a>,
 ) -> Result<(), core::convert::Infallible> {
     match command {
         TextSample::Reader => {
-            println!("\r\n=== 启动文本阅读器 ===");
-            let mut reader = TextReader::new(manager);
-            reader.run()
+            println!("\r\n{}", tr!(i18n::READER_START));
+            apps.start(manager, Box::new(TextReader::new()));
+            Ok(())
         }
         TextSample::Editor => {
-            println!("\r\n=== 启动文本编辑器 ===");
-            let mut editor = TextEditor::new(manager);
-            editor.run()
+            println!("\r\n{}", tr!(i18n::EDITOR_START));
+            apps.start(manager, Box::new(TextEditor::new()));
+            Ok(())
         }
         TextSample::List => {
-            println!("\r\n=== 文件列表 ===");
+            println!("\r\n{}", tr!(i18n::TEXT_LIST));
             let docs = get_documents();
             for (filename, content) in docs.iter() {
                 let lines: Vec<&str> = content.lines().collect();
@@ -167,117 +180,144 @@ pub(crate) fn handle_text_display<'a, T>(
             Ok(())
         }
         TextSample::New { filename } => {
-            println!("\r\n=== 创建新文件: {} ===", filename);
+            println!("\r\n{}", tr!(i18n::TEXT_NEW, filename));
             let docs = get_documents_mut();
             docs.insert(filename.to_string(), String::new());
-            println!("文件创建成功");
+            println!("{}", tr!(i18n::TEXT_CREATED));
             Ok(())
         }
         TextSample::Open { filename } => {
-            println!("\r\n=== 打开文件: {} ===", filename);
+            println!("\r\n{}", tr!(i18n::TEXT_OPEN, filename));
             let docs = get_documents();
             let filename_str = filename.to_string();
             if let Some(content) = docs.get(&filename_str) {
-                println!("文件内容:");
+                println!("{}", tr!(i18n::TEXT_CONTENT));
                 println!("{}", content);
             } else {
-                println!("文件不存在");
+                println!("{}", tr!(i18n::FILE_NOT_FOUND));
             }
             Ok(())
         }
     }
 }
 
+// 上次看的文档，已经不在了就从 demo.txt 开始
+fn last_document() -> String {
+    let name = settings::text(Key::LastDocument);
+    if get_documents().contains_key(&name) {
+        name
+    } else {
+        "demo.txt".to_string()
+    }
+}
+
 // 文本阅读器
-pub(crate) struct TextReader<'a> {
-    manager: &'a mut DisplayManager,
+pub(crate) struct TextReader {
     font: Option<Font<'static>>,
     current_file: String,
     scroll_offset: i32,
+    font_size: u32,
+    // 按 f 弹出的文件列表
+    picker: Option<List>,
+    keys: KeyDecoder,
 }
 
-impl<'a> TextReader<'a> {
-    pub fn new(manager: &'a mut DisplayManager) -> Self {
+impl TextReader {
+    pub fn new() -> Self {
         Self {
-            manager,
             font: None,
-            current_file: "demo.txt".to_string(),
+            current_file: last_document(),
             scroll_offset: 0,
+            font_size: settings::number(Key::FontSize),
+            picker: None,
+            keys: KeyDecoder::new(),
         }
     }
+}
+
+impl App for TextReader {
+    fn name(&self) -> &'static str {
+        tr!(i18n::READER_APP)
+    }
 
-    pub fn run(&mut self) -> Result<(), core::convert::Infallible> {
+    fn on_enter(&mut self, _manager: &mut DisplayManager) -> bool {
         // 加载字体
-        self.font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
+        self.font = match fonts::harmony_sans() {
             Some(font) => {
-                println!("字体加载成功");
+                log::debug!("{}", tr!(i18n::TEXT_FONT_LOADED));
                 Some(font)
             }
             None => {
-                println!("字体加载失败");
-                return Ok(());
+                log::error!("{}", tr!(i18n::TEXT_FONT_FAILED));
+                return false;
             }
         };
 
-        println!("\r\n=== 文本阅读器 ===");
-        println!("可用命令:");
-        println!("  n/p - 下一个/上一个文档");
-        println!("  ↑/↓ - 滚动文本");
-        println!("  f   - 切换文件");
-        println!("  q   - 退出");
-        println!("==================\r\n");
-
-        self.display_current_document()?;
-
-        // 主循环
-        loop {
-            if let Some(byte) = Uart::read_byte_nonblock() {
-                match byte {
-                    b'q' | b'Q' => {
-                        println!("\r\n退出阅读器");
-                        break;
-                    }
-                    b'n' | b'N' => {
-                        self.next_document();
-                        self.display_current_document()?;
-                    }
-                    b'p' | b'P' => {
-                        self.prev_document();
-                        self.display_current_document()?;
-                    }
-                    b'f' | b'F' => {
-                        self.select_file();
-                        self.display_current_document()?;
-                    }
-                    b'[' | b'k' | b'K' => {
-                        // 上滚
-                        self.scroll_offset = (self.scroll_offset - 10).max(0);
-                        self.display_current_document()?;
-                    }
-                    b']' | b'j' | b'J' => {
-                        // 下滚
-                        self.scroll_offset += 10;
-                        self.display_current_document()?;
-                    }
-                    _ => {}
-                }
+        i18n::READER_HELP.print();
+        true
+    }
+
+    fn on_key(&mut self, manager: &mut DisplayManager, byte: u8) -> Flow {
+        if self.picker.is_some() {
+            return self.picker_key(byte);
+        }
+
+        match byte {
+            b'q' | b'Q' => {
+                println!("\r\n{}", tr!(i18n::READER_EXIT));
+                return Flow::Exit;
             }
-            #[cfg(feature = "target-ui-sim")]
-            {
-                self.manager.update_window();
+            b'n' | b'N' => self.next_document(),
+            b'p' | b'P' => self.prev_document(),
+            b'f' | b'F' => self.select_file(manager.size()),
+            b'[' | b'k' | b'K' => {
+                // 上滚
+                self.scroll_offset = (self.scroll_offset - 10).max(0);
             }
+            b']' | b'j' | b'J' => {
+                // 下滚
+                self.scroll_offset += 10;
+            }
+            _ => return Flow::Continue,
         }
+        Flow::Redraw
+    }
 
-        Ok(())
+    fn draw(&mut self, manager: &mut DisplayManager) {
+        match &self.picker {
+            Some(list) => {
+                let mut bar = StatusBar::bottom(manager.size());
+                bar.set_left(tr!(i18n::PICKER_OPEN));
+                bar.set_right(tr!(i18n::PICKER_CANCEL));
+                bar.draw(manager.target());
+                list.draw(manager.target());
+            }
+            None => {
+                let _ = self.display_current_document(manager);
+            }
+        }
+    }
+
+    fn on_exit(&mut self, _manager: &mut DisplayManager) {
+        // 记住看到哪个文档，下次打开阅读器接着看
+        if settings::text(Key::LastDocument) != self.current_file {
+            let _ = settings::set_text(Key::LastDocument, &self.current_file)
+                .and_then(|_| settings::save());
+        }
     }
+}
 
-    fn display_current_document(&mut self) -> Result<(), core::convert::Infallible> {
+impl TextReader {
+    fn display_current_document(
+        &mut self,
+        manager: &mut DisplayManager,
+    ) -> Result<(), core::convert::Infallible> {
         // 清屏
-        let _ = self.manager.display.clear(DisplayColor::new(0, 0, 8));
+        let _ = manager.target().clear(theme::current().reader);
 
         let docs = get_documents();
         if let Some(content) = docs.get(&self.current_file) {
-            println!("显示文档: {}", self.current_file);
+            println!("{}", tr!(i18n::READER_SHOWING, self.current_file));
 
             // 创建字体样式
             let font = match &self.font {
@@ -286,8 +326,8 @@ impl<'a> TextReader<'a> {
             };
 
             let style = FontTextStyleBuilder::new(font.clone())
-                .font_size(24) // 大号字体
-                .text_color(DisplayColor::WHITE)
+                .font_size(self.font_size) // 字号在设置里改（font.size）
+                .text_color(theme::current().text)
                 .build();
 
             // 创建文本框样式 - 使用更安全的参数
@@ -299,8 +339,14 @@ impl<'a> TextReader<'a> {
 
             // 计算显示区域，确保不会出现负数
             let scroll_offset = self.scroll_offset.max(0); // 确保scroll_offset非负
-            let display_bounds =
-                Rectangle::new(Point::new(5, 5 - scroll_offset), Size::new(118, 118));
+            let size = manager.size();
+            let display_bounds = Rectangle::new(
+                Point::new(5, 5 - scroll_offset),
+                Size::new(
+                    size.width.saturating_sub(10),
+                    size.height.saturating_sub(10),
+                ),
+            );
 
             // 确保显示区域在合理范围内
             if display_bounds.size.height > 0 && display_bounds.size.width > 0 {
@@ -308,86 +354,55 @@ impl<'a> TextReader<'a> {
                 let text_box =
                     TextBox::with_textbox_style(content, display_bounds, style, textbox_style);
 
-                let _ = text_box.draw(&mut self.manager.display);
+                let _t = perf::scope("textbox");
+                let _ = text_box.draw(manager.target());
             }
 
             // 绘制滚动条
-            self.draw_scrollbar();
+            self.draw_scrollbar(manager);
 
             // 绘制状态栏
-            self.draw_status_bar();
+            self.draw_status_bar(manager);
         }
 
         Ok(())
     }
 
-    fn draw_scrollbar(&mut self) {
-        use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
-
+    fn draw_scrollbar(&mut self, manager: &mut DisplayManager) {
         let docs = get_documents();
         if let Some(content) = docs.get(&self.current_file) {
-            // 计算总行数
-            let line_count = content.lines().count() as i32;
-            let visible_lines = 5; // 大约5行24像素字体
-
-            if line_count > visible_lines {
-                // 绘制滚动条轨道
-                let _ = Rectangle::new(Point::new(122, 5), Size::new(3, 118))
-                    .into_styled(PrimitiveStyle::with_fill(DisplayColor::new(30, 30, 30)))
-                    .draw(&mut self.manager.display);
-
-                // 计算滑块位置和大小
-                let track_height = 118;
-                let slider_height = (track_height * visible_lines / line_count).max(10);
-                let slider_position = if line_count * 24 / 10 > 0 {
-                    (track_height - slider_height) * self.scroll_offset
-                        / (line_count * 24 / 10).max(1)
-                } else {
-                    0
-                };
-
-                // 绘制滑块
-                let _ = Rectangle::new(
-                    Point::new(122, 5 + slider_position as i32),
-                    Size::new(3, slider_height as u32),
-                )
-                .into_styled(PrimitiveStyle::with_fill(DisplayColor::CYAN))
-                .draw(&mut self.manager.display);
-            }
+            // 滚动按像素算，一行就是字号那么高
+            let line_count = content.lines().count() as u32;
+            let size = manager.size();
+            let track_height = size.height.saturating_sub(10 + StatusBar::HEIGHT);
+            let mut bar = ScrollBar::new(Rectangle::new(
+                Point::new(size.width as i32 - 6, 5),
+                Size::new(3, track_height),
+            ));
+            bar.set_range(
+                line_count * self.font_size,
+                track_height,
+                self.scroll_offset.max(0) as u32,
+            );
+            bar.draw(manager.target());
         }
     }
 
-    fn draw_status_bar(&mut self) {
-        use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_6X10};
-        use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
-
-        // 状态栏背景
-        let _ = Rectangle::new(Point::new(0, 123), Size::new(128, 5))
-            .into_styled(PrimitiveStyle::with_fill(DisplayColor::new(20, 20, 20)))
-            .draw(&mut self.manager.display);
-
-        // 状态栏文本
-        let style = MonoTextStyle::new(&FONT_6X10, DisplayColor::WHITE);
-
-        // 文件名
-        let filename_text = if self.current_file.len() > 12 {
-            format!("{}...", &self.current_file[..9])
-        } else {
-            self.current_file.clone()
-        };
-
-        let _ =
-            Text::new(&filename_text, Point::new(2, 124), style).draw(&mut self.manager.display);
+    fn draw_status_bar(&mut self, manager: &mut DisplayManager) {
+        let mut bar = StatusBar::bottom(manager.size());
+        bar.set_left(&self.current_file);
 
         // 滚动位置
         let docs = get_documents();
         if let Some(content) = docs.get(&self.current_file) {
             let line_count = content.lines().count();
-            let scroll_text = format!("{}/{}", self.scroll_offset / 24 + 1, line_count.max(1));
-
-            let _ = Text::new(&scroll_text, Point::new(100, 124), style)
-                .draw(&mut self.manager.display);
+            bar.set_right(&format!(
+                "{}/{}",
+                self.scroll_offset / self.font_size as i32 + 1,
+                line_count.max(1)
+            ));
         }
+        bar.draw(manager.target());
     }
 
     fn next_document(&mut self) {
@@ -416,58 +431,57 @@ impl<'a> TextReader<'a> {
         }
     }
 
-    fn select_file(&mut self) {
-        println!("\r\n=== 选择文件 ===");
+    fn select_file(&mut self, size: Size) {
         let docs = get_documents();
-        let mut files: Vec<&String> = docs.keys().collect();
+        let mut files: Vec<String> = docs.keys().cloned().collect();
         files.sort();
 
-        for (i, filename) in files.iter().enumerate() {
-            println!("  {} - {}", i + 1, filename);
+        // 屏幕上弹出文件列表，方向键选择，回车确认，q/Esc 取消
+        let mut list = List::new(
+            Rectangle::new(
+                Point::zero(),
+                size.saturating_sub(Size::new(0, StatusBar::HEIGHT)),
+            ),
+            files,
+        )
+        .title(tr!(i18n::PICKER_TITLE));
+        if let Some(current) = list.items().iter().position(|f| *f == self.current_file) {
+            list.select(current);
         }
-        println!("  0 - 取消");
-        print!("选择文件编号: ");
-
-        // 简单输入处理
-        let mut input = String::new();
-        let mut input_complete = false;
-
-        while !input_complete {
-            if let Some(byte) = Uart::read_byte_nonblock() {
-                match byte {
-                    b'\r' | b'\n' => {
-                        input_complete = true;
-                    }
-                    b'0'..=b'9' => {
-                        input.push(byte as char);
-                        print!("{}", byte as char);
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        println!();
+        self.picker = Some(list);
+    }
 
-        if let Ok(index) = input.parse::<usize>() {
-            if index > 0 && index <= files.len() {
-                self.current_file = files[index - 1].clone();
+    // 文件列表打开时的按键
+    fn picker_key(&mut self, byte: u8) -> Flow {
+        let (Some(list), Some(key)) = (self.picker.as_mut(), self.keys.feed(byte)) else {
+            return Flow::Continue;
+        };
+        match list.handle_key(key) {
+            Response::Changed => {}
+            Response::Activated(index) => {
+                self.current_file = list.items()[index].clone();
                 self.scroll_offset = 0;
-                println!("已选择: {}", self.current_file);
+                println!("{}", tr!(i18n::READER_SELECTED, self.current_file));
+                self.picker = None;
             }
+            Response::Cancelled => self.picker = None,
+            Response::Ignored => return Flow::Continue,
         }
+        Flow::Redraw
     }
 }
 
 // 文本编辑器
-pub(crate) struct TextEditor<'a> {
-    manager: &'a mut DisplayManager,
+pub(crate) struct TextEditor {
     font: Option<Font<'static>>,
     current_file: String,
     content: String,
     cursor_pos: (usize, usize), // (行, 列)
     scroll_offset: (i32, i32),  // (水平滚动, 垂直滚动)
+    font_size: u32,             // 字号，也是行高
     mode: EditorMode,
+    last_key: Option<u8>,   // 上一个键，dd 用
+    command_buffer: String, // 命令模式下输入的内容
 }
 
 #[derive(PartialEq)]
@@ -477,29 +491,37 @@ enum EditorMode {
     Command,
 }
 
-impl<'a> TextEditor<'a> {
-    pub fn new(manager: &'a mut DisplayManager) -> Self {
+impl TextEditor {
+    pub fn new() -> Self {
         Self {
-            manager,
             font: None,
             current_file: "demo.txt".to_string(),
             content: String::new(),
             cursor_pos: (0, 0),
             scroll_offset: (0, 0),
+            font_size: settings::number(Key::FontSize),
             mode: EditorMode::Normal,
+            last_key: None,
+            command_buffer: String::new(),
         }
     }
+}
 
-    pub fn run(&mut self) -> Result<(), core::convert::Infallible> {
+impl App for TextEditor {
+    fn name(&self) -> &'static str {
+        tr!(i18n::EDITOR_APP)
+    }
+
+    fn on_enter(&mut self, _manager: &mut DisplayManager) -> bool {
         // 加载字体
-        self.font = match Font::try_from_bytes(HARMONYOS_SANS_SC_LIGHT) {
+        self.font = match fonts::harmony_sans() {
             Some(font) => {
-                println!("字体加载成功");
+                log::debug!("{}", tr!(i18n::TEXT_FONT_LOADED));
                 Some(font)
             }
             None => {
-                println!("字体加载失败");
-                return Ok(());
+                log::error!("{}", tr!(i18n::TEXT_FONT_FAILED));
+                return false;
             }
         };
 
@@ -507,72 +529,41 @@ impl<'a> TextEditor<'a> {
         let current_file = self.current_file.to_string();
         self.load_file(&current_file);
 
-        println!("\r\n=== 文本编辑器 ===");
-        println!("模式: Normal (按 i 进入插入模式)");
-        println!("快捷键:");
-        println!("  i     - 进入插入模式");
-        println!("  Esc   - 返回Normal模式");
-        println!("  :     - 进入命令模式");
-        println!("  h/j/k/l - 左/下/上/右移动");
-        println!("  w/b   - 向前/后移动一个词");
-        println!("  0/$   - 行首/行尾");
-        println!("  dd    - 删除当前行");
-        println!("  x     - 删除字符");
-        println!("  u     - 撤销");
-        println!("  Ctrl+S - 保存");
-        println!("  :q    - 退出");
-        println!("==================\r\n");
-
-        self.display_editor()?;
-
-        // 主循环
-        let mut last_key: Option<u8> = None;
-        let mut command_buffer = String::new();
-
-        loop {
-            if let Some(byte) = Uart::read_byte_nonblock() {
-                match self.mode {
-                    EditorMode::Normal => {
-                        if self.handle_normal_mode(byte, &mut last_key)? {
-                            break;
-                        }
-                    }
-                    EditorMode::Insert => {
-                        if self.handle_insert_mode(byte)? {
-                            break;
-                        }
-                    }
-                    EditorMode::Command => {
-                        if self.handle_command_mode(byte, &mut command_buffer)? {
-                            break;
-                        }
-                    }
-                }
+        i18n::EDITOR_HELP.print();
+        true
+    }
 
-                self.display_editor()?;
-            }
+    fn on_key(&mut self, _manager: &mut DisplayManager, byte: u8) -> Flow {
+        let exit = match self.mode {
+            EditorMode::Normal => self.handle_normal_mode(byte),
+            EditorMode::Insert => self.handle_insert_mode(byte),
+            EditorMode::Command => self.handle_command_mode(byte),
+        };
+        match exit {
+            Ok(true) => Flow::Exit,
+            _ => Flow::Redraw,
         }
+    }
 
-        Ok(())
+    fn draw(&mut self, manager: &mut DisplayManager) {
+        let _ = self.display_editor(manager);
     }
+}
 
-    fn handle_normal_mode(
-        &mut self,
-        byte: u8,
-        last_key: &mut Option<u8>,
-    ) -> Result<bool, core::convert::Infallible> {
+impl TextEditor {
+    fn handle_normal_mode(&mut self, byte: u8) -> Result<bool, core::convert::Infallible> {
         match byte {
             b'i' | b'I' => {
-                println!("进入插入模式");
+                println!("{}", tr!(i18n::EDITOR_INSERT));
                 self.mode = EditorMode::Insert;
             }
             b':' => {
-                println!("进入命令模式");
+                println!("{}", tr!(i18n::EDITOR_COMMAND));
                 self.mode = EditorMode::Command;
                 print!(":");
             }
             b'q' | b'Q' => {
-                println!("\r\n退出编辑器");
+                println!("\r\n{}", tr!(i18n::EDITOR_EXIT));
                 return Ok(true);
             }
             b'h' => self.move_cursor_left(),
@@ -585,26 +576,26 @@ impl<'a> TextEditor<'a> {
             b'$' => self.move_to_line_end(),
             b'x' => self.delete_char(),
             b'd' => {
-                if let Some(b'd') = *last_key {
+                if let Some(b'd') = self.last_key {
                     self.delete_line();
-                    *last_key = None;
+                    self.last_key = None;
                 } else {
-                    *last_key = Some(b'd');
+                    self.last_key = Some(b'd');
                 }
             }
             b'u' => {
                 // 简单撤销 - 这里只是重新加载文件
                 let current_file = self.current_file.to_string();
                 self.load_file(&current_file);
-                println!("撤销");
+                println!("{}", tr!(i18n::EDITOR_UNDO));
             }
             b'\x13' => {
                 // Ctrl+S
                 self.save_file();
-                println!("文件已保存");
+                println!("{}", tr!(i18n::EDITOR_SAVED));
             }
             _ => {
-                *last_key = Some(byte);
+                self.last_key = Some(byte);
             }
         }
         Ok(false)
@@ -614,7 +605,7 @@ impl<'a> TextEditor<'a> {
         match byte {
             b'\x1b' => {
                 // Esc
-                println!("返回Normal模式");
+                println!("{}", tr!(i18n::EDITOR_NORMAL));
                 self.mode = EditorMode::Normal;
             }
             b'\r' | b'\n' => {
@@ -626,7 +617,7 @@ impl<'a> TextEditor<'a> {
             }
             b'\x04' => {
                 // Ctrl+D
-                println!("退出插入模式");
+                println!("{}", tr!(i18n::EDITOR_LEAVE_INSERT));
                 return Ok(true);
             }
             _ if byte >= 0x20 && byte <= 0x7e => {
@@ -638,16 +629,11 @@ impl<'a> TextEditor<'a> {
         Ok(false)
     }
 
-    fn handle_command_mode(
-        &mut self,
-        byte: u8,
-        buffer: &mut String,
-    ) -> Result<bool, core::convert::Infallible> {
+    fn handle_command_mode(&mut self, byte: u8) -> Result<bool, core::convert::Infallible> {
         match byte {
             b'\r' | b'\n' => {
                 println!();
-                let command = buffer.clone();
-                buffer.clear();
+                let command = core::mem::take(&mut self.command_buffer);
 
                 if self.execute_command(&command) {
                     return Ok(true);
@@ -658,19 +644,17 @@ impl<'a> TextEditor<'a> {
             b'\x1b' => {
                 // Esc
                 println!();
-                buffer.clear();
+                self.command_buffer.clear();
                 self.mode = EditorMode::Normal;
             }
-            b'\x08' | b'\x7f' => {
+            b'\x08' | b'\x7f' if !self.command_buffer.is_empty() => {
                 // Backspace
-                if !buffer.is_empty() {
-                    buffer.pop();
-                    print!("\x08 \x08");
-                }
+                self.command_buffer.pop();
+                print!("\x08 \x08");
             }
             _ if byte >= 0x20 && byte <= 0x7e => {
                 // 可打印字符
-                buffer.push(byte as char);
+                self.command_buffer.push(byte as char);
                 print!("{}", byte as char);
             }
             _ => {}
@@ -682,26 +666,26 @@ impl<'a> TextEditor<'a> {
         let trimmed = command.trim();
         match trimmed {
             "q" | "quit" => {
-                println!("退出编辑器");
+                println!("{}", tr!(i18n::EDITOR_EXIT));
                 return true;
             }
             "w" | "write" => {
                 self.save_file();
-                println!("文件已保存");
+                println!("{}", tr!(i18n::EDITOR_SAVED));
             }
             "wq" => {
                 self.save_file();
-                println!("文件已保存并退出");
+                println!("{}", tr!(i18n::EDITOR_SAVED_EXIT));
                 return true;
             }
             "e" | "edit" => {
                 // 重新加载当前文件
                 let current_file = self.current_file.to_string();
                 self.load_file(&current_file);
-                println!("重新加载文件");
+                println!("{}", tr!(i18n::EDITOR_RELOADED));
             }
             "ls" => {
-                println!("文件列表:");
+                println!("{}", tr!(i18n::EDITOR_FILES));
                 let docs = get_documents();
                 for filename in docs.keys() {
                     println!("  {}", filename);
@@ -711,24 +695,27 @@ impl<'a> TextEditor<'a> {
                 let filename = cmd[2..].trim();
                 self.current_file = filename.to_string();
                 self.load_file(filename);
-                println!("编辑文件: {}", filename);
+                println!("{}", tr!(i18n::EDITOR_EDITING, filename));
             }
             cmd if cmd.starts_with("w ") => {
                 let filename = cmd[2..].trim();
                 self.current_file = filename.to_string();
                 self.save_file_as(filename);
-                println!("另存为: {}", filename);
+                println!("{}", tr!(i18n::EDITOR_SAVED_AS, filename));
             }
             _ => {
-                println!("未知命令: {}", trimmed);
+                println!("{}", tr!(i18n::EDITOR_UNKNOWN, trimmed));
             }
         }
         false
     }
 
-    fn display_editor(&mut self) -> Result<(), core::convert::Infallible> {
+    fn display_editor(
+        &mut self,
+        manager: &mut DisplayManager,
+    ) -> Result<(), core::convert::Infallible> {
         // 清屏
-        let _ = self.manager.display.clear(DisplayColor::new(8, 8, 16));
+        let _ = manager.target().clear(theme::current().editor);
 
         // 创建字体样式
         let font = match &self.font {
@@ -737,8 +724,8 @@ impl<'a> TextEditor<'a> {
         };
 
         let style = FontTextStyleBuilder::new(font.clone())
-            .font_size(24) // 大号字体
-            .text_color(DisplayColor::WHITE)
+            .font_size(self.font_size) // 字号在设置里改（font.size）
+            .text_color(theme::current().text)
             .build();
 
         // 创建文本框样式
@@ -751,117 +738,105 @@ impl<'a> TextEditor<'a> {
             .build();
 
         // 计算显示区域（为状态栏留出空间）
+        let size = manager.size();
         let display_bounds = Rectangle::new(
             Point::new(5 - self.scroll_offset.0, 5 - self.scroll_offset.1),
-            Size::new(118, 100),
+            Size::new(
+                size.width.saturating_sub(10),
+                size.height.saturating_sub(10 + StatusBar::HEIGHT),
+            ),
         );
 
         // 创建并绘制文本框
         let text_box =
             TextBox::with_textbox_style(&self.content, display_bounds, style, textbox_style);
 
-        let _ = text_box.draw(&mut self.manager.display);
+        {
+            let _t = perf::scope("textbox");
+            let _ = text_box.draw(manager.target());
+        }
 
         // 绘制光标
-        self.draw_cursor();
+        self.draw_cursor(manager);
 
         // 绘制状态栏
-        self.draw_editor_status_bar();
+        self.draw_editor_status_bar(manager);
 
         // 绘制滚动条
-        self.draw_editor_scrollbar();
+        self.draw_editor_scrollbar(manager);
 
         Ok(())
     }
 
-    fn draw_cursor(&mut self) {
+    fn draw_cursor(&mut self, manager: &mut DisplayManager) {
         use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
 
         // 计算光标位置
         let lines: Vec<&str> = self.content.lines().collect();
         if self.cursor_pos.0 < lines.len() {
             let current_line = lines[self.cursor_pos.0];
-            let prefix = if self.cursor_pos.1 < current_line.len() {
-                &current_line[..self.cursor_pos.1]
-            } else {
-                current_line
-            };
+            // 光标列是字符下标，不是字节下标
+            let column = self.cursor_pos.1.min(current_line.chars().count());
 
-            // 计算光标X位置（简单估算，每个字符12像素宽）
-            let cursor_x = 5 + (prefix.chars().count() as i32 * 12) - self.scroll_offset.0;
-            let cursor_y = 5 + (self.cursor_pos.0 as i32 * 24) - self.scroll_offset.1;
+            // 计算光标X位置（简单估算，每个字符半个字号宽）
+            let line_height = self.font_size as i32;
+            let cursor_x = 5 + (column as i32 * line_height / 2) - self.scroll_offset.0;
+            let cursor_y = 5 + (self.cursor_pos.0 as i32 * line_height) - self.scroll_offset.1;
 
             // 绘制光标（根据模式不同显示不同样式）
             let cursor_color = match self.mode {
-                EditorMode::Insert => DisplayColor::GREEN,
-                _ => DisplayColor::CYAN,
+                EditorMode::Insert => theme::current().insert,
+                _ => theme::current().accent,
             };
 
-            let _ = Rectangle::new(Point::new(cursor_x.max(5), cursor_y), Size::new(2, 24))
-                .into_styled(PrimitiveStyle::with_fill(cursor_color))
-                .draw(&mut self.manager.display);
+            let _ = Rectangle::new(
+                Point::new(cursor_x.max(5), cursor_y),
+                Size::new(2, self.font_size),
+            )
+            .into_styled(PrimitiveStyle::with_fill(cursor_color))
+            .draw(manager.target());
         }
     }
 
-    fn draw_editor_status_bar(&mut self) {
-        use embedded_graphics::mono_font::{MonoTextStyle, ascii::FONT_6X10};
-        use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
-
-        // 状态栏背景
-        let _ = Rectangle::new(Point::new(0, 108), Size::new(128, 20))
-            .into_styled(PrimitiveStyle::with_fill(DisplayColor::new(10, 10, 20)))
-            .draw(&mut self.manager.display);
-
-        let style = MonoTextStyle::new(&FONT_6X10, DisplayColor::WHITE);
+    fn draw_editor_status_bar(&mut self, manager: &mut DisplayManager) {
+        let mut bar = StatusBar::bottom(manager.size()).background(theme::current().editor_bar);
 
         // 模式指示器
-        let mode_text = match self.mode {
+        bar.set_left(match self.mode {
             EditorMode::Normal => "NORMAL",
             EditorMode::Insert => "INSERT",
             EditorMode::Command => "COMMAND",
-        };
-
-        let _ = Text::new(mode_text, Point::new(2, 110), style).draw(&mut self.manager.display);
+        });
 
-        // 文件名
-        let filename_text = if self.current_file.len() > 10 {
-            format!("{}...", &self.current_file[..7])
-        } else {
-            self.current_file.clone()
-        };
-
-        let _ =
-            Text::new(&filename_text, Point::new(50, 110), style).draw(&mut self.manager.display);
+        // 文件名和行数
+        let line_count = self.content.lines().count();
+        bar.set_center(&format!("{} {}L", self.current_file, line_count));
 
         // 光标位置
-        let pos_text = format!("{}:{}", self.cursor_pos.0 + 1, self.cursor_pos.1 + 1);
-
-        let _ = Text::new(&pos_text, Point::new(100, 110), style).draw(&mut self.manager.display);
-
-        // 行数统计
-        let line_count = self.content.lines().count();
-        let lines_text = format!("{}L", line_count);
+        bar.set_right(&format!(
+            "{}:{}",
+            self.cursor_pos.0 + 1,
+            self.cursor_pos.1 + 1
+        ));
 
-        let _ = Text::new(&lines_text, Point::new(2, 120), style).draw(&mut self.manager.display);
+        bar.draw(manager.target());
     }
 
-    fn draw_editor_scrollbar(&mut self) {
-        use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
-
-        let line_count = self.content.lines().count() as i32;
-        let visible_lines = 4; // 大约4行24像素字体
-
-        if line_count > visible_lines {
-            // 水平滚动条
-            let _ = Rectangle::new(Point::new(0, 105), Size::new(128, 2))
-                .into_styled(PrimitiveStyle::with_fill(DisplayColor::new(40, 40, 40)))
-                .draw(&mut self.manager.display);
-
-            // 垂直滚动条
-            let _ = Rectangle::new(Point::new(126, 0), Size::new(2, 108))
-                .into_styled(PrimitiveStyle::with_fill(DisplayColor::new(40, 40, 40)))
-                .draw(&mut self.manager.display);
-        }
+    fn draw_editor_scrollbar(&mut self, manager: &mut DisplayManager) {
+        let line_count = self.content.lines().count() as u32;
+        let size = manager.size();
+        let text_height = size.height.saturating_sub(10 + StatusBar::HEIGHT);
+
+        let mut bar = ScrollBar::new(Rectangle::new(
+            Point::new(size.width as i32 - 2, 0),
+            Size::new(2, text_height + 5),
+        ));
+        bar.set_range(
+            line_count * self.font_size,
+            text_height,
+            self.scroll_offset.1.max(0) as u32,
+        );
+        bar.draw(manager.target());
     }
 
     // 编辑器操作函数
@@ -929,7 +904,7 @@ impl<'a> TextEditor<'a> {
         let lines: Vec<&str> = self.content.lines().collect();
         if self.cursor_pos.0 < lines.len() {
             let line = lines[self.cursor_pos.0];
-            let remaining = &line[self.cursor_pos.1..];
+            let remaining = &line[byte_offset(line, self.cursor_pos.1)..];
 
             // 找到下一个单词的开始
             let mut in_word = false;
@@ -961,7 +936,9 @@ impl<'a> TextEditor<'a> {
         if self.cursor_pos.1 > 0 {
             let lines: Vec<&str> = self.content.lines().collect();
             let line = lines[self.cursor_pos.0];
-            let before: Vec<char> = line[..self.cursor_pos.1].chars().collect();
+            let before: Vec<char> = line[..byte_offset(line, self.cursor_pos.1)]
+                .chars()
+                .collect();
 
             // 反向查找单词开始
             let mut last_alpha = None;
@@ -0,0 +1,113 @@
+//! 主题
+//!
+//! 阅读器、编辑器、字体演示、贪吃蛇状态栏和小部件的背景色、文字色都从当前主题取，
+//! 不再各自写死；`theme` 命令切换，下次画的时候生效。
+//! 演示里的彩色文字（彩虹字、红绿标题）是内容的一部分，不跟主题走。
+
+use crate::*;
+
+use crate::color::{palette, rgb};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use embedded_graphics::prelude::RgbColor;
+
+/// 一套配色
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Theme {
+    pub name: &'static str,
+    /// 普通画面（菜单、字体演示）的背景
+    pub background: DisplayColor,
+    /// 正文
+    pub text: DisplayColor,
+    /// 强调色：选中项、滚动条滑块、光标
+    pub accent: DisplayColor,
+    /// 强调色上面的文字
+    pub on_accent: DisplayColor,
+    /// 次要元素：滚动条轨道、进度条外框、没有焦点的选中项
+    pub muted: DisplayColor,
+    /// 状态栏、按钮
+    pub surface: DisplayColor,
+    /// 大块的彩色背景（字体大小演示）
+    #[cfg_attr(not(feature = "cmd-font"), allow(dead_code))]
+    pub panel: DisplayColor,
+    /// 阅读器背景
+    #[cfg_attr(not(feature = "cmd-text"), allow(dead_code))]
+    pub reader: DisplayColor,
+    /// 编辑器背景
+    #[cfg_attr(not(feature = "cmd-text"), allow(dead_code))]
+    pub editor: DisplayColor,
+    /// 编辑器状态栏
+    #[cfg_attr(not(feature = "cmd-text"), allow(dead_code))]
+    pub editor_bar: DisplayColor,
+    /// 插入模式的光标
+    #[cfg_attr(not(feature = "cmd-text"), allow(dead_code))]
+    pub insert: DisplayColor,
+}
+
+/// 深色，也就是原来各模块写死的那些颜色
+pub(crate) const DARK: Theme = Theme {
+    name: "dark",
+    background: DisplayColor::BLACK,
+    text: DisplayColor::WHITE,
+    accent: DisplayColor::CYAN,
+    on_accent: DisplayColor::BLACK,
+    muted: palette::GRAY,
+    surface: palette::DARK_GRAY,
+    panel: palette::DARK_BLUE,
+    reader: palette::NAVY,
+    editor: palette::MIDNIGHT,
+    editor_bar: palette::SLATE,
+    insert: DisplayColor::GREEN,
+};
+
+/// 浅色，白底黑字
+pub(crate) const LIGHT: Theme = Theme {
+    name: "light",
+    background: DisplayColor::WHITE,
+    text: DisplayColor::BLACK,
+    accent: rgb(0, 96, 192),
+    on_accent: DisplayColor::WHITE,
+    muted: rgb(160, 160, 160),
+    surface: rgb(208, 208, 208),
+    panel: rgb(200, 220, 255),
+    reader: rgb(250, 244, 228),
+    editor: rgb(244, 244, 244),
+    editor_bar: rgb(176, 188, 212),
+    insert: rgb(0, 160, 0),
+};
+
+/// 高对比度，黑底白字黄色强调，阳光下也看得清
+pub(crate) const HIGH_CONTRAST: Theme = Theme {
+    name: "contrast",
+    background: DisplayColor::BLACK,
+    text: DisplayColor::WHITE,
+    accent: DisplayColor::YELLOW,
+    on_accent: DisplayColor::BLACK,
+    muted: DisplayColor::WHITE,
+    surface: palette::DARK_BLUE,
+    panel: DisplayColor::BLACK,
+    reader: DisplayColor::BLACK,
+    editor: DisplayColor::BLACK,
+    editor_bar: palette::DARK_BLUE,
+    insert: DisplayColor::GREEN,
+};
+
+/// 所有主题，第一个是默认的
+pub(crate) const THEMES: &[Theme] = &[DARK, LIGHT, HIGH_CONTRAST];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// 当前主题
+pub(crate) fn current() -> &'static Theme {
+    &THEMES[CURRENT.load(Ordering::Relaxed).min(THEMES.len() - 1)]
+}
+
+/// 按名字切换主题，没有这个主题时返回 `false`
+pub(crate) fn set(name: &str) -> bool {
+    match THEMES.iter().position(|t| t.name == name) {
+        Some(index) => {
+            CURRENT.store(index, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
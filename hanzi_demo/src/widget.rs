@@ -0,0 +1,788 @@
+//! 小部件
+//!
+//! 状态栏、列表菜单、对话框、进度条、滚动条这些界面元素。部件自己记着位置和内容（保留模式），
+//! 内容变了再 `draw` 一次就行，各个界面不用再自己算坐标画矩形和 `FONT_6X10` 文字。
+//!
+//! 按键先经过 [`KeyDecoder`] 翻译成 [`Key`]，再交给有焦点的部件，部件用 [`Response`] 告诉调用方发生了什么。
+//! 文字默认是 6x10 的等宽 ASCII 字体；带了字体演示或阅读器时，有非 ASCII 字符的文字改用 TTF 画，
+//! 翻译过的中文标签也能显示。颜色默认跟当前主题走。
+
+use crate::*;
+
+use crate::theme;
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+use embedded_graphics::text::renderer::TextRenderer;
+use embedded_graphics::{
+    mono_font::{MonoTextStyle, ascii::FONT_6X10},
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+use embedded_ttf::{FontTextStyle, FontTextStyleBuilder};
+
+/// 字符宽度
+const CHAR_WIDTH: u32 = 6;
+/// 一行文字的高度（字高 10 + 上下各 1 像素）
+const LINE_HEIGHT: u32 = 12;
+/// 非 ASCII 文字用的 TTF 字号，正好一行高
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+const TTF_SIZE: u32 = LINE_HEIGHT;
+
+// ===========================================
+// 按键
+// ============================================
+
+/// 部件认识的按键
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// 回车或空格
+    Enter,
+    /// q 或 Esc
+    Back,
+    /// 其他可打印字符和控制字符
+    Char(u8),
+}
+
+impl Key {
+    /// 单个字节对应的按键，w/a/s/d 和 h/j/k/l 都当方向键
+    pub fn from_byte(byte: u8) -> Self {
+        match byte {
+            b'w' | b'W' | b'k' | b'K' => Key::Up,
+            b's' | b'S' | b'j' | b'J' => Key::Down,
+            b'a' | b'A' | b'h' | b'H' => Key::Left,
+            b'd' | b'D' | b'l' | b'L' => Key::Right,
+            b'\r' | b'\n' | b' ' => Key::Enter,
+            b'q' | b'Q' => Key::Back,
+            other => Key::Char(other),
+        }
+    }
+}
+
+/// 把串口字节翻译成按键，认得终端方向键的 `ESC [ A` 序列
+///
+/// 单按 Esc 时要等下一个字节才知道不是方向键，那个字节会被吞掉，当成一次 [`Key::Back`]。
+#[derive(Default)]
+pub(crate) struct KeyDecoder {
+    state: u8,
+}
+
+impl KeyDecoder {
+    const IDLE: u8 = 0;
+    const ESC: u8 = 1;
+    const CSI: u8 = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 喂一个字节，凑成一个按键时返回
+    pub fn feed(&mut self, byte: u8) -> Option<Key> {
+        match (self.state, byte) {
+            (Self::IDLE, 0x1b) => {
+                self.state = Self::ESC;
+                None
+            }
+            (Self::IDLE, _) => Some(Key::from_byte(byte)),
+            (Self::ESC, b'[') => {
+                self.state = Self::CSI;
+                None
+            }
+            (Self::ESC, _) => {
+                self.state = Self::IDLE;
+                Some(Key::Back)
+            }
+            (_, _) => {
+                self.state = Self::IDLE;
+                match byte {
+                    b'A' => Some(Key::Up),
+                    b'B' => Some(Key::Down),
+                    b'C' => Some(Key::Right),
+                    b'D' => Some(Key::Left),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// 部件处理按键的结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Response {
+    /// 不关心这个键，调用方自己处理
+    Ignored,
+    /// 状态变了，需要重画
+    Changed,
+    /// 选中了第几项（列表项、对话框按钮）
+    Activated(usize),
+    /// 取消/返回
+    Cancelled,
+}
+
+/// 部件
+pub(crate) trait Widget {
+    /// 按当前状态画出来
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D);
+
+    /// 有焦点时收到的按键
+    fn handle_key(&mut self, _key: Key) -> Response {
+        Response::Ignored
+    }
+}
+
+fn text_style(color: DisplayColor) -> MonoTextStyle<'static, DisplayColor> {
+    MonoTextStyle::new(&FONT_6X10, color)
+}
+
+// 有非 ASCII 字符时用的 TTF 样式，没带字体或者字体解析失败就是 `None`
+#[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+fn ttf_style(text: &str, color: DisplayColor) -> Option<FontTextStyle<DisplayColor>> {
+    if text.is_ascii() {
+        return None;
+    }
+    let font = crate::fonts::harmony_sans()?;
+    Some(
+        FontTextStyleBuilder::new(font)
+            .font_size(TTF_SIZE)
+            .text_color(color)
+            .build(),
+    )
+}
+
+// 文字的像素宽度
+fn text_width(text: &str) -> u32 {
+    #[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+    if let Some(style) = ttf_style(text, theme::current().text) {
+        return style
+            .measure_string(text, Point::zero(), Baseline::Top)
+            .bounding_box
+            .size
+            .width;
+    }
+    text.chars().count() as u32 * CHAR_WIDTH
+}
+
+// 画一行字，`position` 是按 `alignment` 对齐的点，竖直方向在文字中线上
+fn draw_text<D: DrawTarget<Color = DisplayColor>>(
+    target: &mut D,
+    text: &str,
+    position: Point,
+    alignment: Alignment,
+    color: DisplayColor,
+) {
+    let style = TextStyleBuilder::new()
+        .alignment(alignment)
+        .baseline(Baseline::Middle)
+        .build();
+    #[cfg(any(feature = "cmd-font", feature = "cmd-text"))]
+    if let Some(ttf) = ttf_style(text, color) {
+        // embedded-ttf 不管基线，总是从字的顶边往下画
+        let top = position - Point::new(0, TTF_SIZE as i32 / 2);
+        let _ = Text::with_text_style(text, top, ttf, style).draw(target);
+        return;
+    }
+    let _ = Text::with_text_style(text, position, text_style(color), style).draw(target);
+}
+
+// 超出 `width` 像素的文字截掉，末尾换成 ".."
+fn fit(text: &str, width: u32) -> String {
+    if !text.is_ascii() {
+        if text_width(text) <= width {
+            return text.to_string();
+        }
+        let mut out = String::new();
+        for c in text.chars() {
+            out.push(c);
+            if text_width(&out) + 2 * CHAR_WIDTH > width {
+                out.pop();
+                break;
+            }
+        }
+        out.push_str("..");
+        return out;
+    }
+    let max = (width / CHAR_WIDTH) as usize;
+    if text.chars().count() <= max {
+        text.to_string()
+    } else if max <= 2 {
+        text.chars().take(max).collect()
+    } else {
+        let mut out: String = text.chars().take(max - 2).collect();
+        out.push_str("..");
+        out
+    }
+}
+
+fn fill<D: DrawTarget<Color = DisplayColor>>(target: &mut D, area: Rectangle, color: DisplayColor) {
+    let _ = area
+        .into_styled(PrimitiveStyle::with_fill(color))
+        .draw(target);
+}
+
+// ===========================================
+// 标签
+// ============================================
+
+/// 一行文字，在 `bounds` 里按 `alignment` 对齐、垂直居中
+pub(crate) struct Label {
+    bounds: Rectangle,
+    text: String,
+    color: DisplayColor,
+    background: Option<DisplayColor>,
+    alignment: Alignment,
+}
+
+impl Label {
+    pub fn new(bounds: Rectangle, text: &str) -> Self {
+        Self {
+            bounds,
+            text: text.to_string(),
+            color: theme::current().text,
+            background: None,
+            alignment: Alignment::Left,
+        }
+    }
+
+    /// 在屏幕正中放一行字
+    #[cfg_attr(not(feature = "cmd-snake"), allow(dead_code))]
+    pub fn centered(size: Size, text: &str) -> Self {
+        let width = text_width(text).min(size.width);
+        let bounds = Rectangle::with_center(
+            Rectangle::new(Point::zero(), size).center(),
+            Size::new(width + 4, LINE_HEIGHT),
+        );
+        Self::new(bounds, text).alignment(Alignment::Center)
+    }
+
+    pub fn color(mut self, color: DisplayColor) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// 画字之前先把整块涂成这个颜色，不设就直接叠在原来的画面上
+    pub fn background(mut self, color: DisplayColor) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+}
+
+impl Widget for Label {
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D) {
+        if let Some(background) = self.background {
+            fill(target, self.bounds, background);
+        }
+        let x = match self.alignment {
+            Alignment::Left => self.bounds.top_left.x + 1,
+            Alignment::Center => self.bounds.center().x,
+            Alignment::Right => self.bounds.top_left.x + self.bounds.size.width as i32 - 1,
+        };
+        draw_text(
+            target,
+            &fit(&self.text, self.bounds.size.width),
+            Point::new(x, self.bounds.center().y),
+            self.alignment,
+            self.color,
+        );
+    }
+}
+
+// ===========================================
+// 状态栏
+// ============================================
+
+/// 贴着屏幕顶边或底边的一条栏，左、中、右三段文字
+pub(crate) struct StatusBar {
+    bounds: Rectangle,
+    foreground: DisplayColor,
+    background: DisplayColor,
+    left: String,
+    center: String,
+    right: String,
+}
+
+impl StatusBar {
+    /// 状态栏高度
+    pub const HEIGHT: u32 = LINE_HEIGHT;
+
+    #[cfg_attr(not(feature = "cmd-snake"), allow(dead_code))]
+    pub fn top(size: Size) -> Self {
+        Self::new(Rectangle::new(
+            Point::zero(),
+            Size::new(size.width, Self::HEIGHT),
+        ))
+    }
+
+    pub fn bottom(size: Size) -> Self {
+        Self::new(Rectangle::new(
+            Point::new(0, size.height as i32 - Self::HEIGHT as i32),
+            Size::new(size.width, Self::HEIGHT),
+        ))
+    }
+
+    fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            foreground: theme::current().text,
+            background: theme::current().surface,
+            left: String::new(),
+            center: String::new(),
+            right: String::new(),
+        }
+    }
+
+    #[cfg_attr(
+        not(any(feature = "cmd-snake", feature = "cmd-text")),
+        allow(dead_code)
+    )]
+    pub fn background(mut self, color: DisplayColor) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn set_left(&mut self, text: &str) {
+        self.left = text.to_string();
+    }
+
+    #[cfg_attr(
+        not(any(feature = "cmd-snake", feature = "cmd-text")),
+        allow(dead_code)
+    )]
+    pub fn set_center(&mut self, text: &str) {
+        self.center = text.to_string();
+    }
+
+    pub fn set_right(&mut self, text: &str) {
+        self.right = text.to_string();
+    }
+}
+
+impl Widget for StatusBar {
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D) {
+        fill(target, self.bounds, self.background);
+
+        // 右边和左边先占位，中间用剩下的
+        let width = self.bounds.size.width;
+        let right_width = text_width(&self.right).min(width / 2);
+        let left_width = text_width(&self.left).min(width.saturating_sub(right_width));
+        let center_width = width.saturating_sub(left_width + right_width + 2 * CHAR_WIDTH);
+
+        let y = self.bounds.center().y;
+        let x0 = self.bounds.top_left.x;
+        let slots = [
+            (&self.left, left_width, Alignment::Left, x0 + 1),
+            (
+                &self.center,
+                center_width,
+                Alignment::Center,
+                x0 + width as i32 / 2,
+            ),
+            (
+                &self.right,
+                right_width,
+                Alignment::Right,
+                x0 + width as i32 - 1,
+            ),
+        ];
+        for (text, slot_width, alignment, x) in slots {
+            if text.is_empty() || slot_width == 0 {
+                continue;
+            }
+            draw_text(
+                target,
+                &fit(text, slot_width),
+                Point::new(x, y),
+                alignment,
+                self.foreground,
+            );
+        }
+    }
+}
+
+// ===========================================
+// 进度条
+// ============================================
+
+/// 带外框的横向进度条
+pub(crate) struct ProgressBar {
+    bounds: Rectangle,
+    value: u8,
+    color: DisplayColor,
+    frame: DisplayColor,
+}
+
+impl ProgressBar {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            value: 0,
+            color: DisplayColor::GREEN,
+            frame: theme::current().muted,
+        }
+    }
+
+    pub fn set_value(&mut self, value: u8) {
+        self.value = value.min(100);
+    }
+}
+
+impl Widget for ProgressBar {
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D) {
+        // 太矮的进度条不画外框
+        let inner = if self.bounds.size.height >= 5 {
+            let _ = self
+                .bounds
+                .into_styled(PrimitiveStyle::with_stroke(self.frame, 1))
+                .draw(target);
+            self.bounds.offset(-2)
+        } else {
+            self.bounds
+        };
+
+        let filled = inner.size.width * self.value as u32 / 100;
+        fill(
+            target,
+            Rectangle::new(inner.top_left, Size::new(filled, inner.size.height)),
+            self.color,
+        );
+        fill(
+            target,
+            Rectangle::new(
+                inner.top_left + Point::new(filled as i32, 0),
+                Size::new(inner.size.width - filled, inner.size.height),
+            ),
+            theme::current().background,
+        );
+    }
+}
+
+// ===========================================
+// 滚动条
+// ============================================
+
+/// 竖直滚动条，内容比可视区域高时才画
+pub(crate) struct ScrollBar {
+    bounds: Rectangle,
+    /// 内容总高度
+    total: u32,
+    /// 可视区域高度
+    visible: u32,
+    /// 当前滚动位置
+    position: u32,
+}
+
+impl ScrollBar {
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds,
+            total: 0,
+            visible: 0,
+            position: 0,
+        }
+    }
+
+    pub fn set_range(&mut self, total: u32, visible: u32, position: u32) {
+        self.total = total;
+        self.visible = visible;
+        self.position = position;
+    }
+}
+
+impl Widget for ScrollBar {
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D) {
+        if self.total <= self.visible || self.total == 0 {
+            return;
+        }
+        fill(target, self.bounds, theme::current().muted);
+
+        let track = self.bounds.size.height;
+        let thumb = (track * self.visible / self.total).max(6).min(track);
+        let travel = self.total - self.visible;
+        let offset = (track - thumb) * self.position.min(travel) / travel;
+        fill(
+            target,
+            Rectangle::new(
+                self.bounds.top_left + Point::new(0, offset as i32),
+                Size::new(self.bounds.size.width, thumb),
+            ),
+            theme::current().accent,
+        );
+    }
+}
+
+// ===========================================
+// 列表/菜单
+// ============================================
+
+/// 可以上下选择的列表，带标题就是菜单
+pub(crate) struct List {
+    bounds: Rectangle,
+    title: Option<String>,
+    items: Vec<String>,
+    selected: usize,
+    /// 第一行显示的是第几项
+    scroll: usize,
+}
+
+impl List {
+    pub fn new(bounds: Rectangle, items: Vec<String>) -> Self {
+        Self {
+            bounds,
+            title: None,
+            items,
+            selected: 0,
+            scroll: 0,
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    #[cfg_attr(not(feature = "cmd-text"), allow(dead_code))]
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    fn title_height(&self) -> u32 {
+        if self.title.is_some() { LINE_HEIGHT } else { 0 }
+    }
+
+    fn visible_rows(&self) -> usize {
+        ((self.bounds.size.height.saturating_sub(self.title_height())) / LINE_HEIGHT).max(1)
+            as usize
+    }
+
+    fn scroll_to_selected(&mut self) {
+        let rows = self.visible_rows();
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+    }
+}
+
+impl Widget for List {
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D) {
+        let theme = theme::current();
+        fill(target, self.bounds, theme.background);
+
+        let x = self.bounds.top_left.x;
+        let width = self.bounds.size.width;
+        let mut y = self.bounds.top_left.y;
+
+        if let Some(title) = &self.title {
+            Label::new(
+                Rectangle::new(Point::new(x, y), Size::new(width, LINE_HEIGHT)),
+                title,
+            )
+            .color(theme.background)
+            .background(theme.text)
+            .alignment(Alignment::Center)
+            .draw(target);
+            y += LINE_HEIGHT as i32;
+        }
+
+        let rows = self.visible_rows();
+        let scrollable = self.items.len() > rows;
+        // 放不下时右边留 2 像素画滚动条
+        let row_width = if scrollable { width - 3 } else { width };
+
+        for (i, item) in self.items.iter().enumerate().skip(self.scroll).take(rows) {
+            let row = Rectangle::new(Point::new(x, y), Size::new(row_width, LINE_HEIGHT));
+            let mut label = Label::new(row, item);
+            if i == self.selected {
+                label = label.color(theme.on_accent).background(theme.accent);
+            }
+            label.draw(target);
+            y += LINE_HEIGHT as i32;
+        }
+
+        if scrollable {
+            let top = self.bounds.top_left.y + self.title_height() as i32;
+            let mut bar = ScrollBar::new(Rectangle::new(
+                Point::new(x + width as i32 - 2, top),
+                Size::new(2, rows as u32 * LINE_HEIGHT),
+            ));
+            bar.set_range(self.items.len() as u32, rows as u32, self.scroll as u32);
+            bar.draw(target);
+        }
+    }
+
+    fn handle_key(&mut self, key: Key) -> Response {
+        if self.items.is_empty() {
+            return match key {
+                Key::Back => Response::Cancelled,
+                _ => Response::Ignored,
+            };
+        }
+        match key {
+            // 到头了绕回去
+            Key::Up => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(self.items.len() - 1);
+                self.scroll_to_selected();
+                Response::Changed
+            }
+            Key::Down => {
+                self.selected = (self.selected + 1) % self.items.len();
+                self.scroll_to_selected();
+                Response::Changed
+            }
+            Key::Enter | Key::Right => Response::Activated(self.selected),
+            Key::Back | Key::Left => Response::Cancelled,
+            // 数字键直接选第几项
+            Key::Char(c @ b'1'..=b'9') if ((c - b'1') as usize) < self.items.len() => {
+                self.select((c - b'1') as usize);
+                Response::Activated(self.selected)
+            }
+            Key::Char(_) => Response::Ignored,
+        }
+    }
+}
+
+// ===========================================
+// 对话框
+// ============================================
+
+/// 屏幕中间的模态框：标题、几行说明，可选一排按钮
+#[cfg_attr(not(feature = "cmd-snake"), allow(dead_code))]
+pub(crate) struct Dialog {
+    bounds: Rectangle,
+    title: String,
+    lines: Vec<String>,
+    buttons: Vec<String>,
+    selected: usize,
+}
+
+impl Dialog {
+    /// 按内容算好大小，放在 `size` 大小的屏幕正中
+    #[cfg_attr(not(feature = "cmd-snake"), allow(dead_code))]
+    pub fn new(size: Size, title: &str, lines: &[&str], buttons: &[&str]) -> Self {
+        let button_width: u32 = buttons
+            .iter()
+            .map(|b| text_width(b) + 2 * CHAR_WIDTH + 4)
+            .sum();
+        let lines_width = lines
+            .iter()
+            .chain(core::iter::once(&title))
+            .map(|l| text_width(l))
+            .max()
+            .unwrap_or(0);
+        let width = (lines_width.max(button_width) + 12).min(size.width);
+
+        let button_row = if buttons.is_empty() {
+            0
+        } else {
+            LINE_HEIGHT + 2
+        };
+        let height = (LINE_HEIGHT * (1 + lines.len() as u32) + button_row + 6).min(size.height);
+
+        Self {
+            bounds: Rectangle::with_center(
+                Rectangle::new(Point::zero(), size).center(),
+                Size::new(width, height),
+            ),
+            title: title.to_string(),
+            lines: lines.iter().map(|l| l.to_string()).collect(),
+            buttons: buttons.iter().map(|b| b.to_string()).collect(),
+            selected: 0,
+        }
+    }
+}
+
+impl Widget for Dialog {
+    fn draw<D: DrawTarget<Color = DisplayColor>>(&self, target: &mut D) {
+        let theme = theme::current();
+        fill(target, self.bounds, theme.background);
+        let _ = self
+            .bounds
+            .into_styled(PrimitiveStyle::with_stroke(theme.text, 1))
+            .draw(target);
+
+        let inner = self.bounds.offset(-2);
+        let x = inner.top_left.x;
+        let width = inner.size.width;
+        let mut y = inner.top_left.y;
+
+        Label::new(
+            Rectangle::new(Point::new(x, y), Size::new(width, LINE_HEIGHT)),
+            &self.title,
+        )
+        .color(theme.background)
+        .background(theme.text)
+        .alignment(Alignment::Center)
+        .draw(target);
+        y += LINE_HEIGHT as i32 + 1;
+
+        for line in &self.lines {
+            Label::new(
+                Rectangle::new(Point::new(x, y), Size::new(width, LINE_HEIGHT)),
+                line,
+            )
+            .alignment(Alignment::Center)
+            .draw(target);
+            y += LINE_HEIGHT as i32;
+        }
+
+        if self.buttons.is_empty() {
+            return;
+        }
+
+        // 按钮一排居中
+        let widths: Vec<u32> = self
+            .buttons
+            .iter()
+            .map(|b| text_width(b) + 2 * CHAR_WIDTH)
+            .collect();
+        let total: u32 = widths.iter().sum::<u32>() + 4 * (widths.len() as u32 - 1);
+        let mut bx = inner.center().x - total as i32 / 2;
+        for (i, (button, w)) in self.buttons.iter().zip(widths).enumerate() {
+            let mut label = Label::new(
+                Rectangle::new(Point::new(bx, y + 1), Size::new(w, LINE_HEIGHT)),
+                button,
+            )
+            .alignment(Alignment::Center);
+            label = if i == self.selected {
+                label.color(theme.on_accent).background(theme.accent)
+            } else {
+                label.background(theme.surface)
+            };
+            label.draw(target);
+            bx += w as i32 + 4;
+        }
+    }
+
+    fn handle_key(&mut self, key: Key) -> Response {
+        match key {
+            Key::Left if !self.buttons.is_empty() => {
+                self.selected = self
+                    .selected
+                    .checked_sub(1)
+                    .unwrap_or(self.buttons.len() - 1);
+                Response::Changed
+            }
+            Key::Right if !self.buttons.is_empty() => {
+                self.selected = (self.selected + 1) % self.buttons.len();
+                Response::Changed
+            }
+            Key::Enter if !self.buttons.is_empty() => Response::Activated(self.selected),
+            Key::Back => Response::Cancelled,
+            _ => Response::Ignored,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! 把板子上 `screenshot` 命令打出来的串口日志转成 PNG
+//!
+//! ```text
+//! shot2png [-f] <串口日志> [输出.png]
+//! ```
+//!
+//! 输出文件默认和日志同名；一份日志里有好几张图时依次加上 `-1`、`-2` 后缀。
+//! 有坏块或缺块的图默认不写，加 `-f` 照样写出来（坏的地方是黑的）。
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use hanzi_tools::{capture, png};
+
+fn usage() -> ExitCode {
+    eprintln!("用法: shot2png [-f] <串口日志> [输出.png]");
+    ExitCode::from(2)
+}
+
+fn main() -> ExitCode {
+    let mut force = false;
+    let mut paths = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "-f" | "--force" => force = true,
+            "-h" | "--help" => return usage(),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    let (input, output) = match paths.as_slice() {
+        [input] => (input.clone(), input.with_extension("png")),
+        [input, output] => (input.clone(), output.clone()),
+        _ => return usage(),
+    };
+
+    // 串口日志里可能有乱码，按有损 UTF-8 读
+    let log = match std::fs::read(&input) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(e) => {
+            eprintln!("读取 {} 失败: {}", input.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let captures = capture::parse(&log);
+    if captures.is_empty() {
+        eprintln!("{} 里没有找到截图", input.display());
+        return ExitCode::FAILURE;
+    }
+
+    let mut ok = true;
+    for (i, shot) in captures.iter().enumerate() {
+        let path = if captures.len() == 1 {
+            output.clone()
+        } else {
+            numbered(&output, i + 1)
+        };
+
+        for problem in &shot.problems {
+            eprintln!("{}: {}", path.display(), problem);
+        }
+        if !shot.is_intact() {
+            ok = false;
+            if !force {
+                eprintln!("{}: 截图不完整，跳过（加 -f 强制输出）", path.display());
+                continue;
+            }
+        }
+
+        match write_png(&path, shot) {
+            Ok(()) => println!(
+                "{}: {}x{} {:?}",
+                path.display(),
+                shot.width,
+                shot.height,
+                shot.format
+            ),
+            Err(e) => {
+                eprintln!("写入 {} 失败: {}", path.display(), e);
+                ok = false;
+            }
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn write_png(path: &Path, shot: &capture::Capture) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    png::write_rgb(&mut out, shot.width, shot.height, &shot.to_rgb())
+}
+
+// out.png -> out-1.png
+fn numbered(path: &Path, n: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{}-{}.png", stem, n))
+}
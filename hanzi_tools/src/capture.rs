@@ -0,0 +1,413 @@
+//! 解析串口日志里的截图
+//!
+//! 格式见 hanzi-demo 的 `src/screenshot.rs`：
+//!
+//! ```text
+//! @@SHOT 1 <宽> <高> <像素格式> <总字节数>
+//! @@D <序号> <base64 数据> <crc32>
+//! @@END <块数> <整张图的 crc32>
+//! ```
+//!
+//! 日志里夹着别的输出也没关系，只认 `@@` 开头的行。
+//! 坏块和缺块不会让整张图作废，对应位置留黑，问题记在 [`Capture::problems`] 里。
+
+use std::collections::BTreeMap;
+
+use crate::png::crc32;
+
+/// 支持的协议版本
+const VERSION: u32 = 1;
+
+/// 除了最后一块，每块都是这么多字节，和板子上一致
+const CHUNK_BYTES: usize = 48;
+
+/// 像素格式，字节都是大端
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    Rgb565,
+    Rgb888,
+}
+
+impl PixelFormat {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rgb565" => Some(Self::Rgb565),
+            "rgb888" => Some(Self::Rgb888),
+            _ => None,
+        }
+    }
+
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgb565 => 2,
+            Self::Rgb888 => 3,
+        }
+    }
+}
+
+/// 一张截图
+#[derive(Debug)]
+pub struct Capture {
+    pub width: u32,
+    pub height: u32,
+    pub format: PixelFormat,
+    /// 原始像素数据，缺的块是 0
+    pub data: Vec<u8>,
+    /// 校验失败、缺块之类的问题，空的说明完好
+    pub problems: Vec<String>,
+}
+
+impl Capture {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// 转成每像素 3 字节的 8 位 RGB
+    pub fn to_rgb(&self) -> Vec<u8> {
+        match self.format {
+            PixelFormat::Rgb888 => self.data.clone(),
+            PixelFormat::Rgb565 => self
+                .data
+                .chunks_exact(2)
+                .flat_map(|b| {
+                    let raw = u16::from_be_bytes([b[0], b[1]]);
+                    let r = (raw >> 11) as u8 & 0x1F;
+                    let g = (raw >> 5) as u8 & 0x3F;
+                    let b = raw as u8 & 0x1F;
+                    // 高位补到低位，白色还是 255
+                    [r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2]
+                })
+                .collect(),
+        }
+    }
+}
+
+// 正在收的一张图
+struct Pending {
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    total: usize,
+    chunks: BTreeMap<u32, Vec<u8>>,
+    problems: Vec<String>,
+}
+
+impl Pending {
+    fn chunk_count(&self) -> u32 {
+        self.total.div_ceil(CHUNK_BYTES) as u32
+    }
+
+    fn finish(mut self, end: Option<(u32, u32)>) -> Capture {
+        let mut data = vec![0u8; self.total];
+        let mut missing = 0;
+        for seq in 0..self.chunk_count() {
+            match self.chunks.get(&seq) {
+                Some(chunk) => {
+                    let start = seq as usize * CHUNK_BYTES;
+                    let end = (start + chunk.len()).min(self.total);
+                    data[start..end].copy_from_slice(&chunk[..end - start]);
+                }
+                None => missing += 1,
+            }
+        }
+        if missing > 0 {
+            self.problems.push(format!("缺了 {} 块数据", missing));
+        }
+
+        match end {
+            Some((count, crc)) => {
+                if count != self.chunk_count() {
+                    self.problems.push(format!(
+                        "结束行说有 {} 块，按大小应该是 {} 块",
+                        count,
+                        self.chunk_count()
+                    ));
+                }
+                if missing == 0 && crc32(&data) != crc {
+                    self.problems.push("整张图的校验和不对".to_string());
+                }
+            }
+            None => self
+                .problems
+                .push("没有结束行，日志可能被截断了".to_string()),
+        }
+
+        Capture {
+            width: self.width,
+            height: self.height,
+            format: self.format,
+            data,
+            problems: self.problems,
+        }
+    }
+}
+
+/// 从日志文本里找出所有截图
+pub fn parse(log: &str) -> Vec<Capture> {
+    let mut captures = Vec::new();
+    let mut pending: Option<Pending> = None;
+
+    for (index, line) in log.lines().enumerate() {
+        let line_no = index + 1;
+        // 前面可能粘着提示符之类的东西
+        let Some(start) = line.find("@@") else {
+            continue;
+        };
+        let mut fields = line[start..].split_whitespace();
+
+        match fields.next() {
+            Some("@@SHOT") => {
+                if let Some(prev) = pending.take() {
+                    captures.push(prev.finish(None));
+                }
+                match parse_header(fields) {
+                    Ok(header) => pending = Some(header),
+                    Err(e) => eprintln!("第 {} 行: 截图头解析失败: {}", line_no, e),
+                }
+            }
+            Some("@@D") => {
+                let Some(shot) = pending.as_mut() else {
+                    continue;
+                };
+                match parse_chunk(fields) {
+                    Ok((seq, data)) if seq < shot.chunk_count() => {
+                        shot.chunks.insert(seq, data);
+                    }
+                    Ok((seq, _)) => shot
+                        .problems
+                        .push(format!("第 {} 行: 块序号 {} 超出范围", line_no, seq)),
+                    Err(e) => shot.problems.push(format!("第 {} 行: {}", line_no, e)),
+                }
+            }
+            Some("@@END") => {
+                let Some(shot) = pending.take() else {
+                    continue;
+                };
+                let count = fields.next().and_then(|s| s.parse().ok());
+                let crc = fields.next().and_then(|s| u32::from_str_radix(s, 16).ok());
+                match (count, crc) {
+                    (Some(count), Some(crc)) => captures.push(shot.finish(Some((count, crc)))),
+                    _ => captures.push(shot.finish(None)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(shot) = pending {
+        captures.push(shot.finish(None));
+    }
+    captures
+}
+
+fn parse_header<'a>(mut fields: impl Iterator<Item = &'a str>) -> Result<Pending, String> {
+    let mut number = |name: &str| -> Result<usize, String> {
+        fields
+            .next()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| format!("缺少{}", name))
+    };
+
+    let version = number("版本")? as u32;
+    if version != VERSION {
+        return Err(format!("不认识的版本 {}", version));
+    }
+    let width = number("宽度")? as u32;
+    let height = number("高度")? as u32;
+    let format_name = fields.next().ok_or("缺少像素格式")?;
+    let format = PixelFormat::from_name(format_name)
+        .ok_or_else(|| format!("不认识的像素格式 {}", format_name))?;
+    let total = fields
+        .next()
+        .and_then(|s| s.parse::<usize>().ok())
+        .ok_or("缺少总字节数")?;
+
+    if total != width as usize * height as usize * format.bytes_per_pixel() {
+        return Err(format!(
+            "总字节数 {} 和 {}x{} {} 对不上",
+            total, width, height, format_name
+        ));
+    }
+
+    Ok(Pending {
+        width,
+        height,
+        format,
+        total,
+        chunks: BTreeMap::new(),
+        problems: Vec::new(),
+    })
+}
+
+fn parse_chunk<'a>(mut fields: impl Iterator<Item = &'a str>) -> Result<(u32, Vec<u8>), String> {
+    let seq = fields
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or("块序号不对")?;
+    let data = fields
+        .next()
+        .and_then(base64_decode)
+        .ok_or_else(|| format!("块 {} 的 base64 不对", seq))?;
+    let crc = fields
+        .next()
+        .and_then(|s| u32::from_str_radix(s, 16).ok())
+        .ok_or_else(|| format!("块 {} 缺少校验和", seq))?;
+
+    if crc32(&data) != crc {
+        return Err(format!("块 {} 校验和不对", seq));
+    }
+    Ok((seq, data))
+}
+
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+            b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let bytes = text.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(bytes.len() / 4 * 3);
+    for group in bytes.chunks(4) {
+        let pad = group.iter().rev().take_while(|&&c| c == b'=').count();
+        if pad > 2 {
+            return None;
+        }
+        let mut n = 0;
+        for &c in &group[..4 - pad] {
+            n = n << 6 | value(c)?;
+        }
+        n <<= 6 * pad;
+        out.extend_from_slice(&[(n >> 16) as u8, (n >> 8) as u8, n as u8][..3 - pad]);
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 和板子上 `screenshot::send` 一样分块、编码，拼成日志
+    fn frame(width: u32, height: u32, format: &str, data: &[u8]) -> Vec<String> {
+        let mut lines = vec![format!(
+            "@@SHOT {} {} {} {} {}",
+            VERSION,
+            width,
+            height,
+            format,
+            data.len()
+        )];
+        for (seq, chunk) in data.chunks(CHUNK_BYTES).enumerate() {
+            lines.push(format!(
+                "@@D {} {} {:08x}",
+                seq,
+                base64_encode(chunk),
+                crc32(chunk)
+            ));
+        }
+        lines.push(format!(
+            "@@END {} {:08x}",
+            data.len().div_ceil(CHUNK_BYTES),
+            crc32(data)
+        ));
+        lines
+    }
+
+    fn base64_encode(data: &[u8]) -> String {
+        const TABLE: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut out = String::new();
+        for group in data.chunks(3) {
+            let n = (group[0] as u32) << 16
+                | (group.get(1).copied().unwrap_or(0) as u32) << 8
+                | group.get(2).copied().unwrap_or(0) as u32;
+            for i in 0..4 {
+                if i <= group.len() {
+                    out.push(TABLE[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    // 5x7 的 RGB565 是 70 字节，最后一块不满，base64 带填充
+    fn sample() -> Vec<u8> {
+        (0..70u8).map(|i| i.wrapping_mul(37)).collect()
+    }
+
+    #[test]
+    fn parses_framed_capture_among_other_output() {
+        let data = sample();
+        let mut log = vec!["booting...".to_string()];
+        log.extend(frame(5, 7, "rgb565", &data));
+        // 提示符粘在行首也要认
+        log[1] = format!("> {}", log[1]);
+        log.push("done".to_string());
+
+        let captures = parse(&log.join("\r\n"));
+        assert_eq!(captures.len(), 1);
+        let shot = &captures[0];
+        assert!(shot.is_intact(), "{:?}", shot.problems);
+        assert_eq!((shot.width, shot.height), (5, 7));
+        assert_eq!(shot.format, PixelFormat::Rgb565);
+        assert_eq!(shot.data, data);
+    }
+
+    #[test]
+    fn corrupted_chunk_is_rejected_and_left_black() {
+        let data = sample();
+        let mut log = frame(5, 7, "rgb565", &data);
+        // 改掉第 0 块 base64 里的一个字符，长度不变、校验和对不上
+        let mut fields: Vec<String> = log[1].split(' ').map(str::to_string).collect();
+        let first = if fields[2].starts_with('A') { "B" } else { "A" };
+        fields[2].replace_range(..1, first);
+        log[1] = fields.join(" ");
+
+        let captures = parse(&log.join("\n"));
+        let shot = &captures[0];
+        assert!(!shot.is_intact());
+        assert!(shot.problems.iter().any(|p| p.contains("块 0 校验和不对")));
+        assert!(shot.problems.iter().any(|p| p.contains("缺了 1 块")));
+        assert!(shot.data[..CHUNK_BYTES].iter().all(|&b| b == 0));
+        assert_eq!(shot.data[CHUNK_BYTES..], data[CHUNK_BYTES..]);
+    }
+
+    #[test]
+    fn truncated_log_is_reported() {
+        let data = sample();
+        let mut log = frame(5, 7, "rgb565", &data);
+        log.pop();
+
+        let captures = parse(&log.join("\n"));
+        assert_eq!(captures[0].data, data);
+        assert!(
+            captures[0]
+                .problems
+                .iter()
+                .any(|p| p.contains("没有结束行"))
+        );
+    }
+
+    #[test]
+    fn rgb565_expands_to_full_range() {
+        let shot = Capture {
+            width: 2,
+            height: 1,
+            format: PixelFormat::Rgb565,
+            data: vec![0xFF, 0xFF, 0xF8, 0x00],
+            problems: Vec::new(),
+        };
+        assert_eq!(shot.to_rgb(), [255, 255, 255, 255, 0, 0]);
+    }
+}
@@ -0,0 +1,6 @@
+//! hanzi-demo 的主机端工具
+//!
+//! 板子上打出来的东西（截图等）在这里解开，工具本身在 `src/bin` 下。
+
+pub mod capture;
+pub mod png;
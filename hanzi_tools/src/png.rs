@@ -0,0 +1,222 @@
+//! 最简单的 PNG 编码
+//!
+//! 只写 8 位 RGB，压缩用 deflate 的不压缩块（stored），文件大一点但不用拉依赖。
+//! 128x128 的截图也就 48KB，发 bug 报告够用了。
+
+use std::io::{self, Write};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// stored 块一块最多 65535 字节
+const STORED_MAX: usize = 0xFFFF;
+
+/// 把 `rgb`（每像素 3 字节，按行排列）写成 PNG
+pub fn write_rgb<W: Write>(out: &mut W, width: u32, height: u32, rgb: &[u8]) -> io::Result<()> {
+    let stride = width as usize * 3;
+    if rgb.len() != stride * height as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "像素数据长度和宽高对不上",
+        ));
+    }
+
+    out.write_all(&SIGNATURE)?;
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // 位深 8，颜色类型 2（RGB），压缩、过滤、隔行都是 0
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(out, b"IHDR", &ihdr)?;
+
+    // 每行前面加一个过滤类型字节，0 表示不过滤
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in rgb.chunks(stride.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+
+    write_chunk(out, b"IEND", &[])
+}
+
+fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+
+    let mut crc = Crc32::new();
+    crc.update(kind);
+    crc.update(data);
+    out.write_all(&crc.value().to_be_bytes())
+}
+
+// zlib 头 + 一串 stored 块 + adler32
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(STORED_MAX).max(1);
+    let mut out = Vec::with_capacity(data.len() + blocks * 5 + 6);
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    let mut chunks = data.chunks(STORED_MAX).peekable();
+    if chunks.peek().is_none() {
+        // 空数据也要有一个结束块
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 字节内累加不会溢出，再取模
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    b << 16 | a
+}
+
+/// CRC-32（IEEE 802.3），PNG 和截图协议用的是同一个
+pub struct Crc32(u32);
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 ^= byte as u32;
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        !self.0
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 一次算完整段数据的 CRC-32
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 只认这里写出来的 PNG：校验每个块的 CRC，解开 stored 块，去掉行首的过滤字节
+    fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        assert_eq!(png[..8], SIGNATURE);
+        let mut rest = &png[8..];
+        let (mut width, mut height) = (0, 0);
+        let mut zlib = Vec::new();
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let kind = &rest[4..8];
+            let data = &rest[8..8 + len];
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            assert_eq!(crc32(&rest[4..8 + len]), crc);
+            match kind {
+                b"IHDR" => {
+                    width = u32::from_be_bytes(data[..4].try_into().unwrap());
+                    height = u32::from_be_bytes(data[4..8].try_into().unwrap());
+                    assert_eq!(data[8..], [8, 2, 0, 0, 0]);
+                }
+                b"IDAT" => zlib.extend_from_slice(data),
+                b"IEND" => assert!(data.is_empty()),
+                _ => panic!("多出来的块 {:?}", kind),
+            }
+            rest = &rest[12 + len..];
+        }
+
+        assert_eq!(zlib[..2], [0x78, 0x01]);
+        let mut pos = 2;
+        let mut raw = Vec::new();
+        loop {
+            let last = zlib[pos] & 1 == 1;
+            let len = u16::from_le_bytes([zlib[pos + 1], zlib[pos + 2]]);
+            let nlen = u16::from_le_bytes([zlib[pos + 3], zlib[pos + 4]]);
+            assert_eq!(len, !nlen);
+            pos += 5;
+            raw.extend_from_slice(&zlib[pos..pos + len as usize]);
+            pos += len as usize;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(zlib[pos..], adler32(&raw).to_be_bytes());
+
+        let stride = width as usize * 3;
+        let mut rgb = Vec::new();
+        for row in raw.chunks(stride + 1) {
+            assert_eq!(row[0], 0);
+            rgb.extend_from_slice(&row[1..]);
+        }
+        assert_eq!(rgb.len(), stride * height as usize);
+        (width, height, rgb)
+    }
+
+    fn encode(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+        let mut png = Vec::new();
+        write_rgb(&mut png, width, height, rgb).unwrap();
+        png
+    }
+
+    #[test]
+    fn crc32_matches_reference() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn small_image_round_trips() {
+        let rgb: Vec<u8> = (0..3 * 4 * 3).map(|i| i as u8 * 7).collect();
+        assert_eq!(decode(&encode(3, 4, &rgb)), (3, 4, rgb));
+    }
+
+    #[test]
+    fn large_image_spans_several_stored_blocks() {
+        // 160x160 有 76960 字节，要拆成两个 stored 块
+        let rgb: Vec<u8> = (0..160 * 160 * 3).map(|i| (i % 251) as u8).collect();
+        assert_eq!(decode(&encode(160, 160, &rgb)), (160, 160, rgb));
+    }
+
+    #[test]
+    fn empty_image_still_has_final_block() {
+        assert_eq!(decode(&encode(0, 0, &[])), (0, 0, Vec::new()));
+    }
+
+    #[test]
+    fn wrong_length_is_rejected() {
+        let mut png = Vec::new();
+        let error = write_rgb(&mut png, 2, 2, &[0; 11]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(png.is_empty());
+    }
+}